@@ -1 +1,5 @@
+pub mod coset_intt;
+pub mod coset_ntt;
+pub mod intt;
+pub mod ntt;
 pub mod primitive_root_of_unity;
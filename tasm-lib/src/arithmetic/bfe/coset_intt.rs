@@ -0,0 +1,206 @@
+use triton_vm::prelude::*;
+
+use crate::arithmetic::bfe::coset_ntt::CosetNtt;
+use crate::arithmetic::bfe::intt::Intt;
+use crate::prelude::*;
+
+/// The [inverse number-theoretic transform](Intt) of a list of
+/// [`BFieldElement`]s over a coset, performed in place. The inverse of
+/// [`CosetNtt`].
+///
+/// Interprets the list as the evaluations of a polynomial on the coset
+/// `offset·⟨ω⟩`, where `ω` is the primitive root of unity whose order is the
+/// list's length, and replaces them with the coefficients of that polynomial.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *x [offset: BFieldElement]
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// - the argument `*x` points to a properly [`BFieldCodec`]-encoded list
+/// - the length of the list is a power of two
+/// - the `offset` is not 0
+///
+/// ### Postconditions
+///
+/// - the list pointed to by `*x` contains the coefficients of the interpolant
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct CosetIntt;
+
+impl CosetIntt {
+    pub const ZERO_OFFSET_ERROR_ID: i128 = 650;
+}
+
+impl BasicSnippet for CosetIntt {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::List(Box::new(DataType::Bfe)), "*x".to_owned()),
+            (DataType::Bfe, "offset".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_arithmetic_bfe_coset_intt".to_owned()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let scale_by_powers = format!("{entrypoint}_scale_by_powers");
+
+        let intt = library.import(Box::new(Intt));
+
+        triton_asm!(
+            // BEFORE: _ *x offset
+            // AFTER:  _
+            {entrypoint}:
+                dup 0
+                push 0
+                eq
+                push 0
+                eq
+                assert error_id {Self::ZERO_OFFSET_ERROR_ID}
+
+                dup 1
+                call {intt}     // _ *x offset
+                invert
+                call {scale_by_powers}
+                return
+
+            {&CosetNtt::scale_by_powers_subroutine(&scale_by_powers)}
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::Zero;
+    use twenty_first::math::ntt::intt;
+
+    use super::*;
+    use crate::arithmetic::bfe::ntt::Ntt;
+    use crate::test_prelude::*;
+
+    impl Function for CosetIntt {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let offset = stack.pop().unwrap();
+            let list_pointer = stack.pop().unwrap();
+            let mut list = *Vec::<BFieldElement>::decode_from_memory(memory, list_pointer).unwrap();
+            assert!(list.len().is_power_of_two());
+            intt(&mut list);
+            CosetNtt::scale_by_powers(&mut list, offset.inverse());
+            encode_to_memory(memory, list_pointer, &list);
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut state = Ntt.pseudorandom_initial_state(seed, bench_case);
+            let offset = StdRng::from_seed(seed).random_range(1..BFieldElement::P);
+            state.stack.push(bfe!(offset));
+            state
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let offsets = [bfe!(1), bfe!(-1), BFieldElement::generator()];
+            Ntt.corner_case_initial_states()
+                .into_iter()
+                .cartesian_product(offsets)
+                .map(|(mut state, offset)| {
+                    state.stack.push(offset);
+                    state
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedFunction::new(CosetIntt).test();
+    }
+
+    #[proptest(cases = 20)]
+    fn coset_intt_is_inverse_of_coset_ntt(
+        #[strategy((0_u32..8).prop_flat_map(|log_2_len| prop::collection::vec(arb(), 1 << log_2_len)))]
+        list: Vec<BFieldElement>,
+        #[strategy(arb())]
+        #[filter(!#offset.is_zero())]
+        offset: BFieldElement,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let FunctionInitialState {
+            mut stack,
+            mut memory,
+        } = Ntt::initial_state(list_pointer, &list, &[offset]);
+        CosetNtt.rust_shadow(&mut stack, &mut memory);
+        stack.extend([list_pointer, offset]);
+        CosetIntt.rust_shadow(&mut stack, &mut memory);
+
+        let round_trip = *Vec::<BFieldElement>::decode_from_memory(&memory, list_pointer).unwrap();
+        prop_assert_eq!(list, round_trip);
+    }
+
+    #[proptest(cases = 20)]
+    fn zero_offset_crashes_vm(
+        #[strategy((0_u32..8).prop_flat_map(|log_2_len| prop::collection::vec(arb(), 1 << log_2_len)))]
+        list: Vec<BFieldElement>,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        test_assertion_failure(
+            &ShadowedFunction::new(CosetIntt),
+            Ntt::initial_state(list_pointer, &list, &[bfe!(0)]).into(),
+            &[CosetIntt::ZERO_OFFSET_ERROR_ID],
+        );
+    }
+
+    #[proptest(cases = 20)]
+    fn list_length_not_power_of_two_crashes_vm(
+        #[strategy(prop::collection::vec(arb(), 0..100))]
+        #[filter(!#list.len().is_power_of_two())]
+        list: Vec<BFieldElement>,
+        #[strategy(arb())]
+        #[filter(!#offset.is_zero())]
+        offset: BFieldElement,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        test_assertion_failure(
+            &ShadowedFunction::new(CosetIntt),
+            Ntt::initial_state(list_pointer, &list, &[offset]).into(),
+            &[140, 141],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::arithmetic::bfe::ntt::Ntt;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(CosetIntt).bench();
+    }
+
+    #[ignore = "Takes a long time. Make sure to run with `--release`."]
+    #[test]
+    fn benchmark_all_lengths() {
+        Ntt::benchmark_all_lengths(&CosetIntt, |length| {
+            let list = (0..length as u64).map(BFieldElement::new).collect_vec();
+            Ntt::initial_state(bfe!(1), &list, &[BFieldElement::generator()])
+        });
+    }
+}
@@ -0,0 +1,241 @@
+use triton_vm::prelude::*;
+
+use crate::arithmetic::bfe::ntt::Ntt;
+use crate::prelude::*;
+
+/// The [number-theoretic transform](Ntt) of a list of [`BFieldElement`]s over
+/// a coset, performed in place.
+///
+/// Interprets the list as the coefficients of a polynomial and replaces them
+/// with the evaluations of that polynomial on the coset `offset·⟨ω⟩`, where `ω`
+/// is the primitive root of unity whose order is the list's length.
+///
+/// See also [`CosetIntt`](super::coset_intt::CosetIntt) for the inverse
+/// transform.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *x [offset: BFieldElement]
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// - the argument `*x` points to a properly [`BFieldCodec`]-encoded list
+/// - the length of the list is a power of two
+///
+/// ### Postconditions
+///
+/// - the list pointed to by `*x` contains the evaluations of the polynomial
+///   on the coset
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct CosetNtt;
+
+impl CosetNtt {
+    /// Generate a subroutine with the given label that multiplies the element
+    /// with index `i` of a list of [`BFieldElement`]s by `offset^i`.
+    ///
+    /// ```text
+    /// BEFORE: _ *x offset
+    /// AFTER:  _
+    /// ```
+    pub(crate) fn scale_by_powers_subroutine(label: &str) -> Vec<LabelledInstruction> {
+        let scale_loop = format!("{label}_loop");
+
+        triton_asm!(
+            {label}:
+                pick 1
+                read_mem 1
+                addi 2          // _ offset n *x[0]
+                dup 0
+                pick 2
+                add             // _ offset *x[0] *end
+                pick 1
+                push 1          // _ offset *end *x[0] 1
+                call {scale_loop}
+                pop 4
+                return
+
+            // INVARIANT: _ offset *end *x[i] offset^i
+            {scale_loop}:
+                dup 1
+                dup 3
+                eq
+                skiz return     // _ offset *end *x[i] offset^i
+
+                dup 1
+                read_mem 1
+                pop 1
+                dup 1
+                mul
+                dup 2
+                write_mem 1
+                pop 1           // _ offset *end *x[i] offset^i
+
+                dup 3
+                mul
+                pick 1
+                addi 1
+                place 1         // _ offset *end *x[i+1] offset^(i+1)
+                recurse
+        )
+    }
+}
+
+impl BasicSnippet for CosetNtt {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::List(Box::new(DataType::Bfe)), "*x".to_owned()),
+            (DataType::Bfe, "offset".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_arithmetic_bfe_coset_ntt".to_owned()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let scale_by_powers = format!("{entrypoint}_scale_by_powers");
+
+        let ntt = library.import(Box::new(Ntt));
+
+        triton_asm!(
+            // BEFORE: _ *x offset
+            // AFTER:  _
+            {entrypoint}:
+                dup 1
+                pick 1          // _ *x *x offset
+                call {scale_by_powers}
+                call {ntt}
+                return
+
+            {&Self::scale_by_powers_subroutine(&scale_by_powers)}
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twenty_first::math::ntt::ntt;
+    use twenty_first::math::polynomial::Polynomial;
+    use twenty_first::math::traits::PrimitiveRootOfUnity;
+
+    use super::*;
+    use crate::test_prelude::*;
+
+    impl CosetNtt {
+        /// Multiply the element with index `i` by `offset^i`.
+        pub(crate) fn scale_by_powers(list: &mut [BFieldElement], offset: BFieldElement) {
+            let mut power = bfe!(1);
+            for element in list {
+                *element *= power;
+                power *= offset;
+            }
+        }
+    }
+
+    impl Function for CosetNtt {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let offset = stack.pop().unwrap();
+            let list_pointer = stack.pop().unwrap();
+            let mut list = *Vec::<BFieldElement>::decode_from_memory(memory, list_pointer).unwrap();
+            assert!(list.len().is_power_of_two());
+            Self::scale_by_powers(&mut list, offset);
+            ntt(&mut list);
+            encode_to_memory(memory, list_pointer, &list);
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut state = Ntt.pseudorandom_initial_state(seed, bench_case);
+            state.stack.push(StdRng::from_seed(seed).random());
+            state
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let offsets = [bfe!(0), bfe!(1), bfe!(-1), BFieldElement::generator()];
+            Ntt.corner_case_initial_states()
+                .into_iter()
+                .cartesian_product(offsets)
+                .map(|(mut state, offset)| {
+                    state.stack.push(offset);
+                    state
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedFunction::new(CosetNtt).test();
+    }
+
+    #[proptest(cases = 20)]
+    fn rust_shadow_evaluates_polynomial_on_coset(
+        #[strategy(0_u32..8)] log_2_length: u32,
+        #[strategy(prop::collection::vec(arb(), 1 << #log_2_length))] list: Vec<BFieldElement>,
+        #[strategy(arb())] offset: BFieldElement,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let FunctionInitialState {
+            mut stack,
+            mut memory,
+        } = Ntt::initial_state(list_pointer, &list, &[offset]);
+        CosetNtt.rust_shadow(&mut stack, &mut memory);
+        let codeword = *Vec::<BFieldElement>::decode_from_memory(&memory, list_pointer).unwrap();
+
+        let polynomial = Polynomial::new(list);
+        let root_of_unity = BFieldElement::primitive_root_of_unity(1 << log_2_length).unwrap();
+        let domain = (0..1 << log_2_length).map(|i| offset * root_of_unity.mod_pow(i));
+        let evaluations = domain.map(|x| polynomial.evaluate::<_, BFieldElement>(x));
+        prop_assert_eq!(evaluations.collect_vec(), codeword);
+    }
+
+    #[proptest(cases = 20)]
+    fn list_length_not_power_of_two_crashes_vm(
+        #[strategy(prop::collection::vec(arb(), 0..100))]
+        #[filter(!#list.len().is_power_of_two())]
+        list: Vec<BFieldElement>,
+        #[strategy(arb())] offset: BFieldElement,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        test_assertion_failure(
+            &ShadowedFunction::new(CosetNtt),
+            Ntt::initial_state(list_pointer, &list, &[offset]).into(),
+            &[140, 141],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(CosetNtt).bench();
+    }
+
+    #[ignore = "Takes a long time. Make sure to run with `--release`."]
+    #[test]
+    fn benchmark_all_lengths() {
+        Ntt::benchmark_all_lengths(&CosetNtt, |length| {
+            let list = (0..length as u64).map(BFieldElement::new).collect_vec();
+            Ntt::initial_state(bfe!(1), &list, &[BFieldElement::generator()])
+        });
+    }
+}
@@ -0,0 +1,209 @@
+use triton_vm::prelude::*;
+
+use crate::arithmetic::bfe::ntt::Ntt;
+use crate::prelude::*;
+
+/// The [inverse number-theoretic transform][intt] (INTT) of a list of
+/// [`BFieldElement`]s, performed in place.
+///
+/// Uses the identity `intt(x) = reverse(ntt(x)) / n`, where `reverse` reverses
+/// all elements but the first, and `n` is the list's length.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *x
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// - the argument `*x` points to a properly [`BFieldCodec`]-encoded list
+/// - the length of the list is a power of two
+///
+/// ### Postconditions
+///
+/// - the list pointed to by `*x` contains the INTT of its original content
+///
+/// [intt]: twenty_first::math::ntt::intt
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Intt;
+
+impl BasicSnippet for Intt {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::List(Box::new(DataType::Bfe)), "*x".to_owned())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_arithmetic_bfe_intt".to_owned()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let reverse_and_scale = format!("{entrypoint}_reverse_and_scale");
+
+        let ntt = library.import(Box::new(Ntt));
+
+        // BEFORE: _ n_inv *x[i]
+        // AFTER:  _ n_inv
+        let scale_element = triton_asm!(
+            dup 0
+            read_mem 1
+            pop 1
+            dup 2
+            mul
+            pick 1
+            write_mem 1
+            pop 1
+        );
+
+        triton_asm!(
+            // BEFORE: _ *x
+            // AFTER:  _
+            {entrypoint}:
+                dup 0
+                call {ntt}
+                dup 0
+                read_mem 1
+                pop 1           // _ *x n
+                dup 0
+                invert          // _ *x n n_inv
+
+                dup 2
+                addi 1          // _ *x n n_inv *x[0]
+                push 2
+                dup 3
+                div_mod
+                pop 1
+                dup 1
+                add             // _ *x n n_inv *x[0] *x[n/2]
+                pick 1
+                addi 1          // _ *x n n_inv *x[n/2] *x[1]
+                dup 4
+                dup 4
+                add             // _ *x n n_inv *x[n/2] *x[1] *x[n-1]
+                call {reverse_and_scale}
+                pop 2           // _ *x n n_inv *x[n/2]
+
+                /* the loop skips the elements at indices 0 and n/2 */
+                {&scale_element}
+                                // _ *x n n_inv
+                dup 2
+                addi 1
+                {&scale_element}
+                                // _ *x n n_inv
+                pop 3
+                return
+
+            // Swaps the elements at indices `i` and `n - i` for all `i` in
+            // `1..n/2`, scaling both by `n_inv`.
+            //
+            // INVARIANT: _ n_inv *x[n/2] *x[i] *x[n-i]
+            {reverse_and_scale}:
+                dup 0
+                dup 3
+                eq
+                skiz return     // _ n_inv *x[n/2] *x[i] *x[n-i]
+
+                dup 0
+                read_mem 1
+                pop 1
+                dup 4
+                mul             // _ n_inv *x[n/2] *x[i] *x[n-i] (x[n-i]·n_inv)
+                dup 2
+                read_mem 1
+                pop 1
+                dup 5
+                mul             // _ n_inv *x[n/2] *x[i] *x[n-i] (x[n-i]·n_inv) (x[i]·n_inv)
+                dup 2
+                write_mem 1
+                pop 1           // _ n_inv *x[n/2] *x[i] *x[n-i] (x[n-i]·n_inv)
+                dup 2
+                write_mem 1
+                pop 1           // _ n_inv *x[n/2] *x[i] *x[n-i]
+
+                addi -1
+                pick 1
+                addi 1
+                place 1         // _ n_inv *x[n/2] *x[i+1] *x[n-i-1]
+                recurse
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twenty_first::math::ntt::intt;
+
+    use super::*;
+    use crate::test_prelude::*;
+
+    impl Function for Intt {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let list_pointer = stack.pop().unwrap();
+            let mut list = *Vec::<BFieldElement>::decode_from_memory(memory, list_pointer).unwrap();
+            assert!(list.len().is_power_of_two());
+            intt(&mut list);
+            encode_to_memory(memory, list_pointer, &list);
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            Ntt.pseudorandom_initial_state(seed, bench_case)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            Ntt.corner_case_initial_states()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedFunction::new(Intt).test();
+    }
+
+    #[proptest(cases = 20)]
+    fn list_length_not_power_of_two_crashes_vm(
+        #[strategy(prop::collection::vec(arb(), 0..100))]
+        #[filter(!#list.len().is_power_of_two())]
+        list: Vec<BFieldElement>,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        test_assertion_failure(
+            &ShadowedFunction::new(Intt),
+            Ntt::initial_state(list_pointer, &list, &[]).into(),
+            &[140, 141],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(Intt).bench();
+    }
+
+    #[ignore = "Takes a long time. Make sure to run with `--release`."]
+    #[test]
+    fn benchmark_all_lengths() {
+        Ntt::benchmark_all_lengths(&Intt, |length| {
+            let list = (0..length as u64).map(BFieldElement::new).collect_vec();
+            Ntt::initial_state(bfe!(1), &list, &[])
+        });
+    }
+}
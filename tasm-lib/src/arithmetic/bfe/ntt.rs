@@ -0,0 +1,391 @@
+use triton_vm::prelude::*;
+
+use crate::arithmetic::bfe::primitive_root_of_unity::PrimitiveRootOfUnity;
+use crate::prelude::*;
+
+/// The [number-theoretic transform][ntt] (NTT) of a list of
+/// [`BFieldElement`]s, performed in place.
+///
+/// Interprets the list as the coefficients of a polynomial and replaces them
+/// with the evaluations of that polynomial on the powers of the
+/// [primitive root of unity](PrimitiveRootOfUnity) whose order is the list's
+/// length.
+///
+/// See also [`Intt`](super::intt::Intt) for the inverse transform, and
+/// [`XfeNtt`](crate::verifier::xfe_ntt::XfeNtt) for lists of
+/// [`XFieldElement`]s.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *x
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// - the argument `*x` points to a properly [`BFieldCodec`]-encoded list
+/// - the length of the list is a power of two
+///
+/// ### Postconditions
+///
+/// - the list pointed to by `*x` contains the NTT of its original content
+///
+/// [ntt]: twenty_first::math::ntt::ntt
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Ntt;
+
+impl Ntt {
+    /// The inverse of 2 in the field, used to halve even numbers.
+    const TWO_INVERSE: BFieldElement = BFieldElement::new(9223372034707292161);
+}
+
+impl BasicSnippet for Ntt {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::List(Box::new(DataType::Bfe)), "*x".to_owned())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_arithmetic_bfe_ntt".to_owned()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let bitreverse_loop = format!("{entrypoint}_bitreverse_loop");
+        let reverse_increment = format!("{entrypoint}_reverse_increment");
+        let swap = format!("{entrypoint}_swap");
+        let level_loop = format!("{entrypoint}_level_loop");
+        let block_loop = format!("{entrypoint}_block_loop");
+        let butterfly_loop = format!("{entrypoint}_butterfly_loop");
+
+        let primitive_root_of_unity = library.import(Box::new(PrimitiveRootOfUnity));
+
+        triton_asm!(
+            // BEFORE: _ *x
+            // AFTER:  _
+            {entrypoint}:
+                dup 0
+                read_mem 1
+                pop 1           // _ *x n
+                push 0
+                dup 1
+                call {primitive_root_of_unity}
+                                // _ *x n ω
+
+                push 1
+                push 0          // _ *x n ω 1 0
+                call {bitreverse_loop}
+                pop 2           // _ *x n ω
+
+                dup 2
+                addi 1
+                dup 2
+                add             // _ *x n ω *end
+                push 1          // _ *x n ω *end 1
+                call {level_loop}
+                pop 5
+                return
+
+            // Permutes the list such that the element with index `i` ends up at
+            // index `bitreverse(i)`. Maintains `j = bitreverse(i - 1)`.
+            //
+            // INVARIANT: _ *x n ω i j
+            {bitreverse_loop}:
+                dup 1
+                dup 4
+                eq
+                skiz return     // _ *x n ω i j
+
+                dup 3
+                push {Self::TWO_INVERSE}
+                mul             // _ *x n ω i j (n/2)
+                call {reverse_increment}
+                add             // _ *x n ω i j'
+
+                dup 0
+                dup 2
+                lt              // _ *x n ω i j' (i < j')
+                skiz call {swap}
+
+                pick 1
+                addi 1
+                place 1         // _ *x n ω (i+1) j'
+                recurse
+
+            // Increments a bit-reversed number by clearing its leading ones.
+            // The caller has to set the returned bit.
+            //
+            // BEFORE: _ j (n/2)
+            // AFTER:  _ j' bit
+            {reverse_increment}:
+                dup 0
+                dup 2
+                and
+                push 0
+                eq
+                skiz return     // _ j bit
+
+                dup 0
+                push -1
+                mul
+                pick 2
+                add
+                place 1         // _ (j - bit) bit
+                push {Self::TWO_INVERSE}
+                mul             // _ (j - bit) (bit/2)
+                recurse
+
+            // BEFORE: _ *x n ω i j
+            // AFTER:  _ *x n ω i j
+            {swap}:
+                dup 4
+                addi 1
+                dup 0
+                dup 3
+                add             // _ *x n ω i j *x[0] *x[i]
+                pick 1
+                dup 2
+                add             // _ *x n ω i j *x[i] *x[j]
+
+                read_mem 1
+                addi 1          // _ *x n ω i j *x[i] x_j *x[j]
+                pick 2
+                read_mem 1
+                addi 1          // _ *x n ω i j x_j *x[j] x_i *x[i]
+                pick 3
+                pick 1
+                write_mem 1
+                pop 1           // _ *x n ω i j *x[j] x_i
+                pick 1
+                write_mem 1
+                pop 1           // _ *x n ω i j
+                return
+
+            // INVARIANT: _ *x n ω *end m
+            {level_loop}:
+                dup 0
+                dup 4
+                eq
+                skiz return     // _ *x n ω *end m
+
+                dup 0
+                push 2
+                mul
+                dup 4
+                div_mod
+                pop 1           // _ *x n ω *end m (n / 2m)
+                dup 3
+                pow             // _ *x n ω *end m ω_m
+
+                dup 5
+                addi 1          // _ *x n ω *end m ω_m *x[0]
+                call {block_loop}
+                pop 2           // _ *x n ω *end m
+
+                push 2
+                mul             // _ *x n ω *end (2m)
+                recurse
+
+            // INVARIANT: _ *end m ω_m *x[k]
+            {block_loop}:
+                dup 0
+                dup 4
+                eq
+                skiz return     // _ *end m ω_m *x[k]
+
+                dup 2
+                dup 1
+                add             // _ *end m ω_m *x[k] *x[k+m]
+                dup 0
+                pick 2
+                push 1          // _ *end m ω_m *x[k+m] *x[k+m] *x[k] 1
+                call {butterfly_loop}
+                pop 2           // _ *end m ω_m *x[k+m] *x[k+2m]
+                pick 1
+                pop 1           // _ *end m ω_m *x[k+2m]
+                recurse
+
+            // INVARIANT: _ ω_m *x[k+m] *x[k+j+m] *x[k+j] w
+            {butterfly_loop}:
+                dup 1
+                dup 4
+                eq
+                skiz return     // _ ω_m *x[k+m] *x[k+j+m] *x[k+j] w
+
+                dup 2
+                read_mem 1
+                pop 1
+                dup 1
+                mul             // _ ω_m *x[k+m] *x[k+j+m] *x[k+j] w v
+                dup 2
+                read_mem 1
+                pop 1           // _ ω_m *x[k+m] *x[k+j+m] *x[k+j] w v u
+
+                dup 1
+                dup 1
+                add
+                dup 4
+                write_mem 1
+                pop 1           // _ ω_m *x[k+m] *x[k+j+m] *x[k+j] w v u
+
+                pick 1
+                push -1
+                mul
+                add
+                dup 3
+                write_mem 1
+                pop 1           // _ ω_m *x[k+m] *x[k+j+m] *x[k+j] w
+
+                dup 4
+                mul             // _ ω_m *x[k+m] *x[k+j+m] *x[k+j] w'
+                pick 1
+                addi 1
+                place 1
+                pick 2
+                addi 1
+                place 2         // _ ω_m *x[k+m] *x[k+j+m+1] *x[k+j+1] w'
+                recurse
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use twenty_first::math::ntt::ntt;
+
+    use super::*;
+    use crate::empty_stack;
+    use crate::linker::execute_bench;
+    use crate::snippet_bencher::write_benchmarks;
+    use crate::snippet_bencher::NamedBenchmarkResult;
+    use crate::test_prelude::*;
+
+    impl Ntt {
+        /// Encode the list to memory at `list_pointer`, and set up a stack
+        /// containing the pointer followed by the `args`.
+        pub(crate) fn initial_state(
+            list_pointer: BFieldElement,
+            list: &[BFieldElement],
+            args: &[BFieldElement],
+        ) -> FunctionInitialState {
+            let mut memory = HashMap::default();
+            encode_to_memory(&mut memory, list_pointer, &list.to_vec());
+            let stack = [empty_stack(), vec![list_pointer], args.to_vec()].concat();
+
+            FunctionInitialState { stack, memory }
+        }
+
+        /// The list lengths used in [`Self::benchmark_all_lengths`].
+        pub(crate) fn benchmark_lengths() -> impl Iterator<Item = usize> {
+            (1..=14).map(|log_2_length| 1 << log_2_length)
+        }
+
+        /// Benchmark the snippet once for each of the
+        /// [`benchmark_lengths`](Self::benchmark_lengths).
+        pub(crate) fn benchmark_all_lengths<F: Function>(
+            snippet: &F,
+            initial_state: impl Fn(usize) -> FunctionInitialState,
+        ) {
+            let program = snippet.link_for_isolated_run();
+            for length in Self::benchmark_lengths() {
+                let FunctionInitialState { stack, memory } = initial_state(length);
+                let nondeterminism = NonDeterminism::default().with_ram(memory);
+                let benchmark = execute_bench(&program, &stack, vec![], nondeterminism, None);
+                let benchmark = NamedBenchmarkResult {
+                    name: format!("{}_length_{length}", snippet.entrypoint()),
+                    benchmark_result: benchmark,
+                    case: BenchmarkCase::CommonCase,
+                };
+                write_benchmarks(vec![benchmark]);
+            }
+        }
+    }
+
+    impl Function for Ntt {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let list_pointer = stack.pop().unwrap();
+            let mut list = *Vec::<BFieldElement>::decode_from_memory(memory, list_pointer).unwrap();
+            assert!(list.len().is_power_of_two());
+            ntt(&mut list);
+            encode_to_memory(memory, list_pointer, &list);
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let length = match bench_case {
+                Some(BenchmarkCase::CommonCase) => 1 << 8,
+                Some(BenchmarkCase::WorstCase) => 1 << 9,
+                None => 1 << rng.random_range(0..=9),
+            };
+            let list = (0..length).map(|_| rng.random()).collect_vec();
+
+            Self::initial_state(rng.random(), &list, &[])
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let list_pointer = bfe!(1);
+            let one_hot = |length, index| {
+                let mut list = bfe_vec![0; length];
+                list[index] = bfe!(1);
+                Self::initial_state(list_pointer, &list, &[])
+            };
+
+            (0..5)
+                .map(|log_2_length| 1 << log_2_length)
+                .flat_map(|length| (0..length).map(move |index| (length, index)))
+                .map(|(length, index)| one_hot(length, index))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedFunction::new(Ntt).test();
+    }
+
+    #[proptest(cases = 20)]
+    fn list_length_not_power_of_two_crashes_vm(
+        #[strategy(prop::collection::vec(arb(), 0..100))]
+        #[filter(!#list.len().is_power_of_two())]
+        list: Vec<BFieldElement>,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        test_assertion_failure(
+            &ShadowedFunction::new(Ntt),
+            Ntt::initial_state(list_pointer, &list, &[]).into(),
+            &[140, 141],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(Ntt).bench();
+    }
+
+    #[ignore = "Takes a long time. Make sure to run with `--release`."]
+    #[test]
+    fn benchmark_all_lengths() {
+        Ntt::benchmark_all_lengths(&Ntt, |length| {
+            let list = (0..length as u64).map(BFieldElement::new).collect_vec();
+            Ntt::initial_state(bfe!(1), &list, &[])
+        });
+    }
+}
@@ -1,2 +1,10 @@
+pub mod abs;
+pub mod div_mod;
+pub mod from_i32;
+pub mod from_i64;
 pub mod lt;
+pub mod neg;
+pub mod safe_add;
+pub mod safe_mul;
+pub mod safe_sub;
 pub mod shift_right;
@@ -0,0 +1,115 @@
+use triton_vm::prelude::*;
+
+use crate::arithmetic::i128::neg::Neg;
+use crate::prelude::*;
+
+/// The [absolute value][abs] of an `i128`. Crashes the VM on overflow, _i.e._,
+/// if the argument is [`i128::MIN`].
+///
+/// # Behavior
+///
+/// ```text
+/// BEFORE: _ [arg: i128]
+/// AFTER:  _ [|arg|: i128]
+/// ```
+///
+/// # Preconditions
+///
+///  - `arg` consists of 4 `u32`s
+///  - `arg` is not [`i128::MIN`]
+///
+/// # Postconditions
+///
+///  - the output is the absolute value of `arg`
+///  - the output consists of 4 `u32`s
+///
+/// [abs]: i128::abs
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Abs;
+
+impl BasicSnippet for Abs {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::I128, "arg".to_string())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::I128, "abs".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_arithmetic_i128_abs".to_string()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let neg = library.import(Box::new(Neg));
+
+        triton_asm!(
+            // BEFORE: _ [arg]
+            // AFTER:  _ [|arg|]
+            {self.entrypoint()}:
+                {&Neg::is_negative_code()}
+                skiz call {neg}
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arithmetic::i128::safe_add::SafeAdd;
+    use crate::test_prelude::*;
+
+    impl Closure for Abs {
+        type Args = i128;
+
+        fn rust_shadow(&self, stack: &mut Vec<BFieldElement>) {
+            let arg = pop_encodable::<Self::Args>(stack);
+            push_encodable(stack, &arg.checked_abs().unwrap());
+        }
+
+        fn pseudorandom_args(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> Self::Args {
+            match bench_case {
+                Some(BenchmarkCase::CommonCase) => 1 << 64,
+                Some(BenchmarkCase::WorstCase) => -(1 << 64),
+                None => StdRng::from_seed(seed).random_range(i128::MIN + 1..=i128::MAX),
+            }
+        }
+
+        fn corner_case_args(&self) -> Vec<Self::Args> {
+            SafeAdd::corner_case_points()
+                .into_iter()
+                .filter(|&arg| arg != i128::MIN)
+                .collect()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedClosure::new(Abs).test();
+    }
+
+    #[test]
+    fn absolute_value_of_min_crashes_vm() {
+        test_assertion_failure(
+            &ShadowedClosure::new(Abs),
+            InitVmState::with_stack(Abs.set_up_test_stack(i128::MIN)),
+            &[Neg::OVERFLOW_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedClosure::new(Abs).bench();
+    }
+}
@@ -0,0 +1,325 @@
+use itertools::Itertools;
+use triton_vm::prelude::*;
+
+use crate::arithmetic::i128::neg::Neg;
+use crate::arithmetic::i128::safe_mul::SafeMul;
+use crate::arithmetic::u128::lt::Lt as LtU128;
+use crate::prelude::*;
+
+/// Integer division with remainder for `i128`s, following Rust's semantics:
+/// the quotient is rounded towards zero, and the remainder has the sign of the
+/// numerator. See also [`i128::checked_div`] and [`i128::checked_rem`].
+///
+/// # Behavior
+///
+/// ```text
+/// BEFORE: _ [numerator: i128] [denominator: i128]
+/// AFTER:  _ [quotient: i128] [remainder: i128]
+/// ```
+///
+/// # Preconditions
+///
+///  - `numerator` and `denominator` consist of 4 `u32`s
+///  - the `denominator` is not 0
+///  - the division does not overflow, _i.e._, it is not `i128::MIN / -1`
+///
+/// # Postconditions
+///
+///  - `numerator == quotient · denominator + remainder`
+///  - `|remainder| < |denominator|`
+///  - the `remainder` is 0 or has the same sign as the `numerator`
+///  - the output consists of 8 `u32`s
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct DivMod;
+
+impl DivMod {
+    pub const DIVISION_BY_ZERO_ERROR_ID: i128 = 620;
+    pub const OVERFLOW_ERROR_ID: i128 = 621;
+}
+
+impl BasicSnippet for DivMod {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        ["numerator", "denominator"]
+            .map(|name| (DataType::I128, name.to_string()))
+            .to_vec()
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        ["quotient", "remainder"]
+            .map(|name| (DataType::I128, name.to_string()))
+            .to_vec()
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_arithmetic_i128_div_mod".to_string()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let wrapping_neg = format!("{entrypoint}_wrapping_neg");
+        let unsigned_div_mod = format!("{entrypoint}_unsigned");
+        let skip_leading_zero_limbs = format!("{entrypoint}_skip_leading_zero_limbs");
+        let long_division_loop = format!("{entrypoint}_long_division_loop");
+        let subtract_denominator = format!("{entrypoint}_subtract_denominator");
+
+        let lt_u128 = library.import(Box::new(LtU128));
+
+        // Doubles the 4-limb value on top of the stack, adding the carry-in
+        // `c0`, and leaves the carry-out `c4` on top of the stack.
+        //
+        // BEFORE: _ a3 a2 a1 a0 c0
+        // AFTER:  _ b3 b2 b1 b0 c4
+        let double_limb = triton_asm!(pick 1 dup 0 add add split place 4);
+        let double_with_carry = vec![double_limb; 4].concat();
+
+        // Subtracts limb `i` of the denominator from limb `i` of the remainder,
+        // taking the carry into account. See also
+        // [`Neg::wrapping_negation_code`].
+        let subtract_limb = |i: usize| {
+            triton_asm!(
+                pick 1 add
+                dup {8 + i} push -1 mul add
+                push {u32::MAX} add
+                split
+                place 4
+            )
+        };
+        let subtract_limbs = (0..4).flat_map(subtract_limb).collect_vec();
+
+        triton_asm!(
+            // BEFORE: _ [n] [d]
+            // AFTER:  _ [q] [r]
+            {entrypoint}:
+                /* d != 0 */
+                dup 3 dup 3 dup 3 dup 3
+                add add add
+                push 0 eq
+                push 0 eq
+                assert error_id {Self::DIVISION_BY_ZERO_ERROR_ID}
+
+                /* record signs */
+                dup 7 push {i32::MAX} lt
+                dup 4 push {i32::MAX} lt
+                // _ [n] [d] (n < 0) (d < 0)
+
+                dup 1 dup 1 xor
+                place 10
+                // _ (q < 0) [n] [d] (n < 0) (d < 0)
+
+                pick 1 dup 0 place 11
+                place 9
+                // _ (n < 0) (q < 0) (n < 0) [n] [d] (d < 0)
+
+                /* divide absolute values */
+                skiz call {wrapping_neg}
+                pick 7 pick 7 pick 7 pick 7
+                pick 8
+                skiz call {wrapping_neg}
+                pick 7 pick 7 pick 7 pick 7
+                // _ (n < 0) (q < 0) [|n|] [|d|]
+
+                call {unsigned_div_mod}
+                // _ (n < 0) (q < 0) [|q|] [|r|]
+
+                /* restore signs */
+                pick 9
+                skiz call {wrapping_neg}
+                // _ (q < 0) [|q|] [r]
+
+                pick 7 pick 7 pick 7 pick 7
+                pick 8
+                // _ [r] [|q|] (q < 0)
+
+                {&SafeMul::assert_magnitude_fits_code(Self::OVERFLOW_ERROR_ID)}
+                skiz call {wrapping_neg}
+                // _ [r] [q]
+
+                pick 7 pick 7 pick 7 pick 7
+                // _ [q] [r]
+
+                return
+
+            // BEFORE: _ [arg: i128]
+            // AFTER:  _ [arg.wrapping_neg(): i128]
+            {wrapping_neg}:
+                {&Neg::wrapping_negation_code()}
+                return
+
+            // Binary long division. The numerator is shifted out of the
+            // register `nq` bit by bit while the quotient is shifted in.
+            //
+            // BEFORE: _ [n: u128] [d: u128]
+            // AFTER:  _ [q: u128] [r: u128]
+            {unsigned_div_mod}:
+                push 128 place 8
+                push 0 push 0 push 0 push 0
+                pick 11 pick 11 pick 11 pick 11
+                // _ i [d] [r] [nq]
+
+                call {skip_leading_zero_limbs}
+                call {long_division_loop}
+                // _ 0 [d] [r] [q]
+
+                pick 12 pick 12 pick 12 pick 12 pick 12
+                pop 5
+                pick 7 pick 7 pick 7 pick 7
+                // _ [q] [r]
+
+                return
+
+            // BEFORE: _ i [d] [r] [nq]
+            // AFTER:  _ i' [d] [r] [nq']
+            {skip_leading_zero_limbs}:
+                dup 3 push 0 eq
+                dup 13 push 0 eq push 0 eq
+                mul
+                // _ i [d] [r] [nq] (nq_3 == 0 && i != 0)
+
+                push 0 eq
+                skiz return
+
+                pick 3 pop 1 push 0
+                // _ i [d] [r] [nq << 32]
+
+                pick 12 addi -32 place 12
+                recurse
+
+            // BEFORE: _ i [d] [r] [nq]
+            // AFTER:  _ 0 [d] [r] [q]
+            {long_division_loop}:
+                dup 12 push 0 eq
+                skiz return
+
+                pick 12 addi -1 place 12
+
+                push 0
+                {&double_with_carry}
+                // _ i [d] [r] [nq << 1] msb
+
+                pick 8 pick 8 pick 8 pick 8
+                pick 4
+                {&double_with_carry}
+                // _ i [d] [nq << 1] [(r << 1) + msb] carry
+
+                dup 12 dup 12 dup 12 dup 12
+                dup 8 dup 8 dup 8 dup 8
+                call {lt_u128}
+                // _ i [d] [nq'] [r'] carry (r' < d)
+
+                pick 1 push 0 eq mul
+                push 0 eq
+                // _ i [d] [nq'] [r'] (r' >= d)
+
+                skiz call {subtract_denominator}
+                pick 7 pick 7 pick 7 pick 7
+                // _ i [d] [r'] [nq']
+
+                recurse
+
+            // BEFORE: _ i [d] [nq] [r]
+            // AFTER:  _ i [d] [nq + 1] [r - d]
+            {subtract_denominator}:
+                push 1
+                {&subtract_limbs}
+                pop 1
+                pick 4 addi 1 place 4
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_prelude::*;
+
+    impl Closure for DivMod {
+        type Args = (i128, i128);
+
+        fn rust_shadow(&self, stack: &mut Vec<BFieldElement>) {
+            let (numerator, denominator) = pop_encodable::<Self::Args>(stack);
+            let quotient = numerator.checked_div(denominator).unwrap();
+            let remainder = numerator.checked_rem(denominator).unwrap();
+            push_encodable(stack, &quotient);
+            push_encodable(stack, &remainder);
+        }
+
+        fn pseudorandom_args(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> Self::Args {
+            match bench_case {
+                Some(BenchmarkCase::CommonCase) => (-(1 << 63), 1 << 15),
+                Some(BenchmarkCase::WorstCase) => (i128::MIN + 1, 3),
+                None => {
+                    let mut rng = StdRng::from_seed(seed);
+                    let numerator = rng.random_range(i128::MIN + 1..=i128::MAX);
+                    let denominator = match rng.random() {
+                        true => rng.random_range(1..=i128::MAX),
+                        false => rng.random_range(i128::MIN..=-1),
+                    };
+                    (numerator, denominator)
+                }
+            }
+        }
+
+        fn corner_case_args(&self) -> Vec<Self::Args> {
+            let points = [
+                i128::MIN,
+                i128::MIN + 1,
+                -(1 << 64),
+                -(1 << 32) - 1,
+                -2,
+                -1,
+                0,
+                1,
+                2,
+                (1 << 32) + 1,
+                1 << 64,
+                i128::MAX - 1,
+                i128::MAX,
+            ];
+
+            points
+                .into_iter()
+                .cartesian_product(points)
+                .filter(|&(n, d)| n.checked_div(d).is_some())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedClosure::new(DivMod).test();
+    }
+
+    #[proptest(cases = 50)]
+    fn division_by_zero_crashes_vm(numerator: i128) {
+        test_assertion_failure(
+            &ShadowedClosure::new(DivMod),
+            InitVmState::with_stack(DivMod.set_up_test_stack((numerator, 0))),
+            &[DivMod::DIVISION_BY_ZERO_ERROR_ID],
+        );
+    }
+
+    #[test]
+    fn overflow_crashes_vm() {
+        test_assertion_failure(
+            &ShadowedClosure::new(DivMod),
+            InitVmState::with_stack(DivMod.set_up_test_stack((i128::MIN, -1))),
+            &[DivMod::OVERFLOW_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedClosure::new(DivMod).bench();
+    }
+}
@@ -0,0 +1,106 @@
+use triton_vm::prelude::*;
+
+use crate::prelude::*;
+
+/// Sign-extending conversion from `i32` to `i128`, AKA [`i128::from`].
+///
+/// Since there is no [`DataType`] for `i32`s, the argument is represented by a
+/// [`DataType::U32`] with the same bit pattern.
+///
+/// # Behavior
+///
+/// ```text
+/// BEFORE: _ [arg: i32]
+/// AFTER:  _ [arg: i128]
+/// ```
+///
+/// # Preconditions
+///
+///  - `arg` is a `u32`
+///
+/// # Postconditions
+///
+///  - the output has the same value as `arg`
+///  - the output consists of 4 `u32`s
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FromI32;
+
+impl BasicSnippet for FromI32 {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::U32, "arg".to_string())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::I128, "arg".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_arithmetic_i128_from_i32".to_string()
+    }
+
+    fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+        triton_asm!(
+            // BEFORE: _ a
+            // AFTER:  _ e e e a
+            {self.entrypoint()}:
+                dup 0 push {i32::MAX} lt
+                // _ a (arg < 0)
+
+                push {u32::MAX} mul
+                // _ a e
+
+                dup 0 dup 0
+                pick 3
+                // _ e e e a
+
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_prelude::*;
+
+    impl Closure for FromI32 {
+        type Args = i32;
+
+        fn rust_shadow(&self, stack: &mut Vec<BFieldElement>) {
+            let arg = pop_encodable::<Self::Args>(stack);
+            push_encodable(stack, &i128::from(arg));
+        }
+
+        fn pseudorandom_args(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> Self::Args {
+            match bench_case {
+                Some(BenchmarkCase::CommonCase) => 1 << 16,
+                Some(BenchmarkCase::WorstCase) => -(1 << 16),
+                None => StdRng::from_seed(seed).random(),
+            }
+        }
+
+        fn corner_case_args(&self) -> Vec<Self::Args> {
+            vec![i32::MIN, i32::MIN + 1, -1, 0, 1, i32::MAX]
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedClosure::new(FromI32).test();
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedClosure::new(FromI32).bench();
+    }
+}
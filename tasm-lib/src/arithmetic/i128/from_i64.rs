@@ -0,0 +1,115 @@
+use triton_vm::prelude::*;
+
+use crate::prelude::*;
+
+/// Sign-extending conversion from `i64` to `i128`, AKA [`i128::from`].
+///
+/// Since there is no [`DataType`] for `i64`s, the argument is represented by a
+/// [`DataType::U64`] with the same bit pattern.
+///
+/// # Behavior
+///
+/// ```text
+/// BEFORE: _ [arg: i64]
+/// AFTER:  _ [arg: i128]
+/// ```
+///
+/// # Preconditions
+///
+///  - `arg` consists of 2 `u32`s
+///
+/// # Postconditions
+///
+///  - the output has the same value as `arg`
+///  - the output consists of 4 `u32`s
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FromI64;
+
+impl BasicSnippet for FromI64 {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::U64, "arg".to_string())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::I128, "arg".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_arithmetic_i128_from_i64".to_string()
+    }
+
+    fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+        triton_asm!(
+            // BEFORE: _ a1 a0
+            // AFTER:  _ e e a1 a0
+            {self.entrypoint()}:
+                dup 1 push {i32::MAX} lt
+                // _ a1 a0 (arg < 0)
+
+                push {u32::MAX} mul
+                // _ a1 a0 e
+
+                dup 0
+                place 3 place 3
+                // _ e e a1 a0
+
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_prelude::*;
+
+    impl Closure for FromI64 {
+        type Args = i64;
+
+        fn rust_shadow(&self, stack: &mut Vec<BFieldElement>) {
+            let arg = pop_encodable::<Self::Args>(stack);
+            push_encodable(stack, &i128::from(arg));
+        }
+
+        fn pseudorandom_args(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> Self::Args {
+            match bench_case {
+                Some(BenchmarkCase::CommonCase) => 1 << 32,
+                Some(BenchmarkCase::WorstCase) => -(1 << 32),
+                None => StdRng::from_seed(seed).random(),
+            }
+        }
+
+        fn corner_case_args(&self) -> Vec<Self::Args> {
+            vec![
+                i64::MIN,
+                i64::MIN + 1,
+                -(1 << 32),
+                -1,
+                0,
+                1,
+                1 << 32,
+                i64::MAX,
+            ]
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedClosure::new(FromI64).test();
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedClosure::new(FromI64).bench();
+    }
+}
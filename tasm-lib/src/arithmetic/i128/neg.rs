@@ -0,0 +1,166 @@
+use triton_vm::prelude::*;
+
+use crate::prelude::*;
+
+/// [Negation][neg] for `i128`s. Crashes the VM on overflow, _i.e._, if the
+/// argument is [`i128::MIN`].
+///
+/// # Behavior
+///
+/// ```text
+/// BEFORE: _ [arg: i128]
+/// AFTER:  _ [-arg: i128]
+/// ```
+///
+/// # Preconditions
+///
+///  - `arg` consists of 4 `u32`s
+///  - `arg` is not [`i128::MIN`]
+///
+/// # Postconditions
+///
+///  - the output is the negation of `arg`
+///  - the output consists of 4 `u32`s
+///
+/// [neg]: core::ops::Neg
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Neg;
+
+impl Neg {
+    pub const OVERFLOW_ERROR_ID: i128 = 610;
+
+    /// Generate code to compute the two's-complement negation of an `i128`,
+    /// wrapping around at the boundary of the type.
+    ///
+    /// ```text
+    /// BEFORE: _ [arg: i128]
+    /// AFTER:  _ [arg.wrapping_neg(): i128]
+    /// ```
+    ///
+    /// Interpreting the output as a `u128` gives the absolute value of any
+    /// negative input, including [`i128::MIN`].
+    pub(crate) fn wrapping_negation_code() -> Vec<LabelledInstruction> {
+        // Compute `!arg + 1` limb by limb, propagating the carry upwards. The
+        // bitwise negation of a u32 `x` is `u32::MAX - x`.
+        let negate_limb = triton_asm!(
+            // _ [res_lo_limbs] [arg_hi_limbs] carry
+            pick 1 push -1 mul add
+            push {u32::MAX} add
+            split
+            place 4
+        );
+
+        triton_asm!(
+            // _ a3 a2 a1 a0
+            push 1
+            // _ a3 a2 a1 a0 1
+
+            {&negate_limb}
+            // _ r0 a3 a2 a1 c1
+
+            {&negate_limb}
+            {&negate_limb}
+            {&negate_limb}
+            // _ r3 r2 r1 r0 c4
+
+            pop 1
+            // _ r3 r2 r1 r0
+        )
+    }
+
+    /// Generate code to compute whether an `i128` is negative, preserving the
+    /// argument.
+    ///
+    /// ```text
+    /// BEFORE: _ [arg: i128]
+    /// AFTER:  _ [arg: i128] (arg < 0)
+    /// ```
+    pub(crate) fn is_negative_code() -> Vec<LabelledInstruction> {
+        triton_asm!(dup 3 push {i32::MAX} lt)
+    }
+}
+
+impl BasicSnippet for Neg {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::I128, "arg".to_string())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::I128, "negation".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_arithmetic_i128_neg".to_string()
+    }
+
+    fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+        triton_asm!(
+            // BEFORE: _ a3 a2 a1 a0
+            // AFTER:  _ r3 r2 r1 r0
+            {self.entrypoint()}:
+                /* assert arg != i128::MIN */
+                dup 3 push {1_u32 << 31} eq
+                dup 3 dup 3 dup 3 add add push 0 eq
+                mul
+                // _ a3 a2 a1 a0 (arg == i128::MIN)
+
+                push 0 eq
+                assert error_id {Self::OVERFLOW_ERROR_ID}
+
+                {&Self::wrapping_negation_code()}
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arithmetic::i128::safe_add::SafeAdd;
+    use crate::test_prelude::*;
+
+    impl Closure for Neg {
+        type Args = i128;
+
+        fn rust_shadow(&self, stack: &mut Vec<BFieldElement>) {
+            let arg = pop_encodable::<Self::Args>(stack);
+            push_encodable(stack, &arg.checked_neg().unwrap());
+        }
+
+        fn pseudorandom_args(&self, seed: [u8; 32], _: Option<BenchmarkCase>) -> Self::Args {
+            StdRng::from_seed(seed).random_range(i128::MIN + 1..=i128::MAX)
+        }
+
+        fn corner_case_args(&self) -> Vec<Self::Args> {
+            SafeAdd::corner_case_points()
+                .into_iter()
+                .filter(|&arg| arg != i128::MIN)
+                .collect()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedClosure::new(Neg).test();
+    }
+
+    #[test]
+    fn negating_min_crashes_vm() {
+        test_assertion_failure(
+            &ShadowedClosure::new(Neg),
+            InitVmState::with_stack(Neg.set_up_test_stack(i128::MIN)),
+            &[Neg::OVERFLOW_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedClosure::new(Neg).bench();
+    }
+}
@@ -0,0 +1,202 @@
+use triton_vm::prelude::*;
+
+use crate::arithmetic::i128::neg::Neg;
+use crate::arithmetic::u128::overflowing_add::OverflowingAdd;
+use crate::prelude::*;
+
+/// Addition for `i128`s. Crashes the VM on overflow.
+///
+/// # Behavior
+///
+/// ```text
+/// BEFORE: _ [rhs: i128] [lhs: i128]
+/// AFTER:  _ [lhs + rhs: i128]
+/// ```
+///
+/// # Preconditions
+///
+///  - `rhs` and `lhs` consist of 4 `u32`s
+///  - the sum of `lhs` and `rhs` is in the range of `i128`
+///
+/// # Postconditions
+///
+///  - the output is the sum of `lhs` and `rhs`
+///  - the output consists of 4 `u32`s
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct SafeAdd;
+
+impl SafeAdd {
+    pub const OVERFLOW_ERROR_ID: i128 = 580;
+}
+
+impl BasicSnippet for SafeAdd {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        ["rhs", "lhs"]
+            .map(|s| (DataType::I128, s.to_string()))
+            .to_vec()
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::I128, "sum".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_arithmetic_i128_safe_add".to_string()
+    }
+
+    fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+        let is_negative = Neg::is_negative_code();
+
+        triton_asm!(
+            // BEFORE: _ [rhs] [lhs]
+            // AFTER:  _ [sum]
+            {self.entrypoint()}:
+                {&is_negative}
+                dup 8 push {i32::MAX} lt
+                place 9 place 9
+                // _ (lhs < 0) (rhs < 0) [rhs] [lhs]
+
+                {&OverflowingAdd::addition_code()}
+                pop 1
+                // _ (lhs < 0) (rhs < 0) [sum]
+
+                /* overflow iff the sign of the sum differs from both summands’ signs */
+                {&is_negative}
+                pick 5 dup 1 xor
+                pick 6 pick 2 xor
+                // _ [sum] (rhs_sign ^ sum_sign) (lhs_sign ^ sum_sign)
+
+                mul
+                push 0 eq
+                assert error_id {Self::OVERFLOW_ERROR_ID}
+                // _ [sum]
+
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::test_prelude::*;
+
+    impl SafeAdd {
+        /// Powers of two, their negations, and some extreme points, each
+        /// accompanied by its immediate neighbors.
+        pub(crate) fn corner_case_points() -> Vec<i128> {
+            [
+                i128::MIN,
+                -(1 << 96),
+                -(1 << 64),
+                -(1 << 32),
+                -1,
+                0,
+                1,
+                1 << 32,
+                1 << 64,
+                1 << 96,
+                1 << 126,
+                i128::MAX,
+            ]
+            .into_iter()
+            .flat_map(|p| [p.checked_sub(1), Some(p), p.checked_add(1)])
+            .flatten()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .sorted()
+            .collect()
+        }
+    }
+
+    impl Closure for SafeAdd {
+        type Args = (i128, i128);
+
+        fn rust_shadow(&self, stack: &mut Vec<BFieldElement>) {
+            let (rhs, lhs) = pop_encodable::<Self::Args>(stack);
+            push_encodable(stack, &lhs.checked_add(rhs).unwrap());
+        }
+
+        fn pseudorandom_args(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> Self::Args {
+            match bench_case {
+                Some(BenchmarkCase::CommonCase) => (1 << 63, -(1 << 64)),
+                Some(BenchmarkCase::WorstCase) => (i128::MAX, i128::MIN),
+                None => {
+                    let mut rng = StdRng::from_seed(seed);
+                    let lhs = rng.random();
+                    let rhs = match lhs < 0 {
+                        true => rng.random_range(i128::MIN - lhs..=i128::MAX),
+                        false => rng.random_range(i128::MIN..=i128::MAX - lhs),
+                    };
+                    (rhs, lhs)
+                }
+            }
+        }
+
+        fn corner_case_args(&self) -> Vec<Self::Args> {
+            let points = Self::corner_case_points();
+            points
+                .iter()
+                .cartesian_product(&points)
+                .map(|(&r, &l)| (r, l))
+                .filter(|&(r, l)| l.checked_add(r).is_some())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedClosure::new(SafeAdd).test();
+    }
+
+    #[test]
+    fn overflow_crashes_vm() {
+        let points = SafeAdd::corner_case_points();
+        for (&rhs, &lhs) in points.iter().cartesian_product(&points) {
+            if lhs.checked_add(rhs).is_some() {
+                continue;
+            }
+
+            test_assertion_failure(
+                &ShadowedClosure::new(SafeAdd),
+                InitVmState::with_stack(SafeAdd.set_up_test_stack((rhs, lhs))),
+                &[SafeAdd::OVERFLOW_ERROR_ID],
+            );
+        }
+    }
+
+    #[proptest]
+    fn arbitrary_overflow_crashes_vm(
+        #[strategy(1_i128..)] lhs: i128,
+        #[strategy(i128::MAX - #lhs + 1..)] rhs: i128,
+        overflow_towards_min: bool,
+    ) {
+        let (lhs, rhs) = match overflow_towards_min {
+            true => (-lhs, -rhs - 1),
+            false => (lhs, rhs),
+        };
+
+        test_assertion_failure(
+            &ShadowedClosure::new(SafeAdd),
+            InitVmState::with_stack(SafeAdd.set_up_test_stack((rhs, lhs))),
+            &[SafeAdd::OVERFLOW_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedClosure::new(SafeAdd).bench();
+    }
+}
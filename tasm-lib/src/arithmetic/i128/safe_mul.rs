@@ -0,0 +1,261 @@
+use triton_vm::prelude::*;
+
+use crate::arithmetic::i128::neg::Neg;
+use crate::arithmetic::u128::safe_mul::SafeMul as SafeMulU128;
+use crate::prelude::*;
+
+/// Multiplication for `i128`s. Crashes the VM on overflow.
+///
+/// # Behavior
+///
+/// ```text
+/// BEFORE: _ [rhs: i128] [lhs: i128]
+/// AFTER:  _ [lhs · rhs: i128]
+/// ```
+///
+/// # Preconditions
+///
+///  - `rhs` and `lhs` consist of 4 `u32`s
+///  - the product of `lhs` and `rhs` is in the range of `i128`
+///
+/// # Postconditions
+///
+///  - the output is the product of `lhs` and `rhs`
+///  - the output consists of 4 `u32`s
+///
+/// # Crashes
+///
+/// If the absolute value of the product exceeds [`u128::MAX`], the VM crashes
+/// with one of the error IDs of [`u128::SafeMul`](SafeMulU128).
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct SafeMul;
+
+impl SafeMul {
+    pub const OVERFLOW_ERROR_ID: i128 = 600;
+
+    /// Generate code asserting that a sign and a magnitude describe a valid
+    /// `i128`.
+    ///
+    /// ```text
+    /// BEFORE: _ [magnitude: u128] is_negative
+    /// AFTER:  _ [magnitude: u128] is_negative
+    /// ```
+    ///
+    /// The magnitude of a non-negative `i128` is at most [`i128::MAX`]; the
+    /// magnitude of a negative `i128` is at most `i128::MAX + 1`.
+    pub(crate) fn assert_magnitude_fits_code(error_id: i128) -> Vec<LabelledInstruction> {
+        let two_pow_31 = 1_u32 << 31;
+
+        triton_asm!(
+            // _ m3 m2 m1 m0 is_neg
+            push {two_pow_31} dup 5 lt
+            // _ m3 m2 m1 m0 is_neg (m3 < 2^31)
+
+            dup 5 push {two_pow_31} eq
+            dup 3 dup 5 dup 7 add add push 0 eq
+            mul
+            // _ m3 m2 m1 m0 is_neg (m3 < 2^31) (m == 2^127)
+
+            dup 2 mul
+            add
+            // _ m3 m2 m1 m0 is_neg ((m3 < 2^31) || (is_neg && m == 2^127))
+
+            assert error_id {error_id}
+        )
+    }
+}
+
+impl BasicSnippet for SafeMul {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        ["rhs", "lhs"]
+            .map(|s| (DataType::I128, s.to_string()))
+            .to_vec()
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::I128, "product".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_arithmetic_i128_safe_mul".to_string()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let wrapping_neg = format!("{entrypoint}_wrapping_neg");
+        let is_negative = Neg::is_negative_code();
+        let safe_mul_u128 = library.import(Box::new(SafeMulU128));
+
+        triton_asm!(
+            // BEFORE: _ [rhs] [lhs]
+            // AFTER:  _ [product]
+            {entrypoint}:
+                {&is_negative}
+                dup 0 place 9
+                skiz call {wrapping_neg}
+                // _ (lhs < 0) [rhs] [|lhs|]
+
+                pick 7 pick 7 pick 7 pick 7
+                {&is_negative}
+                dup 0 place 9
+                skiz call {wrapping_neg}
+                // _ (lhs < 0) (rhs < 0) [|lhs|] [|rhs|]
+
+                call {safe_mul_u128}
+                // _ (lhs < 0) (rhs < 0) [|product|]
+
+                pick 5 pick 5 xor
+                // _ [|product|] (product < 0)
+
+                {&Self::assert_magnitude_fits_code(Self::OVERFLOW_ERROR_ID)}
+                skiz call {wrapping_neg}
+                // _ [product]
+
+                return
+
+            // BEFORE: _ [arg: i128]
+            // AFTER:  _ [arg.wrapping_neg(): i128]
+            {wrapping_neg}:
+                {&Neg::wrapping_negation_code()}
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arithmetic::i128::safe_add::SafeAdd;
+    use crate::test_prelude::*;
+
+    impl Closure for SafeMul {
+        type Args = (i128, i128);
+
+        fn rust_shadow(&self, stack: &mut Vec<BFieldElement>) {
+            let (rhs, lhs) = pop_encodable::<Self::Args>(stack);
+            push_encodable(stack, &lhs.checked_mul(rhs).unwrap());
+        }
+
+        fn pseudorandom_args(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> Self::Args {
+            match bench_case {
+                Some(BenchmarkCase::CommonCase) => (1 << 63, -(1 << 45) + 1),
+                Some(BenchmarkCase::WorstCase) => (-(1 << 63), -(1 << 63) + 1),
+                None => {
+                    let mut rng = StdRng::from_seed(seed);
+                    let lhs = rng.random_range(1..=i128::MAX);
+                    let rhs = rng.random_range(-(i128::MAX / lhs)..=i128::MAX / lhs);
+                    let lhs = match rng.random() {
+                        true => -lhs,
+                        false => lhs,
+                    };
+                    (rhs, lhs)
+                }
+            }
+        }
+
+        fn corner_case_args(&self) -> Vec<Self::Args> {
+            let points = SafeAdd::corner_case_points()
+                .into_iter()
+                .chain([-(1 << 63), 1 << 63])
+                .collect_vec();
+            points
+                .iter()
+                .cartesian_product(&points)
+                .map(|(&r, &l)| (r, l))
+                .filter(|&(r, l)| l.checked_mul(r).is_some())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedClosure::new(SafeMul).test();
+    }
+
+    #[test]
+    fn overflow_crashes_vm() {
+        let u128_mul_overflow_error_ids = 500..=506;
+        let expected_error_ids = u128_mul_overflow_error_ids
+            .chain([SafeMul::OVERFLOW_ERROR_ID])
+            .collect_vec();
+
+        let points = SafeAdd::corner_case_points();
+        for (&rhs, &lhs) in points.iter().cartesian_product(&points) {
+            if lhs.checked_mul(rhs).is_some() {
+                continue;
+            }
+
+            test_assertion_failure(
+                &ShadowedClosure::new(SafeMul),
+                InitVmState::with_stack(SafeMul.set_up_test_stack((rhs, lhs))),
+                &expected_error_ids,
+            );
+        }
+    }
+
+    #[test]
+    fn product_of_magnitude_two_pow_127_crashes_vm_iff_positive() {
+        let crashes = [(i128::MIN, -1), (-1, i128::MIN), (-(1 << 64), -(1 << 63))];
+        for (rhs, lhs) in crashes {
+            test_assertion_failure(
+                &ShadowedClosure::new(SafeMul),
+                InitVmState::with_stack(SafeMul.set_up_test_stack((rhs, lhs))),
+                &[SafeMul::OVERFLOW_ERROR_ID],
+            );
+        }
+
+        let doesnt_crash = [(i128::MIN, 1), (1 << 64, -(1 << 63)), (-(1 << 64), 1 << 63)];
+        for (rhs, lhs) in doesnt_crash {
+            let stack = SafeMul.set_up_test_stack((rhs, lhs));
+            let mut expected_stack = SafeMul.init_stack_for_isolated_run();
+            push_encodable(&mut expected_stack, &i128::MIN);
+
+            test_rust_equivalence_given_complete_state(
+                &ShadowedClosure::new(SafeMul),
+                &stack,
+                &[],
+                &NonDeterminism::default(),
+                &None,
+                Some(&expected_stack),
+            );
+        }
+    }
+
+    #[proptest]
+    fn arbitrary_overflow_crashes_vm(
+        #[strategy(2_i128..)] lhs: i128,
+        #[strategy(i128::MAX / #lhs + 1..)] rhs: i128,
+        negate_lhs: bool,
+        negate_rhs: bool,
+    ) {
+        let lhs = if negate_lhs { -lhs } else { lhs };
+        let rhs = if negate_rhs { -rhs } else { rhs };
+        prop_assume!(lhs.checked_mul(rhs).is_none());
+
+        let u128_mul_overflow_error_ids = 500..=506;
+        let expected_error_ids = u128_mul_overflow_error_ids
+            .chain([SafeMul::OVERFLOW_ERROR_ID])
+            .collect_vec();
+
+        test_assertion_failure(
+            &ShadowedClosure::new(SafeMul),
+            InitVmState::with_stack(SafeMul.set_up_test_stack((rhs, lhs))),
+            &expected_error_ids,
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedClosure::new(SafeMul).bench();
+    }
+}
@@ -0,0 +1,179 @@
+use triton_vm::prelude::*;
+
+use crate::arithmetic::i128::neg::Neg;
+use crate::arithmetic::u128::overflowing_add::OverflowingAdd;
+use crate::prelude::*;
+
+/// Subtraction for `i128`s. Crashes the VM on overflow.
+///
+/// # Behavior
+///
+/// ```text
+/// BEFORE: _ [rhs: i128] [lhs: i128]
+/// AFTER:  _ [lhs - rhs: i128]
+/// ```
+///
+/// # Preconditions
+///
+///  - `rhs` and `lhs` consist of 4 `u32`s
+///  - the difference of `lhs` and `rhs` is in the range of `i128`
+///
+/// # Postconditions
+///
+///  - the output is `lhs` minus `rhs`
+///  - the output consists of 4 `u32`s
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct SafeSub;
+
+impl SafeSub {
+    pub const OVERFLOW_ERROR_ID: i128 = 590;
+}
+
+impl BasicSnippet for SafeSub {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        ["rhs", "lhs"]
+            .map(|s| (DataType::I128, s.to_string()))
+            .to_vec()
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::I128, "difference".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_arithmetic_i128_safe_sub".to_string()
+    }
+
+    fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+        let is_negative = Neg::is_negative_code();
+
+        triton_asm!(
+            // BEFORE: _ [rhs] [lhs]
+            // AFTER:  _ [difference]
+            {self.entrypoint()}:
+                {&is_negative}
+                dup 8 push {i32::MAX} lt
+                place 9 place 9
+                // _ (lhs < 0) (rhs < 0) [rhs] [lhs]
+
+                pick 7 pick 7 pick 7 pick 7
+                {&Neg::wrapping_negation_code()}
+                // _ (lhs < 0) (rhs < 0) [lhs] [rhs.wrapping_neg()]
+
+                {&OverflowingAdd::addition_code()}
+                pop 1
+                // _ (lhs < 0) (rhs < 0) [difference]
+
+                /* overflow iff the operands’ signs differ and the sign of the
+                 * difference differs from the sign of `lhs`
+                 */
+                {&is_negative}
+                pick 6 dup 0 pick 2 xor
+                pick 1 pick 6 xor
+                // _ [difference] (lhs_sign ^ difference_sign) (lhs_sign ^ rhs_sign)
+
+                mul
+                push 0 eq
+                assert error_id {Self::OVERFLOW_ERROR_ID}
+                // _ [difference]
+
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arithmetic::i128::safe_add::SafeAdd;
+    use crate::test_prelude::*;
+
+    impl Closure for SafeSub {
+        type Args = (i128, i128);
+
+        fn rust_shadow(&self, stack: &mut Vec<BFieldElement>) {
+            let (rhs, lhs) = pop_encodable::<Self::Args>(stack);
+            push_encodable(stack, &lhs.checked_sub(rhs).unwrap());
+        }
+
+        fn pseudorandom_args(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> Self::Args {
+            match bench_case {
+                Some(BenchmarkCase::CommonCase) => (1 << 63, 1 << 64),
+                Some(BenchmarkCase::WorstCase) => (i128::MIN + 1, -1),
+                None => {
+                    let mut rng = StdRng::from_seed(seed);
+                    let lhs = rng.random();
+                    let rhs = match lhs < 0 {
+                        true => rng.random_range(i128::MIN..=lhs - i128::MIN),
+                        false => rng.random_range(lhs - i128::MAX..=i128::MAX),
+                    };
+                    (rhs, lhs)
+                }
+            }
+        }
+
+        fn corner_case_args(&self) -> Vec<Self::Args> {
+            let points = SafeAdd::corner_case_points();
+            points
+                .iter()
+                .cartesian_product(&points)
+                .map(|(&r, &l)| (r, l))
+                .filter(|&(r, l)| l.checked_sub(r).is_some())
+                .collect()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedClosure::new(SafeSub).test();
+    }
+
+    #[test]
+    fn overflow_crashes_vm() {
+        let points = SafeAdd::corner_case_points();
+        for (&rhs, &lhs) in points.iter().cartesian_product(&points) {
+            if lhs.checked_sub(rhs).is_some() {
+                continue;
+            }
+
+            test_assertion_failure(
+                &ShadowedClosure::new(SafeSub),
+                InitVmState::with_stack(SafeSub.set_up_test_stack((rhs, lhs))),
+                &[SafeSub::OVERFLOW_ERROR_ID],
+            );
+        }
+    }
+
+    #[proptest]
+    fn arbitrary_overflow_crashes_vm(
+        #[strategy(1_i128..)] lhs: i128,
+        #[strategy(i128::MIN + 1..#lhs - i128::MAX)] rhs: i128,
+        overflow_towards_min: bool,
+    ) {
+        let (lhs, rhs) = match overflow_towards_min {
+            true => (-lhs - 1, -rhs),
+            false => (lhs, rhs),
+        };
+
+        test_assertion_failure(
+            &ShadowedClosure::new(SafeSub),
+            InitVmState::with_stack(SafeSub.set_up_test_stack((rhs, lhs))),
+            &[SafeSub::OVERFLOW_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedClosure::new(SafeSub).bench();
+    }
+}
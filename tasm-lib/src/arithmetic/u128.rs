@@ -1,5 +1,8 @@
+pub mod div;
+pub mod div_mod;
 pub mod lt;
 pub mod overflowing_add;
+pub mod rem;
 pub mod safe_add;
 pub mod safe_mul;
 pub mod shift_left;
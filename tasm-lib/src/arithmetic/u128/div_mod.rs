@@ -0,0 +1,401 @@
+use triton_vm::prelude::*;
+
+use crate::arithmetic::u128::lt::Lt;
+use crate::arithmetic::u128::safe_add::SafeAdd;
+use crate::arithmetic::u128::safe_mul::SafeMul;
+use crate::prelude::*;
+
+/// Integer division with remainder for `u128`s.
+///
+/// The quotient and the remainder are divined from secret input and subsequently
+/// verified. Use [`DivMod::update_nondeterminism`] to populate the secret input.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ [numerator: u128] [denominator: u128]
+/// AFTER:  _ [quotient: u128] [remainder: u128]
+/// ```
+///
+/// ### Preconditions
+///
+/// - all input arguments are properly [`BFieldCodec`] encoded
+/// - the `denominator` is not 0
+/// - the next 8 individual tokens of secret input are the quotient followed by
+///   the remainder, each in reversed [`BFieldCodec`] encoding
+///
+/// ### Postconditions
+///
+/// - `numerator == quotient · denominator + remainder`
+/// - `remainder < denominator`
+/// - all output is properly [`BFieldCodec`] encoded
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct DivMod;
+
+impl DivMod {
+    pub const DIVISION_BY_ZERO_ERROR_ID: i128 = 570;
+    pub const NON_U32_LIMB_ERROR_ID: i128 = 571;
+    pub const REMAINDER_TOO_LARGE_ERROR_ID: i128 = 572;
+    pub const INCONSISTENT_QUOTIENT_AND_REMAINDER_ERROR_ID: i128 = 573;
+
+    /// Update a nondeterminism in accordance with dividing `numerator` by
+    /// `denominator` using this snippet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `denominator` is 0.
+    pub fn update_nondeterminism(
+        nondeterminism: &mut NonDeterminism,
+        numerator: u128,
+        denominator: u128,
+    ) {
+        let quotient = numerator / denominator;
+        let remainder = numerator % denominator;
+        for value in [quotient, remainder] {
+            let mut encoding = value.encode();
+            encoding.reverse();
+            nondeterminism.individual_tokens.extend(encoding);
+        }
+    }
+}
+
+impl BasicSnippet for DivMod {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        ["numerator", "denominator"]
+            .map(|name| (DataType::U128, name.to_string()))
+            .to_vec()
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        ["quotient", "remainder"]
+            .map(|name| (DataType::U128, name.to_string()))
+            .to_vec()
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_arithmetic_u128_div_mod".to_string()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let lt_u128 = library.import(Box::new(Lt));
+        let safe_mul_u128 = library.import(Box::new(SafeMul));
+        let safe_add_u128 = library.import(Box::new(SafeAdd));
+
+        triton_asm!(
+            // BEFORE: _ [n] [d]
+            // AFTER:  _ [q] [r]
+            {self.entrypoint()}:
+                divine 4
+                divine 4
+                // _ [n] [d] [q] [r]
+
+                /* all divined limbs are u32s */
+                dup 7 split pop 1
+                dup 7 split pop 1
+                dup 7 split pop 1
+                dup 7 split pop 1
+                dup 7 split pop 1
+                dup 7 split pop 1
+                dup 7 split pop 1
+                dup 7 split pop 1
+                // _ [n] [d] [q] [r] q3_hi q2_hi q1_hi q0_hi r3_hi r2_hi r1_hi r0_hi
+
+                // all summands are smaller than 2^32; the sum cannot wrap around
+                add add add add add add add
+                push 0 eq
+                assert error_id {Self::NON_U32_LIMB_ERROR_ID}
+                // _ [n] [d] [q] [r]
+
+                pick 15 pick 15 pick 15 pick 15
+                pick 15 pick 15 pick 15 pick 15
+                // _ [q] [r] [n] [d]
+
+                /* d != 0 */
+                dup 3 dup 3 dup 3 dup 3
+                add add add
+                push 0 eq
+                push 0 eq
+                assert error_id {Self::DIVISION_BY_ZERO_ERROR_ID}
+
+                /* r < d */
+                dup 3 dup 3 dup 3 dup 3
+                dup 15 dup 15 dup 15 dup 15
+                // _ [q] [r] [n] [d] [d] [r]
+
+                call {lt_u128}
+                assert error_id {Self::REMAINDER_TOO_LARGE_ERROR_ID}
+                // _ [q] [r] [n] [d]
+
+                /* q·d + r == n */
+                dup 15 dup 15 dup 15 dup 15
+                // _ [q] [r] [n] [d] [q]
+
+                call {safe_mul_u128}
+                // _ [q] [r] [n] [q·d]
+
+                dup 11 dup 11 dup 11 dup 11
+                call {safe_add_u128}
+                // _ [q] [r] [n] [q·d + r]
+
+                {&DataType::U128.compare()}
+                assert error_id {Self::INCONSISTENT_QUOTIENT_AND_REMAINDER_ERROR_ID}
+                // _ [q] [r]
+
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_prelude::*;
+
+    impl DivMod {
+        pub(crate) fn corner_case_numerators_and_denominators() -> Vec<(u128, u128)> {
+            const NOISE: u128 = 0x9d4b_c1e0_27a5_8f63_4e1d_b2c7_95f0_683a;
+
+            let u128s_of_different_magnitudes = (0..u128::BITS)
+                .step_by(9) // test performance is atrocious otherwise
+                .map(|i| 1 << i)
+                .map(|x| x | (x - 1) & NOISE);
+
+            let additional_inputs = [
+                (0, 1),
+                (0, u128::MAX),
+                (1, 1),
+                (u128::MAX, 1),
+                (u128::MAX, 2),
+                (u128::MAX, u128::MAX),
+                (u128::MAX - 1, u128::MAX),
+                (u128::MAX, u32::MAX.into()),
+                (u128::MAX, u64::MAX.into()),
+                (u128::MAX, 1 << 32),
+                (u128::MAX, 1 << 64),
+                (u128::MAX, 1 << 96),
+                (u128::MAX, (1 << 96) - 1),
+                (u128::MAX, (1 << 96) + 1),
+                (u64::MAX.into(), u128::MAX),
+            ];
+
+            u128s_of_different_magnitudes
+                .clone()
+                .cartesian_product(u128s_of_different_magnitudes)
+                .chain(additional_inputs)
+                .collect()
+        }
+
+        pub(crate) fn pseudorandom_numerator_and_denominator(
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> (u128, u128) {
+            match bench_case {
+                Some(BenchmarkCase::CommonCase) => (u64::MAX.into(), 1 << 15),
+                Some(BenchmarkCase::WorstCase) => (u128::MAX, (1 << 96) + 454545454),
+                None => {
+                    let mut rng = StdRng::from_seed(seed);
+                    (rng.random(), rng.random_range(1..=u128::MAX))
+                }
+            }
+        }
+
+        fn set_up_initial_state(
+            &self,
+            numerator: u128,
+            denominator: u128,
+            nondeterminism: NonDeterminism,
+        ) -> ProcedureInitialState {
+            let mut stack = self.init_stack_for_isolated_run();
+            push_encodable(&mut stack, &numerator);
+            push_encodable(&mut stack, &denominator);
+
+            ProcedureInitialState {
+                stack,
+                nondeterminism,
+                ..Default::default()
+            }
+        }
+
+        fn set_up_honest_initial_state(
+            &self,
+            numerator: u128,
+            denominator: u128,
+        ) -> ProcedureInitialState {
+            let mut nondeterminism = NonDeterminism::default();
+            Self::update_nondeterminism(&mut nondeterminism, numerator, denominator);
+            self.set_up_initial_state(numerator, denominator, nondeterminism)
+        }
+
+        fn set_up_dishonest_initial_state(
+            &self,
+            numerator: u128,
+            denominator: u128,
+            divined_quotient: u128,
+            divined_remainder: u128,
+        ) -> ProcedureInitialState {
+            let individual_tokens = [divined_quotient, divined_remainder]
+                .into_iter()
+                .flat_map(|value| value.encode().into_iter().rev())
+                .collect_vec();
+            let nondeterminism = NonDeterminism::new(individual_tokens);
+            self.set_up_initial_state(numerator, denominator, nondeterminism)
+        }
+    }
+
+    impl Procedure for DivMod {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            _: &mut HashMap<BFieldElement, BFieldElement>,
+            nondeterminism: &NonDeterminism,
+            _: &[BFieldElement],
+            _: &mut Option<Tip5>,
+        ) -> Vec<BFieldElement> {
+            let denominator = pop_encodable::<u128>(stack);
+            let numerator = pop_encodable::<u128>(stack);
+
+            let divine_u128 = |tokens: &[BFieldElement]| {
+                tokens
+                    .iter()
+                    .map(|&t| u32::try_from(t).unwrap())
+                    .fold(0, |acc, limb| (acc << 32) | u128::from(limb))
+            };
+            let quotient = divine_u128(&nondeterminism.individual_tokens[0..4]);
+            let remainder = divine_u128(&nondeterminism.individual_tokens[4..8]);
+
+            assert_ne!(0, denominator);
+            assert!(remainder < denominator);
+            let product = quotient.checked_mul(denominator).unwrap();
+            assert_eq!(numerator, product.checked_add(remainder).unwrap());
+
+            push_encodable(stack, &quotient);
+            push_encodable(stack, &remainder);
+
+            vec![]
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> ProcedureInitialState {
+            let (numerator, denominator) =
+                Self::pseudorandom_numerator_and_denominator(seed, bench_case);
+            self.set_up_honest_initial_state(numerator, denominator)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<ProcedureInitialState> {
+            Self::corner_case_numerators_and_denominators()
+                .into_iter()
+                .map(|(n, d)| self.set_up_honest_initial_state(n, d))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedProcedure::new(DivMod).test();
+    }
+
+    #[proptest]
+    fn division_by_zero_crashes_vm(numerator: u128, quotient: u128, remainder: u128) {
+        test_assertion_failure(
+            &ShadowedProcedure::new(DivMod),
+            DivMod
+                .set_up_dishonest_initial_state(numerator, 0, quotient, remainder)
+                .into(),
+            &[DivMod::DIVISION_BY_ZERO_ERROR_ID],
+        );
+    }
+
+    #[proptest]
+    fn non_u32_limbs_in_secret_input_crash_vm(
+        numerator: u128,
+        #[strategy(1_u128..)] denominator: u128,
+        #[strategy(0_usize..8)] poisoned_token: usize,
+        #[strategy(arb())]
+        #[filter(u32::try_from(#poison).is_err())]
+        poison: BFieldElement,
+    ) {
+        let mut state = DivMod.set_up_honest_initial_state(numerator, denominator);
+        state.nondeterminism.individual_tokens[poisoned_token] = poison;
+
+        test_assertion_failure(
+            &ShadowedProcedure::new(DivMod),
+            state.into(),
+            &[DivMod::NON_U32_LIMB_ERROR_ID],
+        );
+    }
+
+    #[proptest]
+    fn too_large_remainder_crashes_vm(
+        numerator: u128,
+        #[strategy(1_u128..)] denominator: u128,
+        #[strategy(#denominator..)] remainder: u128,
+        quotient: u128,
+    ) {
+        test_assertion_failure(
+            &ShadowedProcedure::new(DivMod),
+            DivMod
+                .set_up_dishonest_initial_state(numerator, denominator, quotient, remainder)
+                .into(),
+            &[DivMod::REMAINDER_TOO_LARGE_ERROR_ID],
+        );
+    }
+
+    #[proptest]
+    fn wrong_quotient_crashes_vm(
+        numerator: u128,
+        #[strategy(1_u128..)] denominator: u128,
+        #[filter(#offset != 0)] offset: u128,
+    ) {
+        let quotient = (numerator / denominator).wrapping_add(offset);
+        let remainder = numerator % denominator;
+
+        let mul_overflow = [500, 501, 502, 503, 504, 505, 506];
+        let add_overflow = [SafeAdd::OVERFLOW_ERROR_ID];
+        let inconsistency = [DivMod::INCONSISTENT_QUOTIENT_AND_REMAINDER_ERROR_ID];
+        let expected_error_ids = [&mul_overflow[..], &add_overflow, &inconsistency].concat();
+
+        test_assertion_failure(
+            &ShadowedProcedure::new(DivMod),
+            DivMod
+                .set_up_dishonest_initial_state(numerator, denominator, quotient, remainder)
+                .into(),
+            &expected_error_ids,
+        );
+    }
+
+    #[proptest]
+    fn wrong_remainder_crashes_vm(
+        numerator: u128,
+        #[strategy(2_u128..)] denominator: u128,
+        #[strategy(0..#denominator)]
+        #[filter(#numerator % #denominator != #remainder)]
+        remainder: u128,
+    ) {
+        let quotient = numerator / denominator;
+
+        test_assertion_failure(
+            &ShadowedProcedure::new(DivMod),
+            DivMod
+                .set_up_dishonest_initial_state(numerator, denominator, quotient, remainder)
+                .into(),
+            &[
+                SafeAdd::OVERFLOW_ERROR_ID,
+                DivMod::INCONSISTENT_QUOTIENT_AND_REMAINDER_ERROR_ID,
+            ],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedProcedure::new(DivMod).bench();
+    }
+}
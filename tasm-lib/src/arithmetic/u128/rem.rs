@@ -0,0 +1,160 @@
+use triton_vm::prelude::*;
+
+use crate::arithmetic::u128::div_mod::DivMod;
+use crate::prelude::*;
+
+/// [Remainder][rem] of integer division for `u128`s.
+///
+/// The remainder is divined from secret input and subsequently verified. Use
+/// [`DivMod::update_nondeterminism`] to populate the secret input.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ [numerator: u128] [denominator: u128]
+/// AFTER:  _ [remainder: u128]
+/// ```
+///
+/// ### Preconditions
+///
+/// - all input arguments are properly [`BFieldCodec`] encoded
+/// - the `denominator` is not 0
+/// - the secret input is populated as required by [`DivMod`]
+///
+/// ### Postconditions
+///
+/// - the output is the remainder of dividing the `numerator` by the `denominator`
+/// - the output is properly [`BFieldCodec`] encoded
+///
+/// [rem]: core::ops::Rem
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Rem;
+
+impl BasicSnippet for Rem {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        DivMod.inputs()
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::U128, "remainder".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_arithmetic_u128_rem".to_string()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let div_mod = library.import(Box::new(DivMod));
+
+        triton_asm!(
+            // BEFORE: _ [n] [d]
+            // AFTER:  _ [r]
+            {self.entrypoint()}:
+                call {div_mod}
+                // _ [q] [r]
+
+                pick 7 pick 7 pick 7 pick 7
+                pop 4
+                // _ [r]
+
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_prelude::*;
+
+    impl Rem {
+        fn set_up_initial_state(
+            &self,
+            numerator: u128,
+            denominator: u128,
+        ) -> ProcedureInitialState {
+            let mut stack = self.init_stack_for_isolated_run();
+            push_encodable(&mut stack, &numerator);
+            push_encodable(&mut stack, &denominator);
+
+            let mut nondeterminism = NonDeterminism::default();
+            DivMod::update_nondeterminism(&mut nondeterminism, numerator, denominator);
+
+            ProcedureInitialState {
+                stack,
+                nondeterminism,
+                ..Default::default()
+            }
+        }
+    }
+
+    impl Procedure for Rem {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+            nondeterminism: &NonDeterminism,
+            public_input: &[BFieldElement],
+            sponge: &mut Option<Tip5>,
+        ) -> Vec<BFieldElement> {
+            let std_out = DivMod.rust_shadow(stack, memory, nondeterminism, public_input, sponge);
+            let remainder = pop_encodable::<u128>(stack);
+            let _quotient = pop_encodable::<u128>(stack);
+            push_encodable(stack, &remainder);
+
+            std_out
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> ProcedureInitialState {
+            let (numerator, denominator) =
+                DivMod::pseudorandom_numerator_and_denominator(seed, bench_case);
+            self.set_up_initial_state(numerator, denominator)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<ProcedureInitialState> {
+            DivMod::corner_case_numerators_and_denominators()
+                .into_iter()
+                .map(|(n, d)| self.set_up_initial_state(n, d))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedProcedure::new(Rem).test();
+    }
+
+    #[proptest]
+    fn division_by_zero_crashes_vm(numerator: u128) {
+        let mut stack = Rem.init_stack_for_isolated_run();
+        push_encodable(&mut stack, &numerator);
+        push_encodable(&mut stack, &0_u128);
+        let nondeterminism = NonDeterminism::new(bfe_vec![0; 8]);
+
+        test_assertion_failure(
+            &ShadowedProcedure::new(Rem),
+            ProcedureInitialState {
+                stack,
+                nondeterminism,
+                ..Default::default()
+            }
+            .into(),
+            &[DivMod::DIVISION_BY_ZERO_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedProcedure::new(Rem).bench();
+    }
+}
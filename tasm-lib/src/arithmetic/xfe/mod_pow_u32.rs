@@ -193,7 +193,7 @@ pub mod tests {
         let bfe_14 = BFieldElement::new(14);
         let xfe_14 = XFieldElement::new([bfe_14, bfe_14, bfe_14]);
         let xfe_14: Vec<_> = xfe_14.coefficients.into_iter().rev().collect();
-        let code = XfeModPowU32.link_for_isolated_run();
+        let (code, source_map) = XfeModPowU32.link_for_isolated_run_with_source_map();
 
         for exponent in [
             1 << 32,
@@ -206,6 +206,7 @@ pub mod tests {
             let init_stack = [empty_stack(), bfe_vec![exponent], xfe_14.clone()].concat();
             let tvm_result = execute_with_terminal_state(
                 Program::new(&code),
+                &source_map,
                 &[],
                 &init_stack,
                 &NonDeterminism::default(),
@@ -23,8 +23,13 @@ use crate::io::read_input::ReadInput;
 use crate::io::write_to_stdout::WriteToStdout;
 use crate::io::InputSource;
 use crate::list;
+use crate::list::binary_search::SearchMode;
+use crate::memory::arena_malloc::ArenaMalloc;
 use crate::memory::dyn_malloc::DynMalloc;
+use crate::memory::memcmp::MemCmp;
 use crate::memory::memcpy::MemCpy;
+use crate::memory::memmove::MemMove;
+use crate::memory::memset::MemSet;
 use crate::mmr::bag_peaks::BagPeaks;
 use crate::mmr::calculate_new_peaks_from_append::CalculateNewPeaksFromAppend;
 use crate::mmr::calculate_new_peaks_from_leaf_mutation::MmrCalculateNewPeaksFromLeafMutationMtIndices;
@@ -68,6 +73,14 @@ const CHALLENGES_NEW_FROM_DYN_CLAIM: &str = formatcp!(
 pub fn name_to_snippet(fn_name: &str) -> Option<Box<dyn BasicSnippet>> {
     match fn_name {
         // BFieldElement
+        "tasmlib_arithmetic_bfe_coset_intt" => {
+            Some(Box::new(crate::arithmetic::bfe::coset_intt::CosetIntt))
+        }
+        "tasmlib_arithmetic_bfe_coset_ntt" => {
+            Some(Box::new(crate::arithmetic::bfe::coset_ntt::CosetNtt))
+        }
+        "tasmlib_arithmetic_bfe_intt" => Some(Box::new(crate::arithmetic::bfe::intt::Intt)),
+        "tasmlib_arithmetic_bfe_ntt" => Some(Box::new(crate::arithmetic::bfe::ntt::Ntt)),
         "tasmlib_arithmetic_bfe_primitive_root_of_unity" => Some(Box::new(
             crate::arithmetic::bfe::primitive_root_of_unity::PrimitiveRootOfUnity,
         )),
@@ -132,10 +145,13 @@ pub fn name_to_snippet(fn_name: &str) -> Option<Box<dyn BasicSnippet>> {
         "tasmlib_arithmetic_u64_xor" => Some(Box::new(u64::xor::Xor)),
 
         // u128
+        "tasmlib_arithmetic_u128_div" => Some(Box::new(u128::div::Div)),
+        "tasmlib_arithmetic_u128_div_mod" => Some(Box::new(u128::div_mod::DivMod)),
         "tasmlib_arithmetic_u128_lt" => Some(Box::new(u128::lt::Lt)),
         "tasmlib_arithmetic_u128_overflowing_add" => {
             Some(Box::new(u128::overflowing_add::OverflowingAdd))
         }
+        "tasmlib_arithmetic_u128_rem" => Some(Box::new(u128::rem::Rem)),
         "tasmlib_arithmetic_u128_safe_add" => Some(Box::new(u128::safe_add::SafeAdd)),
         "tasmlib_arithmetic_u128_safe_mul" => Some(Box::new(u128::safe_mul::SafeMul)),
         "tasmlib_arithmetic_u128_shift_left" => Some(Box::new(u128::shift_left::ShiftLeft)),
@@ -209,7 +225,15 @@ pub fn name_to_snippet(fn_name: &str) -> Option<Box<dyn BasicSnippet>> {
         "tasmlib_arithmetic_u128_shift_right_static_32" => Some(Box::new(SShrU128::<32>)),
 
         // i128
+        "tasmlib_arithmetic_i128_abs" => Some(Box::new(i128::abs::Abs)),
+        "tasmlib_arithmetic_i128_div_mod" => Some(Box::new(i128::div_mod::DivMod)),
+        "tasmlib_arithmetic_i128_from_i32" => Some(Box::new(i128::from_i32::FromI32)),
+        "tasmlib_arithmetic_i128_from_i64" => Some(Box::new(i128::from_i64::FromI64)),
         "tasmlib_arithmetic_i128_lt" => Some(Box::new(i128::lt::Lt)),
+        "tasmlib_arithmetic_i128_neg" => Some(Box::new(i128::neg::Neg)),
+        "tasmlib_arithmetic_i128_safe_add" => Some(Box::new(i128::safe_add::SafeAdd)),
+        "tasmlib_arithmetic_i128_safe_mul" => Some(Box::new(i128::safe_mul::SafeMul)),
+        "tasmlib_arithmetic_i128_safe_sub" => Some(Box::new(i128::safe_sub::SafeSub)),
         "tasmlib_arithmetic_i128_shift_right" => Some(Box::new(i128::shift_right::ShiftRight)),
 
         // Hashing
@@ -226,7 +250,11 @@ pub fn name_to_snippet(fn_name: &str) -> Option<Box<dyn BasicSnippet>> {
         "tasmlib_hashing_merkle_step_u64_index" => {
             Some(Box::new(hashing::merkle_step_u64_index::MerkleStepU64Index))
         }
+        "tasmlib_hashing_merkle_update" => Some(Box::new(hashing::merkle_update::MerkleUpdate)),
         "tasmlib_hashing_merkle_verify" => Some(Box::new(hashing::merkle_verify::MerkleVerify)),
+        "tasmlib_hashing_merkle_verify_authentication_structure" => Some(Box::new(
+            hashing::merkle_verify_authentication_structure::MerkleVerifyAuthenticationStructure,
+        )),
 
         // Hashing -> algebraic hasher trait
         "tasmlib_hashing_algebraic_hasher_hash_varlen" => {
@@ -351,6 +379,35 @@ pub fn name_to_snippet(fn_name: &str) -> Option<Box<dyn BasicSnippet>> {
         "tasmlib_list_push___bfe" => Some(Box::new(list::push::Push::new(DataType::Bfe))),
         "tasmlib_list_push___xfe" => Some(Box::new(list::push::Push::new(DataType::Xfe))),
         "tasmlib_list_push___digest" => Some(Box::new(list::push::Push::new(DataType::Digest))),
+        "tasmlib_list_extend___bool" => Some(Box::new(list::extend::Extend::new(DataType::Bool))),
+        "tasmlib_list_extend___u32" => Some(Box::new(list::extend::Extend::new(DataType::U32))),
+        "tasmlib_list_extend___u64" => Some(Box::new(list::extend::Extend::new(DataType::U64))),
+        "tasmlib_list_extend___bfe" => Some(Box::new(list::extend::Extend::new(DataType::Bfe))),
+        "tasmlib_list_extend___xfe" => Some(Box::new(list::extend::Extend::new(DataType::Xfe))),
+        "tasmlib_list_extend___digest" => Some(Box::new(list::extend::Extend::new(DataType::Digest))),
+        "tasmlib_list_insert___bool" => Some(Box::new(list::insert::Insert::new(DataType::Bool))),
+        "tasmlib_list_insert___u32" => Some(Box::new(list::insert::Insert::new(DataType::U32))),
+        "tasmlib_list_insert___u64" => Some(Box::new(list::insert::Insert::new(DataType::U64))),
+        "tasmlib_list_insert___bfe" => Some(Box::new(list::insert::Insert::new(DataType::Bfe))),
+        "tasmlib_list_insert___xfe" => Some(Box::new(list::insert::Insert::new(DataType::Xfe))),
+        "tasmlib_list_insert___digest" => Some(Box::new(list::insert::Insert::new(DataType::Digest))),
+        "tasmlib_list_remove___bool" => Some(Box::new(list::remove::Remove::new(DataType::Bool))),
+        "tasmlib_list_remove___u32" => Some(Box::new(list::remove::Remove::new(DataType::U32))),
+        "tasmlib_list_remove___u64" => Some(Box::new(list::remove::Remove::new(DataType::U64))),
+        "tasmlib_list_remove___bfe" => Some(Box::new(list::remove::Remove::new(DataType::Bfe))),
+        "tasmlib_list_remove___xfe" => Some(Box::new(list::remove::Remove::new(DataType::Xfe))),
+        "tasmlib_list_remove___digest" => Some(Box::new(list::remove::Remove::new(DataType::Digest))),
+        "tasmlib_list_reverse___bool" => Some(Box::new(list::reverse::Reverse::new(DataType::Bool))),
+        "tasmlib_list_reverse___u32" => Some(Box::new(list::reverse::Reverse::new(DataType::U32))),
+        "tasmlib_list_reverse___u64" => Some(Box::new(list::reverse::Reverse::new(DataType::U64))),
+        "tasmlib_list_reverse___bfe" => Some(Box::new(list::reverse::Reverse::new(DataType::Bfe))),
+        "tasmlib_list_reverse___xfe" => Some(Box::new(list::reverse::Reverse::new(DataType::Xfe))),
+        "tasmlib_list_dedup___bool" => Some(Box::new(list::dedup::Dedup::new(DataType::Bool))),
+        "tasmlib_list_dedup___u32" => Some(Box::new(list::dedup::Dedup::new(DataType::U32))),
+        "tasmlib_list_dedup___u64" => Some(Box::new(list::dedup::Dedup::new(DataType::U64))),
+        "tasmlib_list_dedup___bfe" => Some(Box::new(list::dedup::Dedup::new(DataType::Bfe))),
+        "tasmlib_list_dedup___xfe" => Some(Box::new(list::dedup::Dedup::new(DataType::Xfe))),
+        "tasmlib_list_dedup___digest" => Some(Box::new(list::dedup::Dedup::new(DataType::Digest))),
 
         "tasmlib_list_set_element___bool" => Some(Box::new(list::set::Set::new(DataType::Bool))),
         "tasmlib_list_set_element___u32" => Some(Box::new(list::set::Set::new(DataType::U32))),
@@ -368,7 +425,60 @@ pub fn name_to_snippet(fn_name: &str) -> Option<Box<dyn BasicSnippet>> {
         "tasmlib_list_multiset_equality_digests" => Some(Box::new(
             list::multiset_equality_digests::MultisetEqualityDigests,
         )),
+        "tasmlib_list_multiset_equality___bfe" => Some(Box::new(
+            list::multiset_equality::MultisetEquality::new(DataType::Bfe),
+        )),
+        "tasmlib_list_multiset_equality___xfe" => Some(Box::new(
+            list::multiset_equality::MultisetEquality::new(DataType::Xfe),
+        )),
         "tasmlib_list_range" => Some(Box::new(list::range::Range)),
+        "tasmlib_list_elementwise_inversion_bfe" => Some(Box::new(
+            list::elementwise_inversion::ElementwiseInversion::new(DataType::Bfe),
+        )),
+        "tasmlib_list_elementwise_inversion_xfe" => Some(Box::new(
+            list::elementwise_inversion::ElementwiseInversion::new(DataType::Xfe),
+        )),
+        "tasmlib_list_binary_search___u32" => Some(Box::new(
+            list::binary_search::BinarySearch::new(DataType::U32, SearchMode::Bisection),
+        )),
+        "tasmlib_list_binary_search_with_witness___u32" => Some(Box::new(
+            list::binary_search::BinarySearch::new(DataType::U32, SearchMode::Witness),
+        )),
+        "tasmlib_list_binary_search___u64" => Some(Box::new(
+            list::binary_search::BinarySearch::new(DataType::U64, SearchMode::Bisection),
+        )),
+        "tasmlib_list_binary_search_with_witness___u64" => Some(Box::new(
+            list::binary_search::BinarySearch::new(DataType::U64, SearchMode::Witness),
+        )),
+        "tasmlib_list_binary_search___u128" => Some(Box::new(
+            list::binary_search::BinarySearch::new(DataType::U128, SearchMode::Bisection),
+        )),
+        "tasmlib_list_binary_search_with_witness___u128" => Some(Box::new(
+            list::binary_search::BinarySearch::new(DataType::U128, SearchMode::Witness),
+        )),
+        "tasmlib_list_binary_search___bfe" => Some(Box::new(
+            list::binary_search::BinarySearch::new(DataType::Bfe, SearchMode::Bisection),
+        )),
+        "tasmlib_list_binary_search_with_witness___bfe" => Some(Box::new(
+            list::binary_search::BinarySearch::new(DataType::Bfe, SearchMode::Witness),
+        )),
+        "tasmlib_list_binary_search___digest" => Some(Box::new(
+            list::binary_search::BinarySearch::new(DataType::Digest, SearchMode::Bisection),
+        )),
+        "tasmlib_list_binary_search_with_witness___digest" => Some(Box::new(
+            list::binary_search::BinarySearch::new(DataType::Digest, SearchMode::Witness),
+        )),
+        "tasmlib_list_sort___u32" => Some(Box::new(list::sort::Sort::new(DataType::U32))),
+        "tasmlib_list_sort___u64" => Some(Box::new(list::sort::Sort::new(DataType::U64))),
+        "tasmlib_list_sort___u128" => Some(Box::new(list::sort::Sort::new(DataType::U128))),
+        "tasmlib_list_sort___bfe" => Some(Box::new(list::sort::Sort::new(DataType::Bfe))),
+        "tasmlib_list_sort___digest" => Some(Box::new(list::sort::Sort::new(DataType::Digest))),
+        "tasmlib_list_sort_nondeterministic___u64" => Some(Box::new(
+            list::sort_nondeterministic::SortNondeterministic::new(DataType::U64),
+        )),
+        "tasmlib_list_sort_nondeterministic___digest" => Some(Box::new(
+            list::sort_nondeterministic::SortNondeterministic::new(DataType::Digest),
+        )),
 
         // MMR
         "tasmlib_mmr_bag_peaks" => Some(Box::new(BagPeaks)),
@@ -542,8 +652,12 @@ pub fn name_to_snippet(fn_name: &str) -> Option<Box<dyn BasicSnippet>> {
         )),
 
         // memory
+        "tasmlib_memory_arena_malloc" => Some(Box::new(ArenaMalloc)),
         "tasmlib_memory_dyn_malloc" => Some(Box::new(DynMalloc)),
+        "tasmlib_memory_memcmp" => Some(Box::new(MemCmp)),
         "tasmlib_memory_memcpy" => Some(Box::new(MemCpy)),
+        "tasmlib_memory_memmove" => Some(Box::new(MemMove)),
+        "tasmlib_memory_memset" => Some(Box::new(MemSet)),
 
         // FRI
         #[cfg(not(test))]
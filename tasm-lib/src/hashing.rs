@@ -7,7 +7,9 @@ pub mod merkle_root;
 pub mod merkle_root_from_xfes;
 pub mod merkle_step_mem_u64_index;
 pub mod merkle_step_u64_index;
+pub mod merkle_update;
 pub mod merkle_verify;
+pub mod merkle_verify_authentication_structure;
 pub mod sponge_hasher;
 pub mod squeeze_repeatedly;
 pub mod squeeze_repeatedly_static_number;
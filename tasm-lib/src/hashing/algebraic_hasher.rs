@@ -1,4 +1,5 @@
 pub mod hash_static_size;
+pub mod hash_tasm_object;
 pub mod hash_varlen;
 pub mod sample_indices;
 pub mod sample_scalar_one;
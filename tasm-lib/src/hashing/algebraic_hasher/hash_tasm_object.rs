@@ -0,0 +1,266 @@
+use std::marker::PhantomData;
+
+use triton_vm::prelude::*;
+
+use crate::hashing::algebraic_hasher::hash_varlen::HashVarlen;
+use crate::prelude::*;
+
+/// Hash a [`TasmObject`] that lives in memory.
+///
+/// The size of the object is computed from its size indicators, which are
+/// verified in the process. The resulting digest equals the host-side
+/// [`Tip5::hash`] of the object.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *object
+/// AFTER:  _ [digest: Digest]
+/// ```
+///
+/// ### Preconditions
+///
+/// None.
+///
+/// ### Postconditions
+///
+/// - the object's size indicators are consistent with its encoding
+#[derive(Debug, Clone)]
+pub struct HashTasmObject<T: TasmObject> {
+    _phantom_data: PhantomData<T>,
+}
+
+impl<T: TasmObject> Default for HashTasmObject<T> {
+    fn default() -> Self {
+        Self {
+            _phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<T: TasmObject> BasicSnippet for HashTasmObject<T> {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*object".to_owned())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::Digest, "digest".to_owned())]
+    }
+
+    fn entrypoint(&self) -> String {
+        let name = T::label_friendly_name();
+        format!("tasmlib_hashing_algebraic_hasher_hash_tasm_object___{name}")
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let compute_size = T::compute_size_and_assert_valid_size_indicator(library);
+        let hash_varlen = library.import(Box::new(HashVarlen));
+
+        triton_asm!(
+            // BEFORE: _ *object
+            // AFTER:  _ [digest: Digest]
+            {self.entrypoint()}:
+                dup 0
+                {&compute_size}
+                // _ *object size
+
+                call {hash_varlen}
+                // _ [digest: Digest]
+
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::fmt::Debug;
+
+    use arbitrary::Arbitrary;
+    use arbitrary::Unstructured;
+
+    use super::*;
+    use crate::memory::encode_to_memory;
+    use crate::neptune::neptune_like_types_for_tests::*;
+    use crate::test_helpers::test_assertion_failure;
+    use crate::test_helpers::test_rust_equivalence_given_complete_state;
+    use crate::test_prelude::*;
+
+    impl<T> HashTasmObject<T>
+    where
+        T: TasmObject + BFieldCodec + for<'a> Arbitrary<'a>,
+    {
+        fn initial_state(&self, address: BFieldElement, t: &T) -> MemPreserverInitialState {
+            let mut ram = HashMap::default();
+            encode_to_memory(&mut ram, address, t);
+
+            MemPreserverInitialState {
+                stack: [self.init_stack_for_isolated_run(), vec![address]].concat(),
+                nondeterminism: NonDeterminism::default().with_ram(ram),
+                ..MemPreserverInitialState::default()
+            }
+        }
+
+        fn prepare_random_object(randomness: &[u8]) -> T {
+            T::arbitrary_take_rest(Unstructured::new(randomness)).unwrap()
+        }
+    }
+
+    impl<T> MemPreserver for HashTasmObject<T>
+    where
+        T: TasmObject + BFieldCodec + for<'a> Arbitrary<'a> + Debug + Clone,
+    {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &HashMap<BFieldElement, BFieldElement>,
+            nd_tokens: VecDeque<BFieldElement>,
+            nd_digests: VecDeque<Digest>,
+            stdin: VecDeque<BFieldElement>,
+            sponge: &mut Option<Tip5>,
+        ) -> Vec<BFieldElement> {
+            // If the type can be decoded then it must have valid size indicators
+            let pointer = stack.pop().unwrap();
+            let obj = *T::decode_from_memory(memory, pointer).unwrap();
+
+            stack.push(pointer);
+            stack.push(bfe!(obj.encode().len() as u64));
+            HashVarlen.rust_shadow(stack, memory, nd_tokens, nd_digests, stdin, sponge)
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            _bench_case: Option<BenchmarkCase>,
+        ) -> MemPreserverInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let mut randomness = vec![0u8; 100_000];
+            rng.fill(randomness.as_mut_slice());
+            let t = Self::prepare_random_object(&randomness);
+
+            let address = bfe!(rng.random_range(0..1_u64 << 30));
+            self.initial_state(address, &t)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<MemPreserverInitialState> {
+            let empty_object = Self::prepare_random_object(&[]);
+            vec![self.initial_state(bfe!(0), &empty_object)]
+        }
+    }
+
+    macro_rules! test_case {
+        (fn $test_name:ident for $t:ty) => {
+            #[test]
+            fn $test_name() {
+                ShadowedMemPreserver::new(HashTasmObject::<$t>::default()).test();
+            }
+        };
+    }
+
+    #[derive(Debug, Clone, TasmObject, BFieldCodec, Arbitrary)]
+    struct TestStruct {
+        a: Vec<u128>,
+        b: Digest,
+        c: Option<Vec<XFieldElement>>,
+        d: Vec<Vec<BFieldElement>>,
+    }
+
+    #[derive(Debug, Clone, TasmObject, BFieldCodec, Arbitrary)]
+    enum TestEnum {
+        A,
+        B(Digest),
+        C(Vec<u64>),
+    }
+
+    test_case! { fn test_struct for TestStruct }
+    test_case! { fn test_enum for TestEnum }
+    test_case! { fn digest for Digest }
+    test_case! { fn vec_of_xfes for Vec<XFieldElement> }
+    test_case! { fn option_neptune_coins for Option<NeptuneCoinsLookalike> }
+    test_case! { fn utxo for UtxoLookalike }
+    test_case! { fn proof_collection for ProofCollectionLookalike }
+    test_case! { fn transaction_kernel for TransactionKernelLookalike }
+
+    fn assert_digest_agrees_with_host<T>(randomness: &[u8], address: BFieldElement)
+    where
+        T: TasmObject + BFieldCodec + for<'a> Arbitrary<'a> + Debug + Clone,
+    {
+        let snippet = HashTasmObject::<T>::default();
+        let obj = HashTasmObject::<T>::prepare_random_object(randomness);
+        let initial_state = snippet.initial_state(address, &obj);
+
+        let mut expected_stack = snippet.init_stack_for_isolated_run();
+        push_encodable(&mut expected_stack, &Tip5::hash(&obj));
+
+        test_rust_equivalence_given_complete_state(
+            &ShadowedMemPreserver::new(snippet),
+            &initial_state.stack,
+            &[],
+            &initial_state.nondeterminism,
+            &initial_state.sponge_state,
+            Some(&expected_stack),
+        );
+    }
+
+    #[proptest(cases = 20)]
+    fn digest_agrees_with_host_hash_of_struct(
+        #[strategy(prop::collection::vec(arb(), 0..2_000))] randomness: Vec<u8>,
+        #[strategy(0_u64..1 << 30)] address: u64,
+    ) {
+        assert_digest_agrees_with_host::<TestStruct>(&randomness, bfe!(address));
+    }
+
+    #[proptest(cases = 20)]
+    fn digest_agrees_with_host_hash_of_enum(
+        #[strategy(prop::collection::vec(arb(), 0..200))] randomness: Vec<u8>,
+        #[strategy(0_u64..1 << 30)] address: u64,
+    ) {
+        assert_digest_agrees_with_host::<TestEnum>(&randomness, bfe!(address));
+    }
+
+    #[proptest(cases = 10)]
+    fn digest_agrees_with_host_hash_of_transaction_kernel(
+        #[strategy(prop::collection::vec(arb(), 0..20_000))] randomness: Vec<u8>,
+    ) {
+        assert_digest_agrees_with_host::<TransactionKernelLookalike>(&randomness, bfe!(42));
+    }
+
+    #[test]
+    fn lying_size_indicator_crashes_vm() {
+        let snippet = HashTasmObject::<TestStruct>::default();
+        let obj = HashTasmObject::<TestStruct>::prepare_random_object(&[42; 2_000]);
+
+        let address = bfe!(4);
+        let mut initial_state = snippet.initial_state(address, &obj);
+        let true_value = initial_state.nondeterminism.ram[&address];
+        initial_state
+            .nondeterminism
+            .ram
+            .insert(address, true_value + bfe!(1));
+
+        test_assertion_failure(
+            &ShadowedMemPreserver::new(snippet),
+            initial_state.into(),
+            &[181],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::neptune::neptune_like_types_for_tests::ProofCollectionLookalike;
+    use crate::neptune::neptune_like_types_for_tests::TransactionKernelLookalike;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn bench_proof_collection_lookalike() {
+        ShadowedMemPreserver::new(HashTasmObject::<ProofCollectionLookalike>::default()).bench();
+    }
+
+    #[test]
+    fn bench_transaction_kernel_lookalike() {
+        ShadowedMemPreserver::new(HashTasmObject::<TransactionKernelLookalike>::default()).bench();
+    }
+}
@@ -0,0 +1,442 @@
+use triton_vm::prelude::*;
+
+use crate::prelude::*;
+
+/// Update a leaf in a [Merkle tree](twenty_first::prelude::MerkleTree).
+///
+/// Given the tree's root, its height, the index of the leaf to update, the old
+/// leaf, and the new leaf, verify that the old leaf lives in the Merkle tree
+/// and compute the root of the Merkle tree in which the old leaf is replaced by
+/// the new leaf. The authentication path is non-deterministically divined; it
+/// is used for both, the verification and the computation of the new root.
+/// If membership of the old leaf could not be established, the VM crashes.
+///
+/// See also [`MerkleVerify`](super::merkle_verify::MerkleVerify).
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ [root: Digest] tree_height leaf_index [old_leaf: Digest] [new_leaf: Digest]
+/// AFTER:  _ [new_root: Digest]
+/// ```
+///
+/// ### Preconditions
+///
+/// - all input arguments are properly [`BFieldCodec`] encoded
+/// - the authentication path is supplied as individual non-deterministic
+///   tokens, starting with the leaf's sibling
+///
+/// ### Postconditions
+///
+/// - all output is properly [`BFieldCodec`] encoded
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct MerkleUpdate;
+
+impl MerkleUpdate {
+    pub const TREE_TOO_HIGH_ERROR_ID: i128 = 670;
+    pub const OUT_OF_BOUNDS_LEAF_ERROR_ID: i128 = 671;
+    pub const ROOT_MISMATCH_ERROR_ID: i128 = 672;
+}
+
+impl BasicSnippet for MerkleUpdate {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::Digest, "root".to_string()),
+            (DataType::U32, "tree_height".to_string()),
+            (DataType::U32, "leaf_index".to_string()),
+            (DataType::Digest, "old_leaf".to_string()),
+            (DataType::Digest, "new_leaf".to_string()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::Digest, "new_root".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_hashing_merkle_update".to_string()
+    }
+
+    fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let traverse_tree = format!("{entrypoint}_traverse_tree");
+        let swap_digests = format!("{entrypoint}_swap_digests");
+
+        triton_asm!(
+            // BEFORE: _ [root; 5] tree_height leaf_index [old_leaf; 5] [new_leaf; 5]
+            // AFTER:  _ [new_root; 5]
+            {entrypoint}:
+                /* Assert reasonable tree height.
+                 * See `MerkleVerify` for why this is important.
+                 */
+                push 32
+                dup 12
+                lt
+                assert error_id {Self::TREE_TOO_HIGH_ERROR_ID}
+
+                /* Calculate node index from tree height and leaf index */
+                dup 11
+                push 2
+                pow
+                // _ [root; 5] tree_height leaf_index [old_leaf; 5] [new_leaf; 5] num_leaves
+
+                dup 0 dup 12 lt
+                assert error_id {Self::OUT_OF_BOUNDS_LEAF_ERROR_ID}
+
+                pick 11
+                add
+                place 10
+                pick 11
+                pop 1
+                // _ [root; 5] node_index [old_leaf; 5] [new_leaf; 5]
+
+                call {traverse_tree}
+                // _ [root; 5] 1 [calculated_root; 5] [new_root; 5]
+
+                pick 10
+                pop 1
+                place 14
+                place 14
+                place 14
+                place 14
+                place 14
+                // _ [new_root; 5] [root; 5] [calculated_root; 5]
+
+                /* compare calculated and provided root */
+                assert_vector error_id {Self::ROOT_MISMATCH_ERROR_ID}
+                pop 5
+                return
+
+            // INVARIANT: _ node_index [old_node; 5] [new_node; 5]
+            {traverse_tree}:
+                dup 10
+                push 1
+                eq
+                skiz return
+
+                dup 10
+                push 1
+                and
+                divine 5
+                dup 4
+                dup 4
+                dup 4
+                dup 4
+                dup 4
+                // _ node_index [old_node; 5] [new_node; 5] node_is_right_sibling [sibling; 5] [sibling; 5]
+
+                pick 15
+                pick 15
+                pick 15
+                pick 15
+                pick 15
+                // _ node_index [old_node; 5] node_is_right_sibling [sibling; 5] [sibling; 5] [new_node; 5]
+
+                /* instruction `hash` expects the left node on top */
+                dup 15
+                skiz
+                    call {swap_digests}
+                hash
+                // _ node_index [old_node; 5] node_is_right_sibling [sibling; 5] [new_parent; 5]
+
+                place 15
+                place 15
+                place 15
+                place 15
+                place 15
+                // _ node_index [new_parent; 5] [old_node; 5] node_is_right_sibling [sibling; 5]
+
+                pick 5
+                push 0
+                eq
+                skiz
+                    call {swap_digests}
+                hash
+                // _ node_index [new_parent; 5] [old_parent; 5]
+
+                place 9
+                place 9
+                place 9
+                place 9
+                place 9
+                // _ node_index [old_parent; 5] [new_parent; 5]
+
+                push 2
+                pick 11
+                div_mod
+                pop 1
+                place 10
+                // _ (node_index / 2) [old_parent; 5] [new_parent; 5]
+
+                recurse
+
+            // BEFORE: _ [a; 5] [b; 5]
+            // AFTER:  _ [b; 5] [a; 5]
+            {swap_digests}:
+                pick 9
+                pick 9
+                pick 9
+                pick 9
+                pick 9
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use twenty_first::prelude::MerkleTree;
+
+    use super::*;
+    use crate::test_prelude::*;
+
+    impl MerkleUpdate {
+        fn set_up_initial_state(
+            root: Digest,
+            tree_height: u32,
+            leaf_index: u32,
+            old_leaf: Digest,
+            new_leaf: Digest,
+            authentication_path: &[Digest],
+        ) -> ReadOnlyAlgorithmInitialState {
+            let mut stack = Self.init_stack_for_isolated_run();
+            push_encodable(&mut stack, &root);
+            push_encodable(&mut stack, &tree_height);
+            push_encodable(&mut stack, &leaf_index);
+            push_encodable(&mut stack, &old_leaf);
+            push_encodable(&mut stack, &new_leaf);
+
+            let individual_tokens = authentication_path
+                .iter()
+                .flat_map(|digest| digest.reversed().values())
+                .collect_vec();
+            let nondeterminism = NonDeterminism::new(individual_tokens);
+
+            ReadOnlyAlgorithmInitialState {
+                stack,
+                nondeterminism,
+            }
+        }
+    }
+
+    impl ReadOnlyAlgorithm for MerkleUpdate {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            _: &HashMap<BFieldElement, BFieldElement>,
+            mut nd_tokens: VecDeque<BFieldElement>,
+            _: VecDeque<Digest>,
+        ) {
+            let new_leaf = pop_encodable::<Digest>(stack);
+            let old_leaf = pop_encodable::<Digest>(stack);
+            let leaf_index = pop_encodable::<u32>(stack);
+            let tree_height = pop_encodable::<u32>(stack);
+            let root = pop_encodable::<Digest>(stack);
+
+            assert!(tree_height < 32);
+            let num_leaves = 1 << tree_height;
+            assert!(leaf_index < num_leaves);
+
+            let mut old_node = old_leaf;
+            let mut new_node = new_leaf;
+            let mut node_index = leaf_index + num_leaves;
+            while node_index != 1 {
+                let mut sibling = [bfe!(0); Digest::LEN];
+                for word in sibling.iter_mut().rev() {
+                    *word = nd_tokens.pop_front().unwrap();
+                }
+                let sibling = Digest::new(sibling);
+
+                let node_is_left_sibling = node_index.is_multiple_of(2);
+                (old_node, new_node) = if node_is_left_sibling {
+                    let old_node = Tip5::hash_pair(old_node, sibling);
+                    (old_node, Tip5::hash_pair(new_node, sibling))
+                } else {
+                    let old_node = Tip5::hash_pair(sibling, old_node);
+                    (old_node, Tip5::hash_pair(sibling, new_node))
+                };
+                node_index /= 2;
+            }
+            assert_eq!(root, old_node);
+
+            push_encodable(stack, &new_node);
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> ReadOnlyAlgorithmInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let tree_height = match bench_case {
+                Some(BenchmarkCase::CommonCase) => 6,
+                Some(BenchmarkCase::WorstCase) => 20,
+                None => rng.random_range(0..20),
+            };
+
+            // sample unconstrained inputs directly
+            let num_leaves: u32 = 1 << tree_height;
+            let leaf_index = rng.random_range(0..num_leaves);
+            let path = (0..tree_height).map(|_| rng.random()).collect_vec();
+            let old_leaf = rng.random();
+            let new_leaf = rng.random();
+
+            // walk up tree to calculate root
+            let mut current_node = old_leaf;
+            let mut node_index = leaf_index + num_leaves;
+            for &sibling in &path {
+                let node_is_left_sibling = node_index.is_multiple_of(2);
+                current_node = if node_is_left_sibling {
+                    Tip5::hash_pair(current_node, sibling)
+                } else {
+                    Tip5::hash_pair(sibling, current_node)
+                };
+                node_index /= 2;
+            }
+            let root = current_node;
+
+            Self::set_up_initial_state(root, tree_height, leaf_index, old_leaf, new_leaf, &path)
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedReadOnlyAlgorithm::new(MerkleUpdate).test();
+    }
+
+    #[proptest(cases = 20)]
+    fn new_root_matches_root_of_updated_tree(
+        #[strategy(0_u32..10)] tree_height: u32,
+        #[strategy(prop::collection::vec(arb(), 1 << #tree_height))] leafs: Vec<Digest>,
+        #[strategy(0_u32..1 << #tree_height)] leaf_index: u32,
+        #[strategy(arb())] new_leaf: Digest,
+    ) {
+        let tree = MerkleTree::par_new(&leafs).unwrap();
+        let authentication_path = tree
+            .authentication_structure(&[leaf_index as usize])
+            .unwrap();
+        let initial_state = MerkleUpdate::set_up_initial_state(
+            tree.root(),
+            tree_height,
+            leaf_index,
+            leafs[leaf_index as usize],
+            new_leaf,
+            &authentication_path,
+        );
+
+        let mut updated_leafs = leafs;
+        updated_leafs[leaf_index as usize] = new_leaf;
+        let updated_tree = MerkleTree::par_new(&updated_leafs).unwrap();
+
+        let mut expected_stack = MerkleUpdate.init_stack_for_isolated_run();
+        push_encodable(&mut expected_stack, &updated_tree.root());
+
+        test_rust_equivalence_given_complete_state(
+            &ShadowedReadOnlyAlgorithm::new(MerkleUpdate),
+            &initial_state.stack,
+            &[],
+            &initial_state.nondeterminism,
+            &None,
+            Some(&expected_stack),
+        );
+    }
+
+    #[proptest]
+    fn update_fails_if_old_leaf_is_disturbed_slightly(
+        seed: [u8; 32],
+        #[strategy(5_usize..10)] perturbation_index: usize,
+        #[filter(#perturbation != 0)] perturbation: i8,
+    ) {
+        let mut initial_state = MerkleUpdate.pseudorandom_initial_state(seed, None);
+        let top_of_stack = initial_state.stack.len() - 1;
+        initial_state.stack[top_of_stack - perturbation_index] += bfe!(perturbation);
+
+        test_assertion_failure(
+            &ShadowedReadOnlyAlgorithm::new(MerkleUpdate),
+            initial_state.into(),
+            &[MerkleUpdate::ROOT_MISMATCH_ERROR_ID],
+        );
+    }
+
+    #[proptest]
+    fn update_fails_if_root_is_disturbed_slightly(
+        seed: [u8; 32],
+        #[strategy(12_usize..17)] perturbation_index: usize,
+        #[filter(#perturbation != 0)] perturbation: i8,
+    ) {
+        let mut initial_state = MerkleUpdate.pseudorandom_initial_state(seed, None);
+        let top_of_stack = initial_state.stack.len() - 1;
+        initial_state.stack[top_of_stack - perturbation_index] += bfe!(perturbation);
+
+        test_assertion_failure(
+            &ShadowedReadOnlyAlgorithm::new(MerkleUpdate),
+            initial_state.into(),
+            &[MerkleUpdate::ROOT_MISMATCH_ERROR_ID],
+        );
+    }
+
+    #[proptest]
+    fn update_fails_if_authentication_path_is_disturbed_slightly(
+        seed: [u8; 32],
+        #[strategy(any::<prop::sample::Index>())] perturbation_index: prop::sample::Index,
+        #[filter(#perturbation != 0)] perturbation: i8,
+    ) {
+        let mut initial_state = MerkleUpdate.pseudorandom_initial_state(seed, None);
+        let tokens = &mut initial_state.nondeterminism.individual_tokens;
+        prop_assume!(!tokens.is_empty());
+        let perturbation_index = perturbation_index.index(tokens.len());
+        tokens[perturbation_index] += bfe!(perturbation);
+
+        test_assertion_failure(
+            &ShadowedReadOnlyAlgorithm::new(MerkleUpdate),
+            initial_state.into(),
+            &[MerkleUpdate::ROOT_MISMATCH_ERROR_ID],
+        );
+    }
+
+    #[proptest]
+    fn update_fails_if_leaf_index_is_out_of_bounds(
+        seed: [u8; 32],
+        #[strategy(0_u32..1 << 10)] excess: u32,
+    ) {
+        let mut initial_state = MerkleUpdate.pseudorandom_initial_state(seed, None);
+        let top_of_stack = initial_state.stack.len() - 1;
+        let tree_height = initial_state.stack[top_of_stack - 11].value();
+        initial_state.stack[top_of_stack - 10] = bfe!((1 << tree_height) + excess);
+
+        test_assertion_failure(
+            &ShadowedReadOnlyAlgorithm::new(MerkleUpdate),
+            initial_state.into(),
+            &[MerkleUpdate::OUT_OF_BOUNDS_LEAF_ERROR_ID],
+        );
+    }
+
+    #[proptest]
+    fn update_fails_if_tree_height_is_too_large(
+        seed: [u8; 32],
+        #[strategy(32_u32..)] tree_height: u32,
+    ) {
+        let mut initial_state = MerkleUpdate.pseudorandom_initial_state(seed, None);
+        let top_of_stack = initial_state.stack.len() - 1;
+        initial_state.stack[top_of_stack - 11] = bfe!(tree_height);
+
+        test_assertion_failure(
+            &ShadowedReadOnlyAlgorithm::new(MerkleUpdate),
+            initial_state.into(),
+            &[MerkleUpdate::TREE_TOO_HIGH_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedReadOnlyAlgorithm::new(MerkleUpdate).bench();
+    }
+}
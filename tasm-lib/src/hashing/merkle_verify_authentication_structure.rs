@@ -0,0 +1,808 @@
+use triton_vm::prelude::*;
+
+use crate::prelude::*;
+
+/// Verify membership of multiple leafs in a
+/// [Merkle tree](twenty_first::prelude::MerkleTree) using a de-duplicated
+/// [authentication structure][auth_structure].
+///
+/// Verify that all the indexed leafs live in a Merkle tree, given the tree's
+/// root, its height, the list of (leaf index, leaf) pairs, and the
+/// authentication structure for those leaf indices. In contrast to
+/// [`MerkleVerify`](super::merkle_verify::MerkleVerify), no internal node is
+/// hashed more than once, even if the authentication paths of the individual
+/// leafs overlap. This algorithm asserts that the leafs are members of the
+/// tree; phrased differently, if membership could not be established, it
+/// crashes the VM.
+///
+/// The semantics match those of [`MerkleTreeInclusionProof::verify`][verify]:
+/// - leaf indices may appear in any order,
+/// - a leaf index may be repeated, in which case the leafs must be identical,
+/// - the authentication structure must contain exactly the required nodes, in
+///   descending order of their node indices, and
+/// - if there are no leafs and the authentication structure is empty, the
+///   verification succeeds for any root.
+///
+/// The nodes that can be computed are kept in a queue, sorted by node index in
+/// descending order, which lives in a freshly
+/// [allocated](crate::memory::dyn_malloc::DynMalloc) memory page.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ [root: Digest] tree_height *indexed_leafs *authentication_structure
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// - all input arguments are properly [`BFieldCodec`] encoded
+/// - the argument `*indexed_leafs` points to a properly [`BFieldCodec`]-encoded
+///   list of type `Vec<(u32, Digest)>`
+/// - the argument `*authentication_structure` points to a properly
+///   [`BFieldCodec`]-encoded list of type `Vec<Digest>`
+///
+/// ### Postconditions
+///
+/// None.
+///
+/// [auth_structure]: twenty_first::prelude::MerkleTree::authentication_structure
+/// [verify]: twenty_first::util_types::merkle_tree::MerkleTreeInclusionProof::verify
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct MerkleVerifyAuthenticationStructure;
+
+impl MerkleVerifyAuthenticationStructure {
+    pub const TREE_TOO_HIGH_ERROR_ID: i128 = 660;
+    pub const OUT_OF_BOUNDS_LEAF_ERROR_ID: i128 = 661;
+    pub const REPEATED_LEAF_MISMATCH_ERROR_ID: i128 = 662;
+    pub const ROOT_MISMATCH_ERROR_ID: i128 = 663;
+    pub const AUTHENTICATION_STRUCTURE_LENGTH_MISMATCH_ERROR_ID: i128 = 664;
+
+    /// The number of words a (node index, node) pair occupies in memory.
+    const NODE_SIZE: usize = 1 + Digest::LEN;
+}
+
+impl BasicSnippet for MerkleVerifyAuthenticationStructure {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let indexed_leaf = DataType::Tuple(vec![DataType::U32, DataType::Digest]);
+        vec![
+            (DataType::Digest, "root".to_string()),
+            (DataType::U32, "tree_height".to_string()),
+            (
+                DataType::List(Box::new(indexed_leaf)),
+                "*indexed_leafs".to_string(),
+            ),
+            (
+                DataType::List(Box::new(DataType::Digest)),
+                "*authentication_structure".to_string(),
+            ),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_hashing_merkle_verify_authentication_structure".to_string()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let insert_leafs = format!("{entrypoint}_insert_leafs");
+        let find_position = format!("{entrypoint}_find_position");
+        let check_repeated_leaf = format!("{entrypoint}_check_repeated_leaf");
+        let insert_node = format!("{entrypoint}_insert_node");
+        let shift_nodes = format!("{entrypoint}_shift_nodes");
+        let compute_and_compare_root = format!("{entrypoint}_compute_and_compare_root");
+        let compute_root = format!("{entrypoint}_compute_root");
+
+        let dyn_malloc = library.import(Box::new(DynMalloc));
+
+        let node_size = Self::NODE_SIZE;
+
+        triton_asm!(
+            // BEFORE: _ [root; 5] tree_height *indexed_leafs *auth_structure
+            // AFTER:  _
+            {entrypoint}:
+                /* Assert reasonable tree height.
+                 * See `MerkleVerify` for why this is important.
+                 */
+                push 32
+                dup 3
+                lt
+                assert error_id {Self::TREE_TOO_HIGH_ERROR_ID}
+
+                pick 2
+                push 2
+                pow             // _ [root; 5] *indexed_leafs *auth_structure num_leafs
+
+                call {dyn_malloc}
+                                // _ [root; 5] *indexed_leafs *auth_structure num_leafs *queue
+                pick 3
+                read_mem 1
+                addi 2          // _ [root; 5] *auth_structure num_leafs *queue len *indexed_leafs[0]
+                pick 1
+                push {node_size}
+                mul
+                dup 1
+                add             // _ [root; 5] *auth_structure num_leafs *queue *indexed_leafs[0] *end
+                dup 2
+                place 2         // _ [root; 5] *auth_structure num_leafs *queue *queue *indexed_leafs[0] *end
+
+                call {insert_leafs}
+                pop 2           // _ [root; 5] *auth_structure num_leafs *queue *tail
+                pick 2
+                pop 1           // _ [root; 5] *auth_structure *queue *tail
+
+                pick 2
+                read_mem 1
+                addi 2          // _ [root; 5] *queue *tail len *auth_structure[0]
+                dup 0
+                pick 2
+                push {Digest::LEN}
+                mul
+                add             // _ [root; 5] *queue *tail *auth_structure[0] *auth_structure_end
+                place 3
+                place 2
+                pick 1          // _ [root; 5] *auth_structure_end *auth_structure[0] *tail *queue
+
+                /* an empty proof is valid for any root */
+                dup 1
+                dup 1
+                eq
+                push 0
+                eq
+                skiz
+                    call {compute_and_compare_root}
+                                // _ [root; 5] *auth_structure_end *auth_structure[i] *tail *head
+
+                /* assert that the authentication structure was consumed entirely */
+                pop 2
+                eq
+                assert error_id {Self::AUTHENTICATION_STRUCTURE_LENGTH_MISMATCH_ERROR_ID}
+                pop 5
+                return
+
+            // Sorts the leafs into the queue by descending node index. Repeated
+            // leafs are inserted only once.
+            //
+            // INVARIANT: _ num_leafs *queue *tail *indexed_leafs[i] *end
+            {insert_leafs}:
+                dup 1
+                dup 1
+                eq
+                skiz return
+
+                dup 1
+                addi {node_size - 1}
+                read_mem 1
+                read_mem {Digest::LEN}
+                pop 1           // _ num_leafs *queue *tail *indexed_leafs[i] *end leaf_index [leaf; 5]
+                pick 7
+                addi {node_size}
+                place 7         // _ num_leafs *queue *tail *indexed_leafs[i+1] *end leaf_index [leaf; 5]
+
+                dup 10
+                dup 6
+                lt
+                assert error_id {Self::OUT_OF_BOUNDS_LEAF_ERROR_ID}
+
+                pick 5
+                dup 10
+                add
+                place 5         // _ num_leafs *queue *tail *indexed_leafs[i+1] *end node_index [leaf; 5]
+
+                dup 8
+                call {find_position}
+                                // _ num_leafs *queue *tail *indexed_leafs[i+1] *end node_index [leaf; 5] *position
+
+                /* check whether the preceding node has the same node index */
+                dup 0
+                addi -1
+                read_mem 1
+                pop 1
+                dup 7
+                eq
+                dup 11
+                dup 2
+                eq
+                push 0
+                eq
+                mul             // _ [...] node_index [leaf; 5] *position is_repeated_leaf
+
+                push 1
+                pick 1
+                skiz
+                    call {check_repeated_leaf}
+                skiz
+                    call {insert_node}
+                                // _ num_leafs *queue *tail' *indexed_leafs[i+1] *end node_index [leaf; 5] *position
+
+                pop 5
+                pop 2
+                recurse
+
+            // Walks the queue from its end towards its beginning until the node
+            // preceding `*position` has a node index that is at least as large
+            // as the given node index.
+            //
+            // INVARIANT: _ *queue *tail *indexed_leafs[i] *end node_index [leaf; 5] *position
+            {find_position}:
+                dup 10
+                dup 1
+                eq
+                skiz return
+
+                dup 0
+                addi -1
+                read_mem 1
+                pop 1           // _ *queue *tail *indexed_leafs[i] *end node_index [leaf; 5] *position preceding_node_index
+                dup 7
+                pick 1
+                lt
+                push 0
+                eq
+                skiz return
+
+                addi {-(node_size as isize)}
+                recurse
+
+            // BEFORE: _ node_index [leaf; 5] *position 1
+            // AFTER:  _ node_index [leaf; 5] *position 0
+            {check_repeated_leaf}:
+                dup 1
+                addi -2
+                read_mem {Digest::LEN}
+                pop 1           // _ node_index [leaf; 5] *position 1 [preceding_leaf; 5]
+                dup 11
+                dup 11
+                dup 11
+                dup 11
+                dup 11
+                assert_vector error_id {Self::REPEATED_LEAF_MISMATCH_ERROR_ID}
+                pop 5
+                pop 1
+                push 0
+                return
+
+            // BEFORE: _ *tail *indexed_leafs[i] *end node_index [leaf; 5] *position
+            // AFTER:  _ *tail' *indexed_leafs[i] *end node_index [leaf; 5] *position
+            {insert_node}:
+                dup 9
+                call {shift_nodes}
+                pop 1           // _ *tail *indexed_leafs[i] *end node_index [leaf; 5] *position
+
+                dup 6
+                dup 6
+                dup 6
+                dup 6
+                dup 6
+                dup 6
+                dup 6
+                write_mem {Digest::LEN}
+                write_mem 1
+                pop 1           // _ *tail *indexed_leafs[i] *end node_index [leaf; 5] *position
+
+                pick 9
+                addi {node_size}
+                place 9         // _ *tail' *indexed_leafs[i] *end node_index [leaf; 5] *position
+                return
+
+            // Moves every node in the range [*position, *current) one slot
+            // towards the end of the queue, starting with the last one.
+            //
+            // INVARIANT: _ *position *current
+            {shift_nodes}:
+                dup 1
+                dup 1
+                eq
+                skiz return
+
+                dup 0
+                addi -1
+                read_mem {Digest::LEN}
+                read_mem 1
+                pop 1           // _ *position *current node_index [node; 5]
+                dup 6
+                write_mem {Digest::LEN}
+                write_mem 1
+                pop 1           // _ *position *current
+
+                addi {-(node_size as isize)}
+                recurse
+
+            // BEFORE: _ [root; 5] *auth_structure_end *auth_structure[0] *tail *queue
+            // AFTER:  _ [root; 5] *auth_structure_end *auth_structure[i] *tail *head
+            {compute_and_compare_root}:
+                call {compute_root}
+                                // _ [root; 5] *auth_structure_end *auth_structure[i] *tail *head 1 [computed_root; 5]
+                dup 14
+                dup 14
+                dup 14
+                dup 14
+                dup 14
+                assert_vector error_id {Self::ROOT_MISMATCH_ERROR_ID}
+                pop 5
+                pop 1
+                return
+
+            // Processes the queue's nodes in order of descending node index. The
+            // sibling of the current node is either the next node in the queue
+            // or the next node of the authentication structure. The parent node
+            // is appended to the queue.
+            //
+            // INVARIANT: _ *auth_structure[i] *tail *head
+            {compute_root}:
+                dup 0
+                addi {node_size - 1}
+                read_mem 1
+                read_mem {Digest::LEN}
+                pop 1           // _ *auth_structure[i] *tail *head node_index [node; 5]
+
+                dup 5
+                push 1
+                eq
+                skiz return
+
+                pick 6
+                addi {node_size}
+                place 6         // _ *auth_structure[i] *tail *head' node_index [node; 5]
+
+                /* The next node in the queue is the current node's sibling if
+                 * and only if the queue is not empty and the node indices match.
+                 */
+                dup 6
+                addi {node_size - 1}
+                read_mem 1
+                pop 1
+                dup 6
+                push 1
+                xor
+                eq
+                dup 8
+                dup 8
+                eq
+                push 0
+                eq
+                mul             // _ *auth_structure[i] *tail *head' node_index [node; 5] sibling_is_in_queue
+
+                /* *sibling = *auth_structure[i] + sibling_is_in_queue·(*head' - *auth_structure[i]) */
+                dup 7
+                dup 10
+                push -1
+                mul
+                add
+                dup 1
+                mul
+                dup 10
+                add             // _ *auth_structure[i] *tail *head' node_index [node; 5] sibling_is_in_queue *sibling
+                place 7
+                place 6         // _ *auth_structure[i] *tail *head' *sibling sibling_is_in_queue node_index [node; 5]
+
+                merkle_step_mem // _ *auth_structure[i] *tail *head' (*sibling + 5) sibling_is_in_queue parent_index [parent; 5]
+                pick 7
+                pop 1
+                pick 6          // _ *auth_structure[i] *tail *head' parent_index [parent; 5] sibling_is_in_queue
+
+                dup 0
+                push {node_size}
+                mul
+                pick 8
+                add
+                place 7         // _ *auth_structure[i] *tail *head'' parent_index [parent; 5] sibling_is_in_queue
+
+                push {-(Digest::LEN as isize)}
+                mul
+                addi {Digest::LEN}
+                pick 9
+                add
+                place 8         // _ *auth_structure[i'] *tail *head'' parent_index [parent; 5]
+
+                dup 7
+                write_mem {Digest::LEN}
+                write_mem 1     // _ *auth_structure[i'] *tail *head'' *tail'
+                pick 2
+                pop 1
+                place 1         // _ *auth_structure[i'] *tail' *head''
+                recurse
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use twenty_first::prelude::MerkleTree;
+    use twenty_first::util_types::merkle_tree::MerkleTreeInclusionProof;
+
+    use super::*;
+    use crate::empty_stack;
+    use crate::rust_shadowing_helper_functions::dyn_malloc::dynamic_allocator;
+    use crate::test_prelude::*;
+
+    impl MerkleVerifyAuthenticationStructure {
+        /// Encode the indexed leafs and the authentication structure to memory,
+        /// and set up a stack containing all arguments.
+        pub(crate) fn initial_state(
+            root: Digest,
+            tree_height: u32,
+            indexed_leafs: &[(u32, Digest)],
+            authentication_structure: &[Digest],
+        ) -> FunctionInitialState {
+            let indexed_leafs = indexed_leafs.to_vec();
+            let authentication_structure = authentication_structure.to_vec();
+
+            let indexed_leafs_pointer = bfe!(0);
+            let authentication_structure_pointer =
+                indexed_leafs_pointer + bfe!(indexed_leafs.encode().len() as u64);
+
+            let mut memory = HashMap::default();
+            encode_to_memory(&mut memory, indexed_leafs_pointer, &indexed_leafs);
+            encode_to_memory(
+                &mut memory,
+                authentication_structure_pointer,
+                &authentication_structure,
+            );
+
+            let mut stack = empty_stack();
+            push_encodable(&mut stack, &root);
+            push_encodable(&mut stack, &tree_height);
+            stack.push(indexed_leafs_pointer);
+            stack.push(authentication_structure_pointer);
+
+            FunctionInitialState { stack, memory }
+        }
+
+        /// An honest proof for the given leaf indices in a random tree of the
+        /// given height.
+        fn random_proof(
+            rng: &mut StdRng,
+            tree_height: u32,
+            leaf_indices: &[u32],
+        ) -> (Digest, MerkleTreeInclusionProof) {
+            let leafs = (0..1 << tree_height).map(|_| rng.random()).collect_vec();
+            let tree = MerkleTree::par_new(&leafs).unwrap();
+
+            let leaf_indices = leaf_indices.iter().map(|&i| i as usize).collect_vec();
+            let proof = tree
+                .inclusion_proof_for_leaf_indices(&leaf_indices)
+                .unwrap();
+            (tree.root(), proof)
+        }
+
+        fn initial_state_from_proof(
+            root: Digest,
+            proof: &MerkleTreeInclusionProof,
+        ) -> FunctionInitialState {
+            let indexed_leafs = proof
+                .indexed_leafs
+                .iter()
+                .map(|&(i, leaf)| (u32::try_from(i).unwrap(), leaf))
+                .collect_vec();
+
+            Self::initial_state(
+                root,
+                proof.tree_height.try_into().unwrap(),
+                &indexed_leafs,
+                &proof.authentication_structure,
+            )
+        }
+    }
+
+    impl Function for MerkleVerifyAuthenticationStructure {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let authentication_structure_pointer = stack.pop().unwrap();
+            let indexed_leafs_pointer = stack.pop().unwrap();
+            let tree_height = pop_encodable::<u32>(stack);
+            let root = pop_encodable::<Digest>(stack);
+
+            assert!(tree_height < 32);
+            let num_leafs = 1 << tree_height;
+
+            let indexed_leafs =
+                *Vec::<(u32, Digest)>::decode_from_memory(memory, indexed_leafs_pointer).unwrap();
+            let authentication_structure =
+                *Vec::<Digest>::decode_from_memory(memory, authentication_structure_pointer)
+                    .unwrap();
+            let mut authentication_structure = authentication_structure.into_iter();
+
+            let mut nodes = BTreeMap::new();
+            for (leaf_index, leaf) in indexed_leafs {
+                assert!(leaf_index < num_leafs);
+                let node = *nodes.entry(leaf_index + num_leafs).or_insert(leaf);
+                assert_eq!(leaf, node);
+            }
+
+            // mirror the queue's memory layout
+            let queue_pointer = dynamic_allocator(memory);
+            let node_address = |i: usize| queue_pointer + bfe!((i * Self::NODE_SIZE) as u64);
+            let mut queue = nodes.into_iter().rev().collect_vec();
+            for (i, node) in queue.iter().enumerate() {
+                encode_to_memory(memory, node_address(i), node);
+            }
+
+            let mut head = 0;
+            while let Some(&(node_index, node)) = queue.get(head) {
+                head += 1;
+                if node_index == 1 {
+                    assert_eq!(root, node);
+                    break;
+                }
+
+                let sibling = match queue.get(head) {
+                    Some(&(sibling_index, sibling)) if sibling_index == node_index ^ 1 => {
+                        head += 1;
+                        sibling
+                    }
+                    _ => authentication_structure.next().unwrap(),
+                };
+                let parent = if node_index.is_multiple_of(2) {
+                    Tip5::hash_pair(node, sibling)
+                } else {
+                    Tip5::hash_pair(sibling, node)
+                };
+
+                let parent = (node_index / 2, parent);
+                encode_to_memory(memory, node_address(queue.len()), &parent);
+                queue.push(parent);
+            }
+
+            assert!(authentication_structure.next().is_none());
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let (tree_height, num_indices) = match bench_case {
+                Some(BenchmarkCase::CommonCase) => (10, 20),
+                Some(BenchmarkCase::WorstCase) => (16, 80),
+                None => (rng.random_range(0..12), rng.random_range(0..40)),
+            };
+
+            let leaf_indices = (0..num_indices)
+                .map(|_| rng.random_range(0..1 << tree_height))
+                .collect_vec();
+            let (root, proof) = Self::random_proof(&mut rng, tree_height, &leaf_indices);
+
+            Self::initial_state_from_proof(root, &proof)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let mut rng = StdRng::from_seed([0; 32]);
+            let mut proof_for = |tree_height, leaf_indices: &[u32]| {
+                let (root, proof) = Self::random_proof(&mut rng, tree_height, leaf_indices);
+                Self::initial_state_from_proof(root, &proof)
+            };
+
+            vec![
+                proof_for(0, &[]),
+                proof_for(0, &[0]),
+                proof_for(0, &[0, 0, 0]),
+                proof_for(3, &[]),
+                proof_for(3, &[2, 0]),
+                proof_for(3, &[0, 1]),
+                proof_for(3, &[7, 7, 6, 0, 7]),
+                proof_for(3, &[0, 1, 2, 3, 4, 5, 6, 7]),
+                proof_for(3, &[7, 6, 5, 4, 3, 2, 1, 0]),
+                proof_for(10, &[0, 1023]),
+            ]
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedFunction::new(MerkleVerifyAuthenticationStructure).test();
+    }
+
+    #[proptest(cases = 50)]
+    fn empty_proof_is_valid_for_any_root(
+        #[strategy(arb())] root: Digest,
+        #[strategy(0_u32..32)] tree_height: u32,
+    ) {
+        let FunctionInitialState { stack, memory } =
+            MerkleVerifyAuthenticationStructure::initial_state(root, tree_height, &[], &[]);
+        test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(MerkleVerifyAuthenticationStructure),
+            &stack,
+            &[],
+            &NonDeterminism::default().with_ram(memory),
+            &None,
+            None,
+        );
+    }
+
+    /// The strategy for generating a random, valid proof and its root.
+    fn proof_strategy() -> impl Strategy<Value = (Digest, MerkleTreeInclusionProof)> {
+        (1_u32..8, any::<[u8; 32]>())
+            .prop_flat_map(|(tree_height, seed)| {
+                let leaf_indices = prop::collection::vec(0..1_u32 << tree_height, 1..20);
+                (Just(tree_height), Just(seed), leaf_indices)
+            })
+            .prop_map(|(tree_height, seed, leaf_indices)| {
+                let mut rng = StdRng::from_seed(seed);
+                MerkleVerifyAuthenticationStructure::random_proof(
+                    &mut rng,
+                    tree_height,
+                    &leaf_indices,
+                )
+            })
+    }
+
+    fn assert_verification_failure(
+        root: Digest,
+        proof: MerkleTreeInclusionProof,
+        expected_error_ids: &[i128],
+    ) {
+        let initial_state =
+            MerkleVerifyAuthenticationStructure::initial_state_from_proof(root, &proof);
+        assert!(!proof.verify(root));
+
+        test_assertion_failure(
+            &ShadowedFunction::new(MerkleVerifyAuthenticationStructure),
+            initial_state.into(),
+            expected_error_ids,
+        );
+    }
+
+    #[proptest(cases = 50)]
+    fn verification_fails_if_root_is_disturbed(
+        #[strategy(proof_strategy())] root_and_proof: (Digest, MerkleTreeInclusionProof),
+        #[strategy(0_usize..Digest::LEN)] perturbation_index: usize,
+        #[filter(#perturbation != 0)] perturbation: i8,
+    ) {
+        let (mut root, proof) = root_and_proof;
+        root.0[perturbation_index] += bfe!(perturbation);
+
+        assert_verification_failure(
+            root,
+            proof,
+            &[MerkleVerifyAuthenticationStructure::ROOT_MISMATCH_ERROR_ID],
+        );
+    }
+
+    #[proptest(cases = 50)]
+    fn verification_fails_if_leaf_is_disturbed(
+        #[strategy(proof_strategy())] root_and_proof: (Digest, MerkleTreeInclusionProof),
+        #[strategy(any::<prop::sample::Index>())] leaf_index: prop::sample::Index,
+        #[strategy(0_usize..Digest::LEN)] perturbation_index: usize,
+        #[filter(#perturbation != 0)] perturbation: i8,
+    ) {
+        let (root, mut proof) = root_and_proof;
+        let leaf_index = leaf_index.index(proof.indexed_leafs.len());
+        proof.indexed_leafs[leaf_index].1 .0[perturbation_index] += bfe!(perturbation);
+
+        // disturbing one of several identical leafs is detected early
+        let expected_error_ids = [
+            MerkleVerifyAuthenticationStructure::REPEATED_LEAF_MISMATCH_ERROR_ID,
+            MerkleVerifyAuthenticationStructure::ROOT_MISMATCH_ERROR_ID,
+        ];
+        assert_verification_failure(root, proof, &expected_error_ids);
+    }
+
+    #[proptest(cases = 50)]
+    fn verification_fails_if_authentication_structure_is_disturbed(
+        #[strategy(proof_strategy())]
+        #[filter(!#root_and_proof.1.authentication_structure.is_empty())]
+        root_and_proof: (Digest, MerkleTreeInclusionProof),
+        #[strategy(any::<prop::sample::Index>())] node_index: prop::sample::Index,
+        #[strategy(0_usize..Digest::LEN)] perturbation_index: usize,
+        #[filter(#perturbation != 0)] perturbation: i8,
+    ) {
+        let (root, mut proof) = root_and_proof;
+        let node_index = node_index.index(proof.authentication_structure.len());
+        proof.authentication_structure[node_index].0[perturbation_index] += bfe!(perturbation);
+
+        assert_verification_failure(
+            root,
+            proof,
+            &[MerkleVerifyAuthenticationStructure::ROOT_MISMATCH_ERROR_ID],
+        );
+    }
+
+    #[proptest(cases = 50)]
+    fn verification_fails_if_authentication_structure_is_too_long(
+        #[strategy(proof_strategy())] root_and_proof: (Digest, MerkleTreeInclusionProof),
+        #[strategy(arb())] additional_node: Digest,
+        #[strategy(any::<prop::sample::Index>())] insertion_index: prop::sample::Index,
+    ) {
+        let (root, mut proof) = root_and_proof;
+        let insertion_index = insertion_index.index(proof.authentication_structure.len() + 1);
+        proof
+            .authentication_structure
+            .insert(insertion_index, additional_node);
+
+        let expected_error_ids = [
+            MerkleVerifyAuthenticationStructure::ROOT_MISMATCH_ERROR_ID,
+            MerkleVerifyAuthenticationStructure::AUTHENTICATION_STRUCTURE_LENGTH_MISMATCH_ERROR_ID,
+        ];
+        assert_verification_failure(root, proof, &expected_error_ids);
+    }
+
+    #[proptest(cases = 50)]
+    fn verification_fails_if_authentication_structure_is_too_short(
+        #[strategy(proof_strategy())]
+        #[filter(!#root_and_proof.1.authentication_structure.is_empty())]
+        root_and_proof: (Digest, MerkleTreeInclusionProof),
+        #[strategy(any::<prop::sample::Index>())] removal_index: prop::sample::Index,
+    ) {
+        let (root, mut proof) = root_and_proof;
+        let removal_index = removal_index.index(proof.authentication_structure.len());
+        proof.authentication_structure.remove(removal_index);
+
+        let expected_error_ids = [
+            MerkleVerifyAuthenticationStructure::ROOT_MISMATCH_ERROR_ID,
+            MerkleVerifyAuthenticationStructure::AUTHENTICATION_STRUCTURE_LENGTH_MISMATCH_ERROR_ID,
+        ];
+        assert_verification_failure(root, proof, &expected_error_ids);
+    }
+
+    #[proptest(cases = 50)]
+    fn verification_fails_if_leaf_index_is_out_of_bounds(
+        #[strategy(proof_strategy())] root_and_proof: (Digest, MerkleTreeInclusionProof),
+        #[strategy(any::<prop::sample::Index>())] leaf_index: prop::sample::Index,
+        #[strategy(0_usize..1 << 10)] excess: usize,
+    ) {
+        let (root, mut proof) = root_and_proof;
+        let leaf_index = leaf_index.index(proof.indexed_leafs.len());
+        proof.indexed_leafs[leaf_index].0 = (1 << proof.tree_height) + excess;
+
+        assert_verification_failure(
+            root,
+            proof,
+            &[MerkleVerifyAuthenticationStructure::OUT_OF_BOUNDS_LEAF_ERROR_ID],
+        );
+    }
+
+    #[proptest(cases = 50)]
+    fn verification_fails_if_repeated_leafs_differ(
+        #[strategy(proof_strategy())] root_and_proof: (Digest, MerkleTreeInclusionProof),
+        #[strategy(any::<prop::sample::Index>())] leaf_index: prop::sample::Index,
+        #[strategy(arb())] bogus_leaf: Digest,
+    ) {
+        let (root, mut proof) = root_and_proof;
+        let (leaf_index, leaf) = proof.indexed_leafs[leaf_index.index(proof.indexed_leafs.len())];
+        prop_assume!(leaf != bogus_leaf);
+        proof.indexed_leafs.push((leaf_index, bogus_leaf));
+
+        assert_verification_failure(
+            root,
+            proof,
+            &[MerkleVerifyAuthenticationStructure::REPEATED_LEAF_MISMATCH_ERROR_ID],
+        );
+    }
+
+    #[proptest(cases = 20)]
+    fn verification_fails_if_tree_height_is_too_large(
+        #[strategy(32_u32..)] tree_height: u32,
+        #[strategy(arb())] root: Digest,
+    ) {
+        let initial_state =
+            MerkleVerifyAuthenticationStructure::initial_state(root, tree_height, &[], &[]);
+
+        test_assertion_failure(
+            &ShadowedFunction::new(MerkleVerifyAuthenticationStructure),
+            initial_state.into(),
+            &[MerkleVerifyAuthenticationStructure::TREE_TOO_HIGH_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(MerkleVerifyAuthenticationStructure).bench();
+    }
+}
@@ -34,6 +34,7 @@ pub mod list;
 pub mod memory;
 pub mod mmr;
 pub mod neptune;
+pub mod peephole;
 pub mod prelude;
 pub mod rust_shadowing_helper_functions;
 pub mod snippet_bencher;
@@ -48,6 +49,7 @@ use triton_vm::isa::instruction::AnInstruction;
 use triton_vm::prelude::TableId;
 pub use triton_vm::twenty_first;
 
+use crate::linker::SourceMap;
 use crate::test_helpers::prepend_program_with_stack_setup;
 
 pub(crate) const U32_TO_USIZE_ERR: &str =
@@ -119,8 +121,12 @@ pub(crate) fn pop_encodable<T: BFieldCodec>(stack: &mut Vec<BFieldElement>) -> T
 
 /// Execute a Triton-VM program and test correct behavior indicators.
 /// Modify stack and memory. Panic if anything goes wrong.
+///
+/// On failure, the instructions of the program are attributed to snippets
+/// according to the given [`SourceMap`].
 pub(crate) fn execute_test(
     code: &[LabelledInstruction],
+    source_map: &SourceMap,
     stack: &mut Vec<BFieldElement>,
     expected_stack_diff: isize,
     std_in: Vec<BFieldElement>,
@@ -139,10 +145,11 @@ pub(crate) fn execute_test(
     vm_state.op_stack.stack.clone_from(&init_stack);
     vm_state.sponge = maybe_sponge;
 
-    maybe_write_debuggable_vm_state_to_disk(&vm_state);
+    maybe_write_debuggable_vm_state_and_source_map_to_disk(&vm_state, Some(source_map));
 
     if let Err(err) = vm_state.run() {
-        panic!("{err}\n\nFinal state was: {vm_state}")
+        let call_stack = source_map.format_call_stack(&vm_state);
+        panic!("{err}\n\nCall stack:\n{call_stack}\n\nFinal state was: {vm_state}")
     }
     let terminal_state = vm_state;
 
@@ -193,18 +200,39 @@ pub(crate) fn execute_test(
 ///
 /// [Triton TUI]: https://crates.io/crates/triton-tui
 pub fn maybe_write_debuggable_vm_state_to_disk(vm_state: &VMState) {
+    maybe_write_debuggable_vm_state_and_source_map_to_disk(vm_state, None);
+}
+
+/// Like [`maybe_write_debuggable_vm_state_to_disk`], but if a [`SourceMap`] is
+/// given, the state is annotated with it under the key `source_map`, which the
+/// Triton TUI ignores.
+pub fn maybe_write_debuggable_vm_state_and_source_map_to_disk(
+    vm_state: &VMState,
+    source_map: Option<&SourceMap>,
+) {
     let Ok(_) = std::env::var("TASMLIB_TRITON_TUI") else {
         return;
     };
 
     let mut state_file = std::fs::File::create("vm_state.json").unwrap();
-    let state = serde_json::to_string(&vm_state).unwrap();
+    let Some(source_map) = source_map else {
+        let state = serde_json::to_string(&vm_state).unwrap();
+        write!(state_file, "{state}").unwrap();
+        return;
+    };
+
+    let mut state = serde_json::to_value(vm_state).unwrap();
+    state["source_map"] = serde_json::to_value(source_map).unwrap();
     write!(state_file, "{state}").unwrap();
 }
 
 /// Prepare state and run Triton VM
+///
+/// On failure, the instructions of the program are attributed to snippets
+/// according to the given [`SourceMap`].
 pub(crate) fn execute_with_terminal_state(
     program: Program,
+    source_map: &SourceMap,
     std_in: &[BFieldElement],
     stack: &[BFieldElement],
     nondeterminism: &NonDeterminism,
@@ -215,7 +243,7 @@ pub(crate) fn execute_with_terminal_state(
     stack.clone_into(&mut vm_state.op_stack.stack);
     vm_state.sponge = maybe_sponge;
 
-    maybe_write_debuggable_vm_state_to_disk(&vm_state);
+    maybe_write_debuggable_vm_state_and_source_map_to_disk(&vm_state, Some(source_map));
     match vm_state.run() {
         Ok(()) => {
             println!("Triton VM execution successful.");
@@ -226,7 +254,9 @@ pub(crate) fn execute_with_terminal_state(
                 println!("tasm final sponge:");
                 println!("{}", sponge.state.iter().join(", "));
             }
-            println!("Triton VM execution failed. Final state:\n{vm_state}");
+            let call_stack = source_map.format_call_stack(&vm_state);
+            println!("Triton VM execution failed. Call stack:\n{call_stack}");
+            println!("Final state:\n{vm_state}");
             Err(err)
         }
     }
@@ -1,11 +1,22 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::hash::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
 
 use arbitrary::Arbitrary;
 use itertools::Itertools;
 use num_traits::ConstOne;
+use serde::Deserialize;
+use serde::Serialize;
+use triton_vm::isa::instruction::AnInstruction;
 use triton_vm::memory_layout::MemoryRegion;
 use triton_vm::prelude::*;
 
+use crate::linker::LinkedCode;
 use crate::prelude::*;
 
 /// By [convention](crate::memory), the last full memory page is reserved for the static allocator.
@@ -28,8 +39,81 @@ pub struct Library {
     /// Imported dependencies.
     seen_snippets: HashMap<String, Vec<LabelledInstruction>>,
 
+    /// Fingerprints of the imported dependencies' code, used to detect different code being
+    /// imported under the same entrypoint.
+    fingerprints: HashMap<String, u64>,
+
     /// The number of statically allocated words
     num_allocated_words: u32,
+
+    /// Whether imports only resolve entrypoints, without generating and recording the
+    /// dependencies' code. Used to compute the fingerprint of a single snippet's code.
+    shallow: bool,
+}
+
+/// The error returned when importing code under an entrypoint that is already
+/// taken by different code.
+///
+/// Usually, this indicates that some differently parameterized snippets share
+/// an entrypoint, for example because the entrypoint of a generic snippet does
+/// not reflect all its type parameters.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct EntrypointCollision {
+    pub entrypoint: String,
+}
+
+impl Display for EntrypointCollision {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let entrypoint = &self.entrypoint;
+        write!(
+            f,
+            "entrypoint “{entrypoint}” is already taken by different code"
+        )
+    }
+}
+
+impl std::error::Error for EntrypointCollision {}
+
+/// The functions of a program that are [reachable](Library::call_graph) from
+/// the program's code, and which functions they call.
+///
+/// Functions are identified by their entrypoint. The program's code itself is
+/// identified by [`CallGraph::PROGRAM`].
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CallGraph {
+    /// For every reachable function, the functions it calls.
+    pub calls: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl CallGraph {
+    /// The node representing the program's code, which is not part of any
+    /// imported snippet.
+    pub const PROGRAM: &'static str = "program";
+
+    /// All reachable functions, including [the program](Self::PROGRAM) itself.
+    pub fn functions(&self) -> impl Iterator<Item = &str> {
+        self.calls.keys().map(|function| function.as_str())
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// The call graph in the [DOT language](https://graphviz.org/doc/info/lang.html)
+    /// of Graphviz.
+    pub fn to_dot(&self) -> String {
+        let nodes = self
+            .functions()
+            .map(|function| format!("  \"{function}\";"));
+        let edges = self.calls.iter().flat_map(|(caller, callees)| {
+            callees
+                .iter()
+                .map(move |callee| format!("  \"{caller}\" -> \"{callee}\";"))
+        });
+
+        let body = nodes.chain(edges).join("\n");
+        format!("digraph call_graph {{\n{body}\n}}\n")
+    }
 }
 
 /// Represents a [static memory allocation][kmalloc] within Triton VM.
@@ -71,10 +155,21 @@ impl Library {
         MemoryRegion::new(STATIC_MEMORY_LAST_ADDRESS, 1usize << 32)
     }
 
+    /// The memory region of all [static allocations](Self::kmalloc) made through
+    /// this library so far.
+    pub fn kmalloc_allocated_memory_region(&self) -> MemoryRegion {
+        let first_address = STATIC_MEMORY_FIRST_ADDRESS + bfe!(1) - bfe!(self.num_allocated_words);
+        let num_words = self.num_allocated_words.try_into().unwrap();
+
+        MemoryRegion::new(first_address, num_words)
+    }
+
     pub fn new() -> Self {
         Self {
             seen_snippets: HashMap::default(),
+            fingerprints: HashMap::default(),
             num_allocated_words: 0,
+            shallow: false,
         }
     }
 
@@ -98,17 +193,54 @@ impl Library {
     ///
     /// Avoid cyclic dependencies by only calling `T::function_code()` which
     /// may call `.import()` if `.import::<T>()` wasn't already called once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a snippet with the same entrypoint but different code has been
+    /// imported before. This is only checked in debug builds; see
+    /// [`try_import`](Self::try_import) for details and for a non-panicking
+    /// alternative.
     // todo: Above comment is not overly clear. Improve it.
     pub fn import(&mut self, snippet: Box<dyn BasicSnippet>) -> String {
+        self.try_import(snippet)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Like [`import`](Self::import), but returns an error instead of panicking
+    /// if a snippet with the same entrypoint but different code has been
+    /// imported before.
+    ///
+    /// Detecting such a collision requires generating the snippet's own code on
+    /// _every_ import, not only on the first one. Because this is costly for
+    /// programs that import the same snippets many times, the check is only
+    /// performed in debug builds. In release builds, this function never returns
+    /// an error, and re-importing a snippet is free.
+    pub fn try_import(
+        &mut self,
+        snippet: Box<dyn BasicSnippet>,
+    ) -> Result<String, EntrypointCollision> {
         let dep_entrypoint = snippet.entrypoint();
+        if self.shallow {
+            return Ok(dep_entrypoint);
+        }
 
         let is_new_dependency = !self.seen_snippets.contains_key(&dep_entrypoint);
+        if cfg!(debug_assertions) {
+            let fingerprint = Self::snippet_fingerprint(&*snippet);
+            if is_new_dependency {
+                self.fingerprints
+                    .insert(dep_entrypoint.clone(), fingerprint);
+            } else {
+                self.check_for_collision(&dep_entrypoint, fingerprint)?;
+            }
+        }
+
         if is_new_dependency {
             let dep_body = snippet.annotated_code(self);
             self.seen_snippets.insert(dep_entrypoint.clone(), dep_body);
         }
 
-        dep_entrypoint
+        Ok(dep_entrypoint)
     }
 
     /// Import code that does not implement the `Snippet` trait
@@ -118,12 +250,68 @@ impl Library {
     /// isolation. This method is intended to add function to the assembly
     /// that you have defined inline and where a function call is needed due to
     /// e.g. a dynamic counter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if different code has been imported under the same name before.
+    /// See [`try_explicit_import`](Self::try_explicit_import) for a
+    /// non-panicking alternative.
     pub fn explicit_import(&mut self, name: &str, body: &[LabelledInstruction]) -> String {
-        if !self.seen_snippets.contains_key(name) {
+        self.try_explicit_import(name, body)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Like [`explicit_import`](Self::explicit_import), but returns an error
+    /// instead of panicking if different code has been imported under the same
+    /// name before.
+    pub fn try_explicit_import(
+        &mut self,
+        name: &str,
+        body: &[LabelledInstruction],
+    ) -> Result<String, EntrypointCollision> {
+        let fingerprint = Self::fingerprint(body);
+        if self.seen_snippets.contains_key(name) {
+            self.check_for_collision(name, fingerprint)?;
+        } else {
             self.seen_snippets.insert(name.to_owned(), body.to_vec());
+            self.fingerprints.insert(name.to_owned(), fingerprint);
         }
 
-        name.to_string()
+        Ok(name.to_string())
+    }
+
+    fn check_for_collision(
+        &self,
+        entrypoint: &str,
+        fingerprint: u64,
+    ) -> Result<(), EntrypointCollision> {
+        match self.fingerprints.get(entrypoint) {
+            Some(&known_fingerprint) if known_fingerprint != fingerprint => {
+                Err(EntrypointCollision {
+                    entrypoint: entrypoint.to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn fingerprint(code: &[LabelledInstruction]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        code.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// The fingerprint of the snippet's own code, independent of any previous
+    /// [static allocations](Self::kmalloc).
+    ///
+    /// Only the snippet's code is generated; its dependencies are not linked.
+    fn snippet_fingerprint(snippet: &dyn BasicSnippet) -> u64 {
+        let mut shallow_library = Self {
+            shallow: true,
+            ..Self::new()
+        };
+
+        Self::fingerprint(&snippet.annotated_code(&mut shallow_library))
     }
 
     /// Return a list of all external dependencies sorted by name. All snippets are sorted
@@ -149,6 +337,91 @@ impl Library {
         self.all_external_dependencies().concat()
     }
 
+    /// Like [`all_imports`](Self::all_imports), but additionally records the
+    /// [range](crate::linker::SnippetRange) of every imported snippet.
+    pub fn linked_imports(&self) -> LinkedCode {
+        self.link_snippets(|_| true)
+    }
+
+    /// Like [`all_imports`](Self::all_imports), but only contains the snippets
+    /// that are [reachable](Self::call_graph) from the given program code. In
+    /// particular, snippets that were imported but are never called are dropped.
+    pub fn reachable_imports(&self, program: &[LabelledInstruction]) -> Vec<LabelledInstruction> {
+        self.linked_reachable_imports(program).code
+    }
+
+    /// Like [`reachable_imports`](Self::reachable_imports), but additionally
+    /// records the [range](crate::linker::SnippetRange) of every linked snippet.
+    pub fn linked_reachable_imports(&self, program: &[LabelledInstruction]) -> LinkedCode {
+        let call_graph = self.call_graph(program);
+        self.link_snippets(|name| call_graph.calls.contains_key(name))
+    }
+
+    /// Link all imported snippets satisfying the given predicate, sorted by
+    /// name.
+    fn link_snippets(&self, predicate: impl Fn(&str) -> bool) -> LinkedCode {
+        let mut linked_code = LinkedCode::default();
+        for (name, code) in self.seen_snippets.iter().sorted_by_key(|(name, _)| *name) {
+            if predicate(name) {
+                linked_code.push_snippet(name, code);
+            }
+        }
+
+        linked_code
+    }
+
+    /// The call graph of the given program code when linked against this
+    /// library.
+    ///
+    /// Starting from the program code, follows all `call` targets, directly and
+    /// transitively. Only imported snippets are tracked; calls to labels that are
+    /// defined in the program code itself are attributed to the program.
+    ///
+    /// # Panics
+    ///
+    /// Panics if two imported snippets define the same label. Linking such
+    /// snippets would not produce a valid program either.
+    pub fn call_graph(&self, program: &[LabelledInstruction]) -> CallGraph {
+        let mut owning_snippet = HashMap::new();
+        for (name, code) in &self.seen_snippets {
+            for instruction in code {
+                let LabelledInstruction::Label(label) = instruction else {
+                    continue;
+                };
+                if let Some(other_name) = owning_snippet.insert(label.as_str(), name.as_str()) {
+                    panic!("label “{label}” is defined by both “{other_name}” and “{name}”");
+                }
+            }
+        }
+        let callees = |caller: &str, code: &[LabelledInstruction]| {
+            code.iter()
+                .filter_map(|instruction| match instruction {
+                    LabelledInstruction::Instruction(AnInstruction::Call(label)) => {
+                        owning_snippet.get(label.as_str()).copied()
+                    }
+                    _ => None,
+                })
+                .filter(|&callee| callee != caller)
+                .map(|callee| callee.to_string())
+                .collect::<BTreeSet<_>>()
+        };
+
+        let mut calls = BTreeMap::new();
+        let mut unvisited = vec![(CallGraph::PROGRAM.to_string(), program)];
+        while let Some((caller, code)) = unvisited.pop() {
+            if calls.contains_key(&caller) {
+                continue;
+            }
+            let caller_callees = callees(&caller, code);
+            for callee in &caller_callees {
+                unvisited.push((callee.clone(), &self.seen_snippets[callee]));
+            }
+            calls.insert(caller, caller_callees);
+        }
+
+        CallGraph { calls }
+    }
+
     /// Statically allocate `num_words` words of memory.
     ///
     /// # Panics
@@ -178,6 +451,7 @@ mod tests {
     use triton_vm::prelude::Program;
 
     use super::*;
+    use crate::arithmetic::u64::div_mod::DivMod;
     use crate::mmr::calculate_new_peaks_from_leaf_mutation::MmrCalculateNewPeaksFromLeafMutationMtIndices;
     use crate::test_prelude::*;
 
@@ -394,4 +668,264 @@ mod tests {
         let third_chunk = lib.kmalloc(1000);
         assert_eq!(-bfe!(1009), third_chunk.write_address());
     }
+
+    /// Its entrypoint does not reflect its parameter, which makes differently
+    /// parameterized instances collide.
+    #[derive(Debug)]
+    struct PushConstant(u64);
+
+    impl BasicSnippet for PushConstant {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            vec![]
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            vec![(DataType::Bfe, "constant".to_string())]
+        }
+
+        fn entrypoint(&self) -> String {
+            "tasmlib_push_constant_dummy_test_value".to_string()
+        }
+
+        fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+            triton_asm!({self.entrypoint()}: push {self.0} return)
+        }
+    }
+
+    #[test]
+    fn reimporting_identical_snippet_is_fine() {
+        let mut lib = Library::new();
+        let label = lib.try_import(Box::new(PushConstant(42))).unwrap();
+        let same_label = lib.try_import(Box::new(PushConstant(42))).unwrap();
+        assert_eq!(label, same_label);
+        assert_eq!(vec![label], lib.get_all_snippet_names());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn importing_different_snippet_with_same_entrypoint_is_an_error() {
+        let mut lib = Library::new();
+        lib.try_import(Box::new(PushConstant(42))).unwrap();
+
+        let err = lib.try_import(Box::new(PushConstant(43))).unwrap_err();
+        assert_eq!(PushConstant(43).entrypoint(), err.entrypoint);
+
+        // the originally imported code is retained
+        let expected_code = PushConstant(42).annotated_code(&mut Library::new());
+        assert_eq!(expected_code, lib.all_imports());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "already taken by different code")]
+    fn importing_different_snippet_with_same_entrypoint_panics() {
+        let mut lib = Library::new();
+        lib.import(Box::new(PushConstant(42)));
+        lib.import(Box::new(PushConstant(43)));
+    }
+
+    #[test]
+    fn explicitly_importing_different_code_under_same_name_is_an_error() {
+        let mut lib = Library::new();
+        let body = triton_asm!(some_label: push 42 return);
+        lib.try_explicit_import("some_label", &body).unwrap();
+        lib.try_explicit_import("some_label", &body).unwrap();
+
+        let other_body = triton_asm!(some_label: push 43 return);
+        let err = lib
+            .try_explicit_import("some_label", &other_body)
+            .unwrap_err();
+        assert_eq!("some_label", err.entrypoint);
+    }
+
+    /// Imports a snippet, but never calls it.
+    #[derive(Debug)]
+    struct DummyTestSnippetD;
+
+    impl BasicSnippet for DummyTestSnippetD {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            vec![]
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            vec![(DataType::Bfe, "1".to_string())]
+        }
+
+        fn entrypoint(&self) -> String {
+            "tasmlib_d_dummy_test_value".to_string()
+        }
+
+        fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+            let _ = library.import(Box::new(DummyTestSnippetB));
+            triton_asm!({self.entrypoint()}: push 1 return)
+        }
+    }
+
+    fn program_calling(entrypoint: &str) -> Vec<LabelledInstruction> {
+        triton_asm!(call { entrypoint } halt)
+    }
+
+    #[test]
+    fn call_graph_contains_transitive_calls() {
+        let mut lib = Library::new();
+        let a = lib.import(Box::new(DummyTestSnippetA));
+        let b = DummyTestSnippetB.entrypoint();
+        let c = DummyTestSnippetC.entrypoint();
+        let call_graph = lib.call_graph(&program_calling(&a));
+
+        let expected_calls = [
+            (CallGraph::PROGRAM, vec![a.as_str()]),
+            (&a, vec![&b, &c]),
+            (&b, vec![&c]),
+            (&c, vec![]),
+        ];
+        let expected_calls = expected_calls
+            .into_iter()
+            .map(|(caller, callees)| {
+                let callees = callees.into_iter().map(|c| c.to_string()).collect();
+                (caller.to_string(), callees)
+            })
+            .collect::<BTreeMap<_, BTreeSet<_>>>();
+        assert_eq!(expected_calls, call_graph.calls);
+    }
+
+    #[test]
+    fn unreachable_imports_are_dropped() {
+        let mut lib = Library::new();
+        let d = lib.import(Box::new(DummyTestSnippetD));
+        assert_eq!(3, lib.get_all_snippet_names().len());
+
+        let program = program_calling(&d);
+        let call_graph = lib.call_graph(&program);
+        assert_eq!(
+            vec![CallGraph::PROGRAM, &d],
+            call_graph.functions().collect_vec()
+        );
+
+        let expected_imports = DummyTestSnippetD.annotated_code(&mut Library::new());
+        assert_eq!(expected_imports, lib.reachable_imports(&program));
+
+        // sanity check: the dropped code is not needed
+        let imports = lib.reachable_imports(&program);
+        let program = Program::new(&[program, imports].concat());
+        let output = VM::run(program, PublicInput::default(), NonDeterminism::default());
+        assert!(output.is_ok());
+    }
+
+    #[test]
+    fn reachable_imports_equal_all_imports_if_everything_is_called() {
+        let mut lib = Library::new();
+        let a = lib.import(Box::new(DummyTestSnippetA));
+        assert_eq!(
+            lib.all_imports(),
+            lib.reachable_imports(&program_calling(&a))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "is defined by both")]
+    fn call_graph_requires_labels_to_be_unique_across_snippets() {
+        let mut lib = Library::new();
+        lib.explicit_import("foo", &triton_asm!(foo: call shared return shared: return));
+        lib.explicit_import("bar", &triton_asm!(bar: call shared return shared: return));
+        lib.call_graph(&program_calling("foo"));
+    }
+
+    #[test]
+    fn call_graph_can_be_exported() {
+        let mut lib = Library::new();
+        let b = lib.import(Box::new(DummyTestSnippetB));
+        let call_graph = lib.call_graph(&program_calling(&b));
+
+        let json = call_graph.to_json();
+        let deserialized: CallGraph = serde_json::from_str(&json).unwrap();
+        assert_eq!(call_graph, deserialized);
+
+        let dot = call_graph.to_dot();
+        assert!(dot.starts_with("digraph call_graph {"));
+        assert!(dot.contains(r#""program" -> "tasmlib_b_dummy_test_value";"#));
+        assert!(dot.contains(r#""tasmlib_b_dummy_test_value" -> "tasmlib_c_dummy_test_value";"#));
+    }
+
+    thread_local! {
+        static NUM_COUNTING_LEAF_CODE_GENERATIONS: std::cell::Cell<usize> = const {
+            std::cell::Cell::new(0)
+        };
+    }
+
+    /// Counts how often its code is generated.
+    #[derive(Debug)]
+    struct CountingLeaf;
+
+    impl BasicSnippet for CountingLeaf {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            vec![]
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            vec![]
+        }
+
+        fn entrypoint(&self) -> String {
+            "tasmlib_counting_leaf_dummy_test_value".to_string()
+        }
+
+        fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+            NUM_COUNTING_LEAF_CODE_GENERATIONS.with(|count| count.set(count.get() + 1));
+            triton_asm!({self.entrypoint()}: return)
+        }
+    }
+
+    /// Statically allocates memory and depends on [`CountingLeaf`].
+    #[derive(Debug)]
+    struct AllocatingParent;
+
+    impl BasicSnippet for AllocatingParent {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            vec![]
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            vec![]
+        }
+
+        fn entrypoint(&self) -> String {
+            "tasmlib_allocating_parent_dummy_test_value".to_string()
+        }
+
+        fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+            let leaf = library.import(Box::new(CountingLeaf));
+            let allocation = library.kmalloc(1);
+            triton_asm!(
+                {self.entrypoint()}:
+                    push {allocation.write_address()}
+                    pop 1
+                    call {leaf}
+                    return
+            )
+        }
+    }
+
+    #[test]
+    fn reimporting_does_not_generate_code_of_dependencies() {
+        let mut lib = Library::new();
+        let num_code_generations = || NUM_COUNTING_LEAF_CODE_GENERATIONS.with(|count| count.get());
+
+        lib.import(Box::new(AllocatingParent));
+        let num_code_generations_on_first_import = num_code_generations();
+        lib.kmalloc(42);
+        for _ in 0..10 {
+            lib.try_import(Box::new(AllocatingParent)).unwrap();
+        }
+
+        assert_eq!(num_code_generations_on_first_import, num_code_generations());
+    }
+
+    #[test]
+    fn snippet_with_static_allocation_can_be_reimported_after_other_allocations() {
+        let mut lib = Library::new();
+        lib.import(Box::new(DivMod));
+        lib.kmalloc(42);
+        lib.try_import(Box::new(DivMod)).unwrap();
+    }
 }
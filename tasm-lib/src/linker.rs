@@ -1,7 +1,21 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::ops::Range;
+
+use itertools::Itertools;
+use serde::Deserialize;
+use serde::Serialize;
+use triton_vm::isa::instruction::AnInstruction;
+use triton_vm::isa::instruction::AssertionContext;
 use triton_vm::prelude::*;
 
+use crate::peephole;
+use crate::peephole::OptimizationSavings;
 use crate::prelude::*;
 use crate::prove_and_verify;
+use crate::snippet_bencher::BenchmarkCase;
 use crate::snippet_bencher::BenchmarkResult;
 
 /// Execute a Triton-VM program and return its output and execution trace length
@@ -35,3 +49,807 @@ pub fn execute_bench(
 
     BenchmarkResult::new(&simulation_trace)
 }
+
+/// If the environment variable `TASMLIB_PEEPHOLE` is set, [benchmark](execute_bench)
+/// a Triton-VM program before and after [peephole optimization](peephole::optimize)
+/// and return the optimization's savings.
+pub fn maybe_execute_peephole_bench(
+    name: String,
+    case: BenchmarkCase,
+    code: &[LabelledInstruction],
+    stack: &[BFieldElement],
+    std_in: Vec<BFieldElement>,
+    nondeterminism: NonDeterminism,
+    sponge: Option<Tip5>,
+) -> Option<OptimizationSavings> {
+    std::env::var("TASMLIB_PEEPHOLE").ok()?;
+
+    let unoptimized = execute_bench(
+        code,
+        stack,
+        std_in.clone(),
+        nondeterminism.clone(),
+        sponge.clone(),
+    );
+    let optimized = execute_bench(
+        &peephole::optimize(code),
+        stack,
+        std_in,
+        nondeterminism,
+        sponge,
+    );
+
+    Some(OptimizationSavings {
+        name,
+        case,
+        unoptimized,
+        optimized,
+    })
+}
+
+/// Replace calls to functions of fewer than `threshold` instructions by the
+/// functions' bodies.
+///
+/// A function is the code from a label up to the first `return`. It is only
+/// inlined if that `return` is unconditional, _i.e._, not preceded by `skiz`,
+/// if it does not contain `recurse` or `recurse_or_return`, and if it does not
+/// call itself, neither directly nor through other functions. Labels inside
+/// an inlined body are renamed to keep them unique; calls inside the body keep
+/// targeting the original functions. Calls immediately following `skiz` are only
+/// inlined if the body consists of exactly one instruction.
+///
+/// The original functions are retained, and inlining is not applied
+/// recursively to the inlined bodies.
+///
+/// Inlining changes the program, and with it, the program digest.
+pub fn inline_small_functions(
+    code: &[LabelledInstruction],
+    threshold: usize,
+) -> Vec<LabelledInstruction> {
+    inline_small_functions_and_track_indices(code, threshold).0
+}
+
+/// Like [`inline_small_functions`], but additionally returns, for every index
+/// into the given code, the corresponding index into the resulting code. The
+/// returned indices are one longer than the given code, mapping its end.
+fn inline_small_functions_and_track_indices(
+    code: &[LabelledInstruction],
+    threshold: usize,
+) -> (Vec<LabelledInstruction>, Vec<usize>) {
+    let functions = inlinable_functions(code, threshold);
+    let mut known_labels = code
+        .iter()
+        .filter_map(|item| match item {
+            LabelledInstruction::Label(label) => Some(label.clone()),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+
+    let mut inlined = Vec::with_capacity(code.len());
+    let mut new_indices = Vec::with_capacity(code.len() + 1);
+    let mut num_inlined_bodies = 0_usize;
+    for item in code {
+        new_indices.push(inlined.len());
+        let LabelledInstruction::Instruction(AnInstruction::Call(label)) = item else {
+            inlined.push(item.clone());
+            continue;
+        };
+        let Some(body) = functions.get(label.as_str()) else {
+            inlined.push(item.clone());
+            continue;
+        };
+
+        let follows_skiz = inlined
+            .iter()
+            .rev()
+            .find(|item| matches!(item, LabelledInstruction::Instruction(_)))
+            .is_some_and(|item| item == &LabelledInstruction::Instruction(AnInstruction::Skiz));
+        let num_instructions = body
+            .iter()
+            .filter(|item| matches!(item, LabelledInstruction::Instruction(_)))
+            .count();
+        let contains_labels = body
+            .iter()
+            .any(|item| matches!(item, LabelledInstruction::Label(_)));
+        if follows_skiz && (num_instructions != 1 || contains_labels) {
+            inlined.push(item.clone());
+            continue;
+        }
+
+        let mut renamed_labels = HashMap::new();
+        for item in body.iter() {
+            let LabelledInstruction::Label(label) = item else {
+                continue;
+            };
+            let mut new_label = format!("{label}_inlined_{num_inlined_bodies}");
+            while known_labels.contains(&new_label) {
+                num_inlined_bodies += 1;
+                new_label = format!("{label}_inlined_{num_inlined_bodies}");
+            }
+            known_labels.insert(new_label.clone());
+            renamed_labels.insert(label.as_str(), new_label);
+        }
+        num_inlined_bodies += 1;
+
+        for item in body.iter() {
+            let item = match item {
+                LabelledInstruction::Label(label) => {
+                    LabelledInstruction::Label(renamed_labels[label.as_str()].clone())
+                }
+                _ => item.clone(),
+            };
+            inlined.push(item);
+        }
+    }
+    new_indices.push(inlined.len());
+
+    (inlined, new_indices)
+}
+
+/// All functions of fewer than `threshold` instructions that can be inlined,
+/// mapped to their bodies, excluding the final `return`.
+fn inlinable_functions(
+    code: &[LabelledInstruction],
+    threshold: usize,
+) -> HashMap<&str, &[LabelledInstruction]> {
+    let callees = callees(code);
+    let mut functions = HashMap::new();
+    for (start, item) in code.iter().enumerate() {
+        let LabelledInstruction::Label(label) = item else {
+            continue;
+        };
+
+        let body_start = start + 1;
+        let Some(body_len) = code[body_start..]
+            .iter()
+            .position(|item| item == &LabelledInstruction::Instruction(AnInstruction::Return))
+        else {
+            continue;
+        };
+        let body = &code[body_start..body_start + body_len];
+
+        let instructions = body
+            .iter()
+            .filter_map(|item| match item {
+                LabelledInstruction::Instruction(instruction) => Some(instruction),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let returns_conditionally = instructions.last() == Some(&&AnInstruction::Skiz);
+        let recurses = instructions.iter().any(|instruction| {
+            matches!(
+                instruction,
+                AnInstruction::Recurse | AnInstruction::RecurseOrReturn
+            )
+        });
+        if instructions.len() < threshold
+            && !returns_conditionally
+            && !recurses
+            && !calls_itself(label, &callees)
+        {
+            functions.insert(label.as_str(), body);
+        }
+    }
+
+    functions
+}
+
+/// The labels called by each function. Here, a function is the code from a
+/// label up to the first instruction that unconditionally ends execution of
+/// the function, like `return`. This includes code of any labels in between,
+/// which execution falls through to.
+fn callees(code: &[LabelledInstruction]) -> HashMap<&str, HashSet<&str>> {
+    let ends_function = |instruction: &AnInstruction<String>| {
+        matches!(
+            instruction,
+            AnInstruction::Return
+                | AnInstruction::Recurse
+                | AnInstruction::RecurseOrReturn
+                | AnInstruction::Halt
+        )
+    };
+
+    let mut callees = HashMap::new();
+    for (start, item) in code.iter().enumerate() {
+        let LabelledInstruction::Label(label) = item else {
+            continue;
+        };
+
+        let mut called_labels = HashSet::new();
+        let mut follows_skiz = false;
+        for item in &code[start + 1..] {
+            let LabelledInstruction::Instruction(instruction) = item else {
+                continue;
+            };
+            if let AnInstruction::Call(callee) = instruction {
+                called_labels.insert(callee.as_str());
+            }
+            if ends_function(instruction) && !follows_skiz {
+                break;
+            }
+            follows_skiz = instruction == &AnInstruction::Skiz;
+        }
+        callees.insert(label.as_str(), called_labels);
+    }
+
+    callees
+}
+
+/// Whether the function with the given label can be reached from its own body
+/// through calls, possibly via other functions.
+fn calls_itself(label: &str, callees: &HashMap<&str, HashSet<&str>>) -> bool {
+    let mut visited = HashSet::new();
+    let mut to_visit = callees.get(label).into_iter().flatten().collect_vec();
+    while let Some(&function) = to_visit.pop() {
+        if function == label {
+            return true;
+        }
+        if visited.insert(function) {
+            to_visit.extend(callees.get(function).into_iter().flatten());
+        }
+    }
+
+    false
+}
+
+/// The origin of one instruction in a linked program.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct SourceLocation {
+    /// The instruction's address in the program.
+    pub address: u64,
+
+    /// The entrypoint of the snippet the instruction belongs to, or
+    /// [`SourceMap::PROGRAM`] for instructions that belong to no snippet.
+    pub snippet: String,
+
+    /// The distance, in words, of the instruction from the snippet's entrypoint.
+    pub offset: u64,
+
+    /// The nearest label preceding the instruction.
+    pub label: String,
+
+    /// The context of the instruction, if it is an assertion.
+    pub assertion_context: Option<AssertionContext>,
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            address,
+            snippet,
+            offset,
+            label,
+            ..
+        } = self;
+        write!(f, "{address:>6}: {snippet}+{offset}")?;
+        if label != snippet {
+            write!(f, " in {label}")?;
+        }
+        if let Some(AssertionContext::ID(id)) = self.assertion_context {
+            write!(f, " (error_id {id})")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The range of a snippet's code in some [linked code](LinkedCode), as indices
+/// into the linked [`LabelledInstruction`]s.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct SnippetRange {
+    pub entrypoint: String,
+    pub range: Range<usize>,
+}
+
+/// Linked code, together with the [range](SnippetRange) of every snippet's code
+/// in it.
+///
+/// Recording the snippets while linking makes it possible to build a
+/// [`SourceMap`] without guessing which snippet a label belongs to.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct LinkedCode {
+    pub code: Vec<LabelledInstruction>,
+    pub snippets: Vec<SnippetRange>,
+}
+
+impl LinkedCode {
+    /// Append code that does not belong to any snippet, like a program's main
+    /// function.
+    pub fn push_code(&mut self, code: &[LabelledInstruction]) {
+        self.code.extend_from_slice(code);
+    }
+
+    /// Append the code of the snippet with the given entrypoint.
+    pub fn push_snippet(&mut self, entrypoint: &str, code: &[LabelledInstruction]) {
+        let start = self.code.len();
+        self.code.extend_from_slice(code);
+        self.snippets.push(SnippetRange {
+            entrypoint: entrypoint.to_string(),
+            range: start..self.code.len(),
+        });
+    }
+
+    /// Append other linked code, keeping its snippet ranges intact.
+    pub fn append(&mut self, other: Self) {
+        let offset = self.code.len();
+        self.code.extend(other.code);
+        let snippets = other.snippets.into_iter().map(|snippet| SnippetRange {
+            entrypoint: snippet.entrypoint,
+            range: snippet.range.start + offset..snippet.range.end + offset,
+        });
+        self.snippets.extend(snippets);
+    }
+
+    /// [Inline small functions](inline_small_functions), keeping the snippet
+    /// ranges intact. Inlined code belongs to the snippet it is inlined into.
+    pub fn inline_small_functions(self, threshold: usize) -> Self {
+        let (code, new_indices) = inline_small_functions_and_track_indices(&self.code, threshold);
+        let snippets = self
+            .snippets
+            .into_iter()
+            .map(|snippet| SnippetRange {
+                entrypoint: snippet.entrypoint,
+                range: new_indices[snippet.range.start]..new_indices[snippet.range.end],
+            })
+            .collect();
+
+        Self { code, snippets }
+    }
+
+    pub fn source_map(&self) -> SourceMap {
+        SourceMap::new(&self.code, &self.snippets)
+    }
+}
+
+/// Maps instruction addresses of a linked program to their [origin](SourceLocation).
+///
+/// Snippets are identified by the [ranges](SnippetRange) the linker recorded
+/// for them. Instructions outside any snippet's range belong to
+/// [`SourceMap::PROGRAM`].
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SourceMap {
+    locations: Vec<SourceLocation>,
+}
+
+impl SourceMap {
+    /// The name of the code that does not belong to any snippet.
+    pub const PROGRAM: &'static str = "program";
+
+    /// # Panics
+    ///
+    /// Panics if some snippet range exceeds the code.
+    pub fn new(code: &[LabelledInstruction], snippets: &[SnippetRange]) -> Self {
+        let mut owners = vec![None; code.len()];
+        for snippet in snippets {
+            owners[snippet.range.clone()].fill(Some(snippet.entrypoint.as_str()));
+        }
+
+        let mut locations: Vec<SourceLocation> = vec![];
+        let mut address = 0;
+        let mut owner = None;
+        let mut snippet = Self::PROGRAM.to_string();
+        let mut snippet_address = 0;
+        let mut label = Self::PROGRAM.to_string();
+        for (index, item) in code.iter().enumerate() {
+            if index == 0 || owners[index] != owner {
+                owner = owners[index];
+                snippet = owner.unwrap_or(Self::PROGRAM).to_string();
+                snippet_address = address;
+                label.clone_from(&snippet);
+            }
+
+            match item {
+                LabelledInstruction::Instruction(instruction) => {
+                    locations.push(SourceLocation {
+                        address,
+                        snippet: snippet.clone(),
+                        offset: address - snippet_address,
+                        label: label.clone(),
+                        assertion_context: None,
+                    });
+                    address += instruction.size() as u64;
+                }
+                LabelledInstruction::Label(new_label) => label.clone_from(new_label),
+                LabelledInstruction::AssertionContext(context) => {
+                    if let Some(location) = locations.last_mut() {
+                        location.assertion_context = Some(context.clone());
+                    }
+                }
+                LabelledInstruction::Breakpoint | LabelledInstruction::TypeHint(_) => (),
+            }
+        }
+
+        Self { locations }
+    }
+
+    /// The origin of the instruction at the given address, if any.
+    pub fn location(&self, address: u64) -> Option<&SourceLocation> {
+        self.locations
+            .binary_search_by_key(&address, |location| location.address)
+            .ok()
+            .map(|index| &self.locations[index])
+    }
+
+    /// The symbolic call stack of the given state, innermost frame first.
+    ///
+    /// The first frame is the current instruction; every other frame is the
+    /// `call` instruction of an entry in the jump stack.
+    pub fn call_stack(&self, vm_state: &VMState) -> Vec<&SourceLocation> {
+        const CALL_SIZE: u64 = 2;
+
+        let current_address = vm_state.instruction_pointer as u64;
+        let call_sites = vm_state
+            .jump_stack
+            .iter()
+            .rev()
+            .map(|(origin, _)| origin.value().saturating_sub(CALL_SIZE));
+
+        [current_address]
+            .into_iter()
+            .chain(call_sites)
+            .filter_map(|address| self.location(address))
+            .collect()
+    }
+
+    /// The [call stack](Self::call_stack) of the given state, one frame per line.
+    pub fn format_call_stack(&self, vm_state: &VMState) -> String {
+        self.call_stack(vm_state)
+            .into_iter()
+            .enumerate()
+            .map(|(depth, location)| format!("{depth:>3} {location}"))
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_equivalent_execution(code: &[LabelledInstruction], inlined: &[LabelledInstruction]) {
+        let run = |code: &[LabelledInstruction]| {
+            let mut vm_state = VMState::new(
+                Program::new(code),
+                PublicInput::new(bfe_vec![7]),
+                NonDeterminism::default(),
+            );
+            let result = vm_state.run();
+            (result, vm_state.op_stack.stack, vm_state.public_output)
+        };
+
+        let (result, stack, output) = run(code);
+        let (inlined_result, inlined_stack, inlined_output) = run(inlined);
+        assert_eq!(result, inlined_result);
+        assert_eq!(stack[Digest::LEN..], inlined_stack[Digest::LEN..]);
+        assert_eq!(output, inlined_output);
+    }
+
+    #[test]
+    fn small_functions_are_inlined() {
+        let code = triton_asm!(
+            read_io 1
+            call incr
+            call incr
+            call square_and_incr
+            write_io 1
+            halt
+
+            incr:
+                addi 1
+                return
+            square_and_incr:
+                dup 0
+                mul
+                call incr
+                return
+        );
+
+        let inlined = inline_small_functions(&code, 2);
+        let expected_start = triton_asm!(
+            read_io 1
+            addi 1
+            addi 1
+            call square_and_incr
+            write_io 1
+            halt
+        );
+        assert_eq!(expected_start, inlined[..expected_start.len()]);
+        assert_equivalent_execution(&code, &inlined);
+
+        let inlined = inline_small_functions(&code, 4);
+        let expected_start = triton_asm!(
+            read_io 1
+            addi 1
+            addi 1
+            dup 0
+            mul
+            call incr
+            write_io 1
+            halt
+        );
+        assert_eq!(expected_start, inlined[..expected_start.len()]);
+        assert_equivalent_execution(&code, &inlined);
+    }
+
+    #[test]
+    fn zero_threshold_disables_inlining() {
+        let code = triton_asm!(call nothing halt nothing: return);
+        assert_eq!(code, inline_small_functions(&code, 0));
+    }
+
+    #[test]
+    fn recursive_functions_are_not_inlined() {
+        let code = triton_asm!(
+            push 3
+            call count_down
+            call loop_
+            halt
+
+            count_down:
+                dup 0 push 0 eq
+                skiz return
+                addi -1
+                recurse
+            loop_:
+                recurse_or_return
+                return
+        );
+        assert_eq!(code, inline_small_functions(&code, 100));
+    }
+
+    #[test]
+    fn functions_calling_themselves_are_not_inlined() {
+        let code = triton_asm!(
+            read_io 1
+            dup 0
+            dup 0
+            call count_down
+            pop 1
+            call ping
+            pop 1
+            call wrapped_count_down
+            halt
+
+            count_down:
+                addi -1
+                dup 0
+                skiz call count_down
+                return
+            ping:
+                addi -1
+                dup 0
+                skiz call pong
+                return
+            pong:
+                addi -1
+                dup 0
+                skiz call ping
+                return
+            wrapped_count_down:
+                call count_down
+                return
+        );
+
+        let inlined = inline_small_functions(&code, 10);
+        let expected_start = triton_asm!(
+            read_io 1
+            dup 0
+            dup 0
+            call count_down
+            pop 1
+            call ping
+            pop 1
+            call count_down
+            halt
+        );
+        assert_eq!(expected_start, inlined[..expected_start.len()]);
+        assert_eq!(
+            code[expected_start.len()..],
+            inlined[expected_start.len()..]
+        );
+        assert_equivalent_execution(&code, &inlined);
+    }
+
+    #[test]
+    fn labels_of_inlined_bodies_are_unique() {
+        let code = triton_asm!(
+            read_io 1
+            call with_inner_label
+            call with_inner_label
+            call inner_label
+            write_io 1
+            halt
+
+            with_inner_label:
+                addi 1
+            inner_label:
+                addi 2
+                return
+            inner_label_inlined_0:
+                return
+        );
+
+        let inlined = inline_small_functions(&code, 10);
+        let labels = inlined
+            .iter()
+            .filter(|item| matches!(item, LabelledInstruction::Label(_)))
+            .collect_vec();
+        assert!(labels.iter().all_unique());
+        assert_eq!(5, labels.len());
+        assert_equivalent_execution(&code, &inlined);
+    }
+
+    #[test]
+    fn calls_following_skiz_are_only_inlined_if_body_is_one_instruction() {
+        let code = triton_asm!(
+            read_io 1
+            dup 0
+            skiz call one
+            dup 0
+            skiz call two
+            write_io 1
+            halt
+
+            one:
+                addi 1
+                return
+            two:
+                addi 1
+                addi 1
+                return
+        );
+
+        let inlined = inline_small_functions(&code, 10);
+        let expected_start = triton_asm!(
+            read_io 1
+            dup 0
+            skiz addi 1
+            dup 0
+            skiz call two
+        );
+        assert_eq!(expected_start, inlined[..expected_start.len()]);
+        assert_equivalent_execution(&code, &inlined);
+    }
+
+    #[test]
+    fn source_map_attributes_instructions_to_snippets_and_labels() {
+        let mut linked_code = LinkedCode::default();
+        linked_code.push_code(&triton_asm!(call foo halt));
+        linked_code.push_snippet(
+            "foo",
+            &triton_asm!(
+                foo:
+                    push 1
+                    call foo_helper
+                    return
+                foo_helper:
+                    assert error_id 42
+                    call bar
+                    return
+            ),
+        );
+        linked_code.push_snippet("bar", &triton_asm!(bar: return));
+
+        let source_map = linked_code.source_map();
+        let location = |address| source_map.location(address).unwrap();
+
+        assert_eq!(SourceMap::PROGRAM, location(0).snippet);
+        assert!(source_map.location(1).is_none());
+        assert_eq!(SourceMap::PROGRAM, location(2).label);
+
+        let push = location(3);
+        assert_eq!(
+            ("foo", 0, "foo"),
+            (push.snippet.as_str(), push.offset, push.label.as_str())
+        );
+
+        let assertion = location(8);
+        assert_eq!("foo", assertion.snippet);
+        assert_eq!(5, assertion.offset);
+        assert_eq!("foo_helper", assertion.label);
+        assert_eq!(Some(AssertionContext::ID(42)), assertion.assertion_context);
+
+        let bar = location(12);
+        assert_eq!(("bar", 0), (bar.snippet.as_str(), bar.offset));
+        assert!(source_map.location(13).is_none());
+    }
+
+    #[test]
+    fn source_map_distinguishes_snippets_sharing_a_prefix() {
+        use crate::arithmetic::u128::div::Div;
+        use crate::arithmetic::u128::div_mod::DivMod;
+
+        let (code, source_map) = Div.link_for_isolated_run_with_source_map();
+        let div = Div.entrypoint();
+        let div_mod = DivMod.entrypoint();
+        assert!(div_mod.starts_with(&format!("{div}_")));
+
+        let snippet_of_label = |label: &str| {
+            let mut snippets = source_map
+                .locations
+                .iter()
+                .filter(|location| location.label == label)
+                .map(|location| location.snippet.as_str())
+                .unique();
+            let snippet = snippets.next().unwrap();
+            assert!(snippets.next().is_none());
+            snippet
+        };
+        assert_eq!(div, snippet_of_label(&div));
+        assert_eq!(div_mod, snippet_of_label(&div_mod));
+
+        let program = Program::new(&code);
+        let div_mod_address = program
+            .labelled_instructions()
+            .iter()
+            .take_while(|item| item != &&LabelledInstruction::Label(div_mod.clone()))
+            .filter_map(|item| match item {
+                LabelledInstruction::Instruction(instruction) => Some(instruction.size() as u64),
+                _ => None,
+            })
+            .sum();
+        assert_eq!(0, source_map.location(div_mod_address).unwrap().offset);
+    }
+
+    #[test]
+    fn snippet_ranges_survive_inlining() {
+        let mut linked_code = LinkedCode::default();
+        linked_code.push_code(&triton_asm!(call foo halt));
+        linked_code.push_snippet("foo", &triton_asm!(foo: call bar call bar return));
+        linked_code.push_snippet("bar", &triton_asm!(bar: push 1 return));
+
+        let inlined = linked_code.inline_small_functions(2);
+        assert_eq!(triton_asm!(foo: push 1 push 1 return), inlined.code[2..6]);
+
+        let source_map = inlined.source_map();
+        let snippets = source_map
+            .locations
+            .iter()
+            .map(|location| location.snippet.as_str())
+            .collect_vec();
+        let program = SourceMap::PROGRAM;
+        let expected = [program, program, "foo", "foo", "foo", "bar", "bar"];
+        assert_eq!(expected.to_vec(), snippets);
+    }
+
+    #[test]
+    fn call_stack_of_crashed_program_is_symbolic() {
+        let mut linked_code = LinkedCode::default();
+        linked_code.push_code(&triton_asm!(call foo halt));
+        linked_code.push_snippet(
+            "foo",
+            &triton_asm!(
+                foo:
+                    call foo_helper
+                    return
+                foo_helper:
+                    push 0
+                    assert error_id 42
+                    return
+            ),
+        );
+
+        let program = Program::new(&linked_code.code);
+        let mut vm_state = VMState::new(
+            program.clone(),
+            PublicInput::default(),
+            NonDeterminism::default(),
+        );
+        assert!(vm_state.run().is_err());
+
+        let source_map = linked_code.source_map();
+        let call_stack = source_map
+            .call_stack(&vm_state)
+            .into_iter()
+            .map(|location| (location.snippet.as_str(), location.label.as_str()))
+            .collect_vec();
+        let expected = [
+            ("foo", "foo_helper"),
+            ("foo", "foo"),
+            (SourceMap::PROGRAM, SourceMap::PROGRAM),
+        ];
+        assert_eq!(expected.to_vec(), call_stack);
+
+        let formatted = source_map.format_call_stack(&vm_state);
+        assert!(formatted.contains("foo+5 in foo_helper (error_id 42)"));
+    }
+}
@@ -18,19 +18,34 @@
 /// The number of VM words required to store the metadata / bookkeeping data of a list.
 pub const LIST_METADATA_SIZE: usize = 1;
 
+pub mod batch_inversion;
+pub mod binary_search;
 pub mod contains;
+pub mod dedup;
+pub mod elementwise_inversion;
+pub mod extend;
 pub mod get;
+pub mod get_dyn_sized;
 pub mod higher_order;
 pub mod horner_evaluation_dynamic_length;
+pub mod insert;
 pub mod length;
+pub mod multiset_equality;
 pub mod multiset_equality_digests;
 pub mod multiset_equality_u64s;
 pub mod new;
+pub mod next_dyn_sized;
 pub mod pop;
 pub mod push;
+pub mod push_dyn_sized;
 pub mod range;
+pub mod remove;
+pub mod reverse;
 pub mod set;
+pub mod set_dyn_sized;
 pub mod set_length;
+pub mod sort;
+pub mod sort_nondeterministic;
 pub mod split_off;
 pub mod sum_bfes;
 pub mod sum_xfes;
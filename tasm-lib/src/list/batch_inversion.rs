@@ -0,0 +1,374 @@
+use itertools::Itertools;
+use triton_vm::prelude::*;
+
+use crate::list::LIST_METADATA_SIZE;
+use crate::prelude::*;
+
+/// Invert all elements of a list of [`BFieldElement`]s or [`XFieldElement`]s
+/// in place, using [Montgomery's batch-inversion trick][batch_inversion].
+///
+/// Only one (field) inversion is performed; all other operations are
+/// multiplications. The partial products are kept on the stack, so no memory
+/// besides the list's own is used.
+///
+/// Since Triton VM can invert field elements natively, this is _not_ cheaper
+/// than the [element-wise inversion](super::elementwise_inversion::ElementwiseInversion),
+/// which should be preferred and is the one [exported](crate::exported_snippets).
+/// Batch inversion might still be useful if the
+/// number of inversions is to be kept low, for example, when mirroring some
+/// other system's execution trace. The benchmark lists the savings over the
+/// element-wise inversion.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *list
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// - the argument `*list` points to a properly [`BFieldCodec`]-encoded list
+/// - no element of the list is zero
+///
+/// ### Postconditions
+///
+/// - every element of the list is replaced by its multiplicative inverse
+///
+/// [batch_inversion]: twenty_first::math::traits::FiniteField::batch_inversion
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct BatchInversion {
+    element_type: DataType,
+}
+
+impl BatchInversion {
+    pub const ZERO_ELEMENT_ERROR_ID: i128 = 630;
+
+    /// # Panics
+    ///
+    /// Panics if the element type is neither [`DataType::Bfe`] nor
+    /// [`DataType::Xfe`].
+    pub fn new(element_type: DataType) -> Self {
+        Self::assert_element_type_is_supported(&element_type);
+
+        Self { element_type }
+    }
+
+    pub(crate) fn assert_element_type_is_supported(element_type: &DataType) {
+        assert!(
+            matches!(element_type, DataType::Bfe | DataType::Xfe),
+            "element type must be a field element, got: {element_type:?}"
+        );
+    }
+
+    /// Generate code that crashes the VM with the given error ID if the field
+    /// element on top of the stack is zero.
+    ///
+    /// ```text
+    /// BEFORE: _ [element]
+    /// AFTER:  _ [element]
+    /// ```
+    pub(crate) fn assert_non_zero_code(
+        element_type: &DataType,
+        error_id: i128,
+    ) -> Vec<LabelledInstruction> {
+        let element_size = element_type.stack_size();
+        let mut code = triton_asm!(dup 0 push 0 eq);
+        for i in 1..element_size {
+            code.extend(triton_asm!(dup {i + 1} push 0 eq mul));
+        }
+        code.extend(triton_asm!(push 0 eq assert error_id { error_id }));
+
+        code
+    }
+
+    /// Generate code that inverts the field element on top of the stack.
+    pub(crate) fn invert_code(element_type: &DataType) -> Vec<LabelledInstruction> {
+        match element_type {
+            DataType::Bfe => triton_asm!(invert),
+            DataType::Xfe => triton_asm!(x_invert),
+            _ => unreachable!(),
+        }
+    }
+
+    fn self_type(&self) -> DataType {
+        DataType::List(Box::new(self.element_type.to_owned()))
+    }
+}
+
+impl BasicSnippet for BatchInversion {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(self.self_type(), "self".to_owned())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        let element_type = self.element_type.label_friendly_name();
+        format!("tasmlib_list_batch_inversion_{element_type}")
+    }
+
+    fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let invert_non_empty_list = format!("{entrypoint}_invert_non_empty_list");
+        let accumulate_products = format!("{entrypoint}_accumulate_products");
+        let invert_elements = format!("{entrypoint}_invert_elements");
+
+        // Instruction `recurse_or_return` compares stack elements 5 and 6. The
+        // loops' pointers are kept there by padding the accumulator.
+        let element_size = self.element_type.stack_size();
+        let padding_size = 5 - element_size;
+        let (one, mul) = match self.element_type {
+            DataType::Bfe => (triton_asm!(push 1), triton_asm!(mul)),
+            DataType::Xfe => (triton_asm!(push 0 push 0 push 1), triton_asm!(xx_mul)),
+            _ => unreachable!(),
+        };
+        let padding = vec![triton_asm!(push 0); padding_size].concat();
+        let push_acc_below_pointers = (0..element_size)
+            .rev()
+            .flat_map(|i| triton_asm!(dup { i } place 7))
+            .collect_vec();
+        let dup_element = |depth: usize| vec![triton_asm!(dup { depth }); element_size].concat();
+        let pick_element = |depth: usize| vec![triton_asm!(pick { depth }); element_size].concat();
+        let point_to_last_word = match element_size {
+            1 => vec![],
+            _ => triton_asm!(addi { element_size - 1 }),
+        };
+
+        triton_asm!(
+            // BEFORE: _ *list
+            // AFTER:  _
+            {entrypoint}:
+                dup 0
+                read_mem 1
+                pop 1               // _ *list len
+                push {element_size}
+                mul
+                dup 1
+                add                 // _ *list *last_word
+
+                dup 1
+                dup 1
+                eq
+                push 0
+                eq                  // _ *list *last_word (len != 0)
+                skiz
+                    call {invert_non_empty_list}
+
+                pop 2
+                return
+
+            // BEFORE: _ *list *last_word
+            // AFTER:  _ *end  *end
+            {invert_non_empty_list}:
+                {&padding}
+                {&one}              // _ *list *last_word [padding] [1]
+
+                call {accumulate_products}
+                                    // _ [partial_products] *list *list [padding] [product]
+
+                {&Self::assert_non_zero_code(&self.element_type, Self::ZERO_ELEMENT_ERROR_ID)}
+                {&Self::invert_code(&self.element_type)}
+                                    // _ [partial_products] *list *list [padding] [product⁻¹]
+
+                pick 5
+                addi {LIST_METADATA_SIZE}
+                place 5
+                pick 6
+                dup 0
+                read_mem 1
+                pop 1
+                push {element_size}
+                mul
+                add
+                addi {LIST_METADATA_SIZE}
+                place 6             // _ [partial_products] *end *first_word [padding] [product⁻¹]
+
+                call {invert_elements}
+                                    // _ *end *end [padding] [1]
+
+                pop 5
+                return
+
+            // Walks the list from its end to its beginning. Puts the product of
+            // all elements visited so far below the pointers.
+            //
+            // INVARIANT: _ [partial_products] *list *element_last_word [padding] [acc]
+            {accumulate_products}:
+                {&push_acc_below_pointers}
+                                    // _ [partial_products] [acc] *list *element_last_word [padding] [acc]
+
+                pick 5
+                read_mem {element_size}
+                place {element_size + 5}
+                                    // _ [partial_products'] *list *element_last_word' [padding] [acc] [element]
+
+                {&mul}              // _ [partial_products'] *list *element_last_word' [padding] [acc']
+                recurse_or_return
+
+            // Walks the list from its beginning to its end. The accumulator is
+            // the inverse of the product of all elements not yet visited. The
+            // partial products are consumed in the reverse order of their
+            // creation.
+            //
+            // INVARIANT: _ [partial_products] *end *element_first_word [padding] [acc]
+            {invert_elements}:
+                dup 5
+                {&point_to_last_word}
+                read_mem {element_size}
+                pop 1               // _ [partial_products] *end *element_first_word [padding] [acc] [element]
+
+                {&pick_element(2 * element_size + 6)}
+                {&dup_element(3 * element_size - 1)}
+                {&mul}              // _ [partial_products'] *end *element_first_word [padding] [acc] [element] [element⁻¹]
+
+                pick {2 * element_size + 5}
+                write_mem {element_size}
+                place {element_size + 5}
+                                    // _ [partial_products'] *end *element_first_word' [padding] [acc] [element]
+
+                {&mul}              // _ [partial_products'] *end *element_first_word' [padding] [acc']
+                recurse_or_return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::strategy::Union;
+    use twenty_first::math::traits::FiniteField;
+
+    use super::*;
+    use crate::empty_stack;
+    use crate::rust_shadowing_helper_functions::list::load_list_unstructured;
+    use crate::test_prelude::*;
+
+    impl BatchInversion {
+        pub(crate) fn set_up_initial_state(
+            element_type: &DataType,
+            list_pointer: BFieldElement,
+            list_length: usize,
+            zero_index: Option<usize>,
+        ) -> FunctionInitialState {
+            let mut list = element_type.random_list(&mut rand::rng(), list_length);
+            if let Some(index) = zero_index {
+                let element_size = element_type.stack_size();
+                let first_word = LIST_METADATA_SIZE + index * element_size;
+                list[first_word..first_word + element_size].fill(bfe!(0));
+            }
+
+            let mut memory = HashMap::default();
+            let indexed_list = (0..).map(|i| list_pointer + bfe!(i)).zip(list);
+            memory.extend(indexed_list);
+
+            let stack = [empty_stack(), vec![list_pointer]].concat();
+            FunctionInitialState { stack, memory }
+        }
+
+        /// Invert the list pointed to by `list_pointer` in place.
+        fn invert_in_place<F: FiniteField + BFieldCodec>(
+            list_pointer: BFieldElement,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let element_size = F::static_length().unwrap();
+            let list = load_list_unstructured(element_size, list_pointer, memory)
+                .into_iter()
+                .map(|words| *F::decode(&words).unwrap())
+                .collect_vec();
+            assert!(list.iter().all(|element| !element.is_zero()));
+
+            let inverses = F::batch_inversion(list);
+            encode_to_memory(memory, list_pointer, &inverses);
+        }
+    }
+
+    impl Function for BatchInversion {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let list_pointer = stack.pop().unwrap();
+            match self.element_type {
+                DataType::Bfe => Self::invert_in_place::<BFieldElement>(list_pointer, memory),
+                DataType::Xfe => Self::invert_in_place::<XFieldElement>(list_pointer, memory),
+                _ => unreachable!(),
+            }
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let list_length = match bench_case {
+                Some(BenchmarkCase::CommonCase) => 100,
+                Some(BenchmarkCase::WorstCase) => 1000,
+                None => rng.random_range(0..200),
+            };
+
+            Self::set_up_initial_state(&self.element_type, rng.random(), list_length, None)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            (0..4)
+                .map(|len| Self::set_up_initial_state(&self.element_type, bfe!(1), len, None))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        for element_type in [DataType::Bfe, DataType::Xfe] {
+            ShadowedFunction::new(BatchInversion::new(element_type)).test();
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_field_element_type_is_unsupported() {
+        BatchInversion::new(DataType::U64);
+    }
+
+    #[proptest(cases = 50)]
+    fn zero_element_crashes_vm(
+        #[strategy(Union::new([DataType::Bfe, DataType::Xfe].map(Just)))] element_type: DataType,
+        #[strategy(1_usize..100)] list_length: usize,
+        #[strategy(0..#list_length)] zero_index: usize,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let initial_state = BatchInversion::set_up_initial_state(
+            &element_type,
+            list_pointer,
+            list_length,
+            Some(zero_index),
+        );
+
+        test_assertion_failure(
+            &ShadowedFunction::new(BatchInversion::new(element_type)),
+            initial_state.into(),
+            &[BatchInversion::ZERO_ELEMENT_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::list::elementwise_inversion::ElementwiseInversion;
+    use crate::snippet_bencher::write_benchmarks_with_savings;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        for element_type in [DataType::Bfe, DataType::Xfe] {
+            let elementwise_inversion = ElementwiseInversion::new(element_type.clone());
+            let baselines = ShadowedFunction::new(elementwise_inversion).benchmarks();
+            let benchmarks = ShadowedFunction::new(BatchInversion::new(element_type)).benchmarks();
+            write_benchmarks_with_savings(benchmarks, &baselines);
+        }
+    }
+}
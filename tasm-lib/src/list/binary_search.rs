@@ -0,0 +1,580 @@
+use triton_vm::prelude::*;
+
+use crate::list::sort::Sort;
+use crate::prelude::*;
+
+/// How [`BinarySearch`] finds the index it returns.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SearchMode {
+    /// Bisect the list inside Triton VM. This is *O*(log *n*).
+    Bisection,
+
+    /// Divine the index and check only the neighbouring elements. This is
+    /// *O*(1).
+    ///
+    /// The index is the first individual token of the secret input.
+    Witness,
+}
+
+/// Search a sorted list for a given element.
+///
+/// Returns whether the element was found, and the index of the first element
+/// that is not smaller than the needle. If the element was found, this is the
+/// index of its first occurrence; if it was not found, this is the index at
+/// which the needle could be inserted while keeping the list sorted.
+///
+/// Only supports lists with elements of type [`DataType::U32`],
+/// [`DataType::U64`], [`DataType::U128`], [`DataType::Bfe`], or
+/// [`DataType::Digest`]. The list must be sorted in the order established by
+/// [`Sort`].
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *list [needle: ElementType]
+/// AFTER:  _ [found: bool] [index: u32]
+/// ```
+///
+/// ### Preconditions
+///
+/// - the argument `*list` points to a properly [`BFieldCodec`]-encoded list
+/// - the list is sorted in ascending order
+/// - all input arguments are properly [`BFieldCodec`] encoded
+///
+/// ### Postconditions
+///
+/// - `index` is in range `0..=list.len()`
+/// - all elements before `index` are smaller than `needle`
+/// - the element at `index`, if any, is not smaller than `needle`
+/// - `found` is `true` if and only if the element at `index` equals `needle`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct BinarySearch {
+    element_type: DataType,
+    mode: SearchMode,
+}
+
+impl BinarySearch {
+    pub const WITNESS_INDEX_OUT_OF_BOUNDS_ERROR_ID: i128 = 690;
+    pub const WITNESS_INDEX_TOO_LARGE_ERROR_ID: i128 = 691;
+    pub const WITNESS_INDEX_TOO_SMALL_ERROR_ID: i128 = 692;
+
+    /// # Panics
+    ///
+    /// Panics if the element type is not supported. See [`BinarySearch`] for
+    /// the list of supported element types.
+    pub fn new(element_type: DataType, mode: SearchMode) -> Self {
+        Sort::assert_element_type_is_supported(&element_type);
+
+        Self { element_type, mode }
+    }
+
+    /// ```text
+    /// BEFORE: _ *list index
+    /// AFTER:  _ *list[index]_last_word
+    /// ```
+    fn element_last_word_pointer(&self) -> Vec<LabelledInstruction> {
+        let element_size = self.element_type.stack_size();
+        triton_asm!(
+            addi 1
+            push {element_size}
+            mul
+            add
+        )
+    }
+}
+
+impl BasicSnippet for BinarySearch {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let element_type = self.element_type.clone();
+        let list_type = DataType::List(Box::new(element_type.clone()));
+
+        vec![
+            (list_type, "self".to_owned()),
+            (element_type, "needle".to_owned()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::Bool, "found".to_owned()),
+            (DataType::U32, "index".to_owned()),
+        ]
+    }
+
+    fn entrypoint(&self) -> String {
+        let element_type = self.element_type.label_friendly_name();
+        match self.mode {
+            SearchMode::Bisection => format!("tasmlib_list_binary_search___{element_type}"),
+            SearchMode::Witness => {
+                format!("tasmlib_list_binary_search_with_witness___{element_type}")
+            }
+        }
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let compare_at_index = format!("{entrypoint}_compare_at_index");
+
+        let element_size = self.element_type.stack_size().try_into().unwrap();
+        let needle_alloc = library.kmalloc(element_size);
+        let needle_last_word = needle_alloc.read_address();
+        let greater_than = Sort::greater_than_code(&self.element_type, library);
+        let element_last_word_pointer = self.element_last_word_pointer();
+
+        // Bisection guarantees that the needle is not greater than the found
+        // element, but a divined index has to be checked.
+        let check_not_greater_than_element = match self.mode {
+            SearchMode::Bisection => triton_asm!(),
+            SearchMode::Witness => triton_asm!(
+                push {needle_last_word}
+                dup 2
+                dup 2
+                {&element_last_word_pointer}
+                {&greater_than}     // _ *list index (needle > list[index])
+                push 0
+                eq
+                assert error_id {Self::WITNESS_INDEX_TOO_SMALL_ERROR_ID}
+            ),
+        };
+
+        let compare_at_index_code = triton_asm!(
+            // BEFORE: _ *list index 0
+            // AFTER:  _ *list index (list[index] == needle)
+            {compare_at_index}:
+                pop 1
+                {&check_not_greater_than_element}
+                dup 1
+                dup 1
+                {&element_last_word_pointer}
+                {&self.element_type.read_value_from_memory_pop_pointer()}
+                push {needle_last_word}
+                {&self.element_type.read_value_from_memory_pop_pointer()}
+                {&self.element_type.compare()}
+                return
+        );
+
+        let (find_index, subroutines) = match self.mode {
+            SearchMode::Bisection => {
+                let bisection_loop = format!("{entrypoint}_bisection_loop");
+                let find_index = triton_asm!(
+                    // _ *list
+                    dup 0
+                    read_mem 1
+                    pop 1
+                    push 0
+                    pick 1              // _ *list 0 len
+                    call {bisection_loop}
+                    pop 1               // _ *list index
+                );
+                let subroutines = triton_asm!(
+                    // INVARIANT: _ *list lo hi
+                    {bisection_loop}:
+                        dup 1
+                        dup 1
+                        eq
+                        skiz return
+
+                        push 2
+                        dup 1
+                        dup 3
+                        push -1
+                        mul
+                        add
+                        div_mod
+                        pop 1
+                        dup 2
+                        add             // _ *list lo hi mid
+
+                        push {needle_last_word}
+                        dup 4
+                        dup 2
+                        {&element_last_word_pointer}
+                        {&greater_than} // _ *list lo hi mid (needle > list[mid])
+
+                        /* lo' = lo + go_right · (mid + 1 - lo) */
+                        dup 1
+                        addi 1
+                        dup 4
+                        push -1
+                        mul
+                        add
+                        dup 1
+                        mul
+                        pick 4
+                        add
+                        place 3         // _ *list lo' hi mid go_right
+
+                        /* hi' = hi + (1 - go_right) · (mid - hi) */
+                        push 0
+                        eq
+                        pick 2
+                        dup 2
+                        dup 1
+                        push -1
+                        mul
+                        add
+                        pick 2
+                        mul
+                        add             // _ *list lo' mid hi'
+                        pick 1
+                        pop 1           // _ *list lo' hi'
+                        recurse
+                );
+
+                (find_index, subroutines)
+            }
+            SearchMode::Witness => {
+                let check_predecessor = format!("{entrypoint}_check_predecessor");
+                let find_index = triton_asm!(
+                    // _ *list
+                    divine 1            // _ *list index
+                    dup 0
+                    dup 2
+                    read_mem 1
+                    pop 1
+                    lt                  // _ *list index (len < index)
+                    push 0
+                    eq
+                    assert error_id {Self::WITNESS_INDEX_OUT_OF_BOUNDS_ERROR_ID}
+
+                    dup 0
+                    push 0
+                    eq
+                    push 0
+                    eq
+                    skiz call {check_predecessor}
+                                        // _ *list index
+                );
+                let subroutines = triton_asm!(
+                    // BEFORE: _ *list index
+                    // AFTER:  _ *list index
+                    {check_predecessor}:
+                        push {needle_last_word}
+                        dup 2
+                        dup 2
+                        addi -1
+                        {&element_last_word_pointer}
+                        {&greater_than} // _ *list index (needle > list[index - 1])
+                        assert error_id {Self::WITNESS_INDEX_TOO_LARGE_ERROR_ID}
+                        return
+                );
+
+                (find_index, subroutines)
+            }
+        };
+
+        triton_asm!(
+            // BEFORE: _ *list [needle]
+            // AFTER:  _ found index
+            {entrypoint}:
+                push {needle_alloc.write_address()}
+                {&self.element_type.write_value_to_memory_pop_pointer()}
+                                        // _ *list
+                {&find_index}           // _ *list index
+
+                push 0
+                dup 2
+                read_mem 1
+                pop 1
+                dup 2
+                lt                      // _ *list index 0 (index < len)
+                skiz call {compare_at_index}
+                                        // _ *list index found
+
+                place 2
+                pick 1
+                pop 1                   // _ found index
+                return
+
+            {&subroutines}
+            {&compare_at_index_code}
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use proptest::strategy::Union;
+
+    use super::*;
+    use crate::library::STATIC_MEMORY_FIRST_ADDRESS;
+    use crate::list::sort::tests::compare_elements;
+    use crate::list::sort::tests::sort_elements;
+    use crate::rust_shadowing_helper_functions::list::load_list_unstructured;
+    use crate::test_helpers::test_assertion_failure;
+    use crate::test_helpers::test_rust_equivalence_given_complete_state;
+    use crate::test_prelude::*;
+
+    /// The index of the first element that is not smaller than the needle.
+    fn honest_index(elements: &[Vec<BFieldElement>], needle: &[BFieldElement]) -> usize {
+        elements.partition_point(|element| compare_elements(element, needle) == Ordering::Less)
+    }
+
+    impl BinarySearch {
+        fn set_up_initial_state(
+            &self,
+            list_pointer: BFieldElement,
+            elements: Vec<Vec<BFieldElement>>,
+            needle: Vec<BFieldElement>,
+            witness: Option<usize>,
+        ) -> AlgorithmInitialState {
+            let list = [bfe_vec![elements.len()], elements.concat()].concat();
+            let ram: HashMap<_, _> = (0..).map(|i| list_pointer + bfe!(i)).zip(list).collect();
+
+            let individual_tokens = match self.mode {
+                SearchMode::Bisection => vec![],
+                SearchMode::Witness => {
+                    let index = witness.unwrap_or_else(|| honest_index(&elements, &needle));
+                    bfe_vec![index]
+                }
+            };
+            let nondeterminism = NonDeterminism::new(individual_tokens).with_ram(ram);
+
+            let needle = needle.into_iter().rev().collect_vec();
+            let stack = [
+                self.init_stack_for_isolated_run(),
+                vec![list_pointer],
+                needle,
+            ]
+            .concat();
+
+            AlgorithmInitialState {
+                stack,
+                nondeterminism,
+            }
+        }
+
+        fn random_sorted_elements(&self, rng: &mut StdRng, len: usize) -> Vec<Vec<BFieldElement>> {
+            let mut elements = (0..len)
+                .map(|_| self.element_type.seeded_random_element(rng))
+                .collect_vec();
+            sort_elements(&mut elements);
+
+            elements
+        }
+    }
+
+    impl Algorithm for BinarySearch {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+            nondeterminism: &NonDeterminism,
+        ) {
+            let element_size = self.element_type.stack_size();
+            let needle = (0..element_size)
+                .map(|_| stack.pop().unwrap())
+                .collect_vec();
+            let list_pointer = stack.pop().unwrap();
+            let elements = load_list_unstructured(element_size, list_pointer, memory);
+
+            let index = match self.mode {
+                SearchMode::Bisection => honest_index(&elements, &needle),
+                SearchMode::Witness => {
+                    let index = nondeterminism.individual_tokens[0].value();
+                    let index = usize::try_from(index).unwrap();
+                    assert!(index <= elements.len());
+                    if index > 0 {
+                        let predecessor = &elements[index - 1];
+                        assert_eq!(Ordering::Less, compare_elements(predecessor, &needle));
+                    }
+                    if index < elements.len() {
+                        let element = &elements[index];
+                        assert_ne!(Ordering::Less, compare_elements(element, &needle));
+                    }
+                    index
+                }
+            };
+            let found = elements.get(index) == Some(&needle);
+
+            let needle_pointer = STATIC_MEMORY_FIRST_ADDRESS - bfe!(element_size - 1);
+            memory.extend((0..).map(|i| needle_pointer + bfe!(i)).zip(needle));
+
+            stack.push(bfe!(found as u64));
+            stack.push(bfe!(index));
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> AlgorithmInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let list_length = match bench_case {
+                Some(BenchmarkCase::CommonCase) => 100,
+                Some(BenchmarkCase::WorstCase) => 1000,
+                None => rng.random_range(0..200),
+            };
+            let elements = self.random_sorted_elements(&mut rng, list_length);
+
+            let needle = if list_length > 0 && rng.random() {
+                elements[rng.random_range(0..list_length)].clone()
+            } else {
+                self.element_type.seeded_random_element(&mut rng)
+            };
+
+            let list_pointer = bfe!(rng.random_range(0..1_u64 << 30));
+            self.set_up_initial_state(list_pointer, elements, needle, None)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<AlgorithmInitialState> {
+            let element_size = self.element_type.stack_size();
+            let element = |i: u64| bfe_vec![i; element_size];
+            let list_pointer = bfe!(42);
+            let elements = [1, 3, 3, 3, 5].map(element).to_vec();
+
+            let empty_list = self.set_up_initial_state(list_pointer, vec![], element(1), None);
+            let mut states = (0..=6)
+                .map(|i| {
+                    self.set_up_initial_state(list_pointer, elements.clone(), element(i), None)
+                })
+                .collect_vec();
+            states.push(empty_list);
+
+            states
+        }
+    }
+
+    const ELEMENT_TYPES: [DataType; 5] = [
+        DataType::U32,
+        DataType::U64,
+        DataType::U128,
+        DataType::Bfe,
+        DataType::Digest,
+    ];
+
+    #[test]
+    fn rust_shadow() {
+        for element_type in ELEMENT_TYPES {
+            for mode in [SearchMode::Bisection, SearchMode::Witness] {
+                ShadowedAlgorithm::new(BinarySearch::new(element_type.clone(), mode)).test();
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn element_type_without_total_order_is_unsupported() {
+        BinarySearch::new(DataType::Xfe, SearchMode::Bisection);
+    }
+
+    #[proptest(cases = 50)]
+    fn search_agrees_with_host_binary_search(
+        #[strategy(prop::collection::vec(0_u64..50, 0..40))] mut list: Vec<u64>,
+        #[strategy(0_u64..60)] needle: u64,
+        #[strategy(Union::new([SearchMode::Bisection, SearchMode::Witness].map(Just)))]
+        mode: SearchMode,
+    ) {
+        list.sort_unstable();
+        let snippet = BinarySearch::new(DataType::U64, mode);
+        let elements = list.iter().map(|element| element.encode()).collect();
+        let initial_state = snippet.set_up_initial_state(bfe!(42), elements, needle.encode(), None);
+
+        let final_state = test_rust_equivalence_given_complete_state(
+            &ShadowedAlgorithm::new(snippet),
+            &initial_state.stack,
+            &[],
+            &initial_state.nondeterminism,
+            &None,
+            None,
+        );
+
+        let stack = final_state.op_stack.stack;
+        let index = usize::try_from(stack[stack.len() - 1].value()).unwrap();
+        let found = stack[stack.len() - 2].value() == 1;
+
+        match list.binary_search(&needle) {
+            Ok(_) => {
+                prop_assert!(found);
+                prop_assert_eq!(needle, list[index]);
+                prop_assert!(index == 0 || list[index - 1] < needle);
+            }
+            Err(insertion_index) => {
+                prop_assert!(!found);
+                prop_assert_eq!(insertion_index, index);
+            }
+        }
+    }
+
+    #[proptest(cases = 20)]
+    fn witness_index_out_of_bounds_crashes_vm(
+        #[strategy(prop::collection::vec(arb(), 0..20))] mut list: Vec<u64>,
+        #[strategy(arb())] needle: u64,
+        #[strategy(#list.len() + 1..1 << 32)] witness: usize,
+    ) {
+        list.sort_unstable();
+        let snippet = BinarySearch::new(DataType::U64, SearchMode::Witness);
+        let elements = list.iter().map(|element| element.encode()).collect();
+        let initial_state =
+            snippet.set_up_initial_state(bfe!(42), elements, needle.encode(), Some(witness));
+
+        test_assertion_failure(
+            &ShadowedAlgorithm::new(snippet),
+            initial_state.into(),
+            &[BinarySearch::WITNESS_INDEX_OUT_OF_BOUNDS_ERROR_ID],
+        );
+    }
+
+    #[proptest(cases = 20)]
+    fn witness_index_too_large_crashes_vm(
+        #[strategy(Union::new(ELEMENT_TYPES.map(Just)))] element_type: DataType,
+        #[strategy(1_usize..30)] list_length: usize,
+        #[strategy(0..#list_length)] needle_index: usize,
+        #[strategy(#needle_index + 1..=#list_length)] witness: usize,
+        seed: u64,
+    ) {
+        let snippet = BinarySearch::new(element_type, SearchMode::Witness);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let elements = snippet.random_sorted_elements(&mut rng, list_length);
+        let needle = elements[needle_index].clone();
+        let initial_state = snippet.set_up_initial_state(bfe!(42), elements, needle, Some(witness));
+
+        test_assertion_failure(
+            &ShadowedAlgorithm::new(snippet),
+            initial_state.into(),
+            &[BinarySearch::WITNESS_INDEX_TOO_LARGE_ERROR_ID],
+        );
+    }
+
+    #[proptest(cases = 20)]
+    fn witness_index_too_small_crashes_vm(
+        #[strategy(Union::new(ELEMENT_TYPES.map(Just)))] element_type: DataType,
+        #[strategy(2_usize..30)] list_length: usize,
+        #[strategy(1..#list_length)] needle_index: usize,
+        #[strategy(0..#needle_index)] witness: usize,
+        seed: u64,
+    ) {
+        let snippet = BinarySearch::new(element_type, SearchMode::Witness);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let elements = snippet.random_sorted_elements(&mut rng, list_length);
+        let needle = elements[needle_index].clone();
+
+        // avoid accidentally honest witness due to repeated elements
+        let honest_index = honest_index(&elements, &needle);
+        prop_assume!(witness < honest_index);
+        let initial_state = snippet.set_up_initial_state(bfe!(42), elements, needle, Some(witness));
+
+        test_assertion_failure(
+            &ShadowedAlgorithm::new(snippet),
+            initial_state.into(),
+            &[BinarySearch::WITNESS_INDEX_TOO_SMALL_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        for element_type in [DataType::U64, DataType::Digest] {
+            for mode in [SearchMode::Bisection, SearchMode::Witness] {
+                ShadowedAlgorithm::new(BinarySearch::new(element_type.clone(), mode)).bench();
+            }
+        }
+    }
+}
@@ -6,6 +6,8 @@ use crate::prelude::*;
 /// Returns `true` if the list contains an element with the given value.
 ///
 /// This operation is *O*(*n*).
+/// For sorted lists, consider [`BinarySearch`](super::binary_search::BinarySearch)
+/// instead.
 ///
 /// Mirrors the `contains` method from Rust `core` as closely as possible.
 ///
@@ -0,0 +1,305 @@
+use triton_vm::prelude::*;
+
+use crate::list::get::Get;
+use crate::prelude::*;
+
+/// Remove consecutive repeated elements from a list, in place. Mimics
+/// [`Vec::dedup`]. If the list is sorted, this removes all duplicates.
+///
+/// Only supports lists with [statically sized](BFieldCodec::static_length)
+/// elements. The element's static size must be in range `1..=13`.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *list
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// - the argument `*list` points to a properly [`BFieldCodec`]-encoded list
+///
+/// ### Postconditions
+///
+/// - `*list` points to a properly [`BFieldCodec`]-encoded list
+/// - no two consecutive elements of the list are equal
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Dedup {
+    element_type: DataType,
+}
+
+impl Dedup {
+    /// # Panics
+    ///
+    /// Panics
+    /// - if the element has [dynamic length][BFieldCodec::static_length], or
+    /// - if the static length is 0, or
+    /// - if the static length is larger than 13.
+    pub fn new(element_type: DataType) -> Self {
+        // need to access internal registers below the element
+        assert!(element_type.stack_size() <= 13);
+        Get::assert_element_type_is_supported(&element_type);
+
+        Self { element_type }
+    }
+}
+
+impl BasicSnippet for Dedup {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let list_type = DataType::List(Box::new(self.element_type.clone()));
+        vec![(list_type, "*list".to_string())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        let element_type = self.element_type.label_friendly_name();
+        format!("tasmlib_list_dedup___{element_type}")
+    }
+
+    fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+        let element_size = self.element_type.stack_size();
+        let read_element = self.element_type.read_value_from_memory_pop_pointer();
+
+        let entrypoint = self.entrypoint();
+        let main_loop = format!("{entrypoint}_loop");
+        let keep_element = format!("{entrypoint}_keep");
+
+        triton_asm!(
+            // BEFORE: _ *list
+            // AFTER:  _
+            {entrypoint}:
+                dup 0
+                read_mem 1
+                pop 1               // _ *list len
+
+                /* the first element, if any, is always kept */
+                dup 0
+                push 0
+                eq
+                push 0
+                eq                  // _ *list len (len != 0)
+                dup 2
+                addi 1              // _ *list len num_kept *list[0]
+                dup 0
+                addi {element_size} // _ *list len num_kept *list[0] *list[1]
+                pick 3
+                dup 3
+                push -1
+                mul
+                add                 // _ *list num_kept *list[0] *list[1] (len - num_kept)
+                                    // _ *list num_kept *last_kept *read remaining
+
+                call {main_loop}    // _ *list num_kept *last_kept *read 0
+                pop 3
+                pick 1
+                write_mem 1
+                pop 1
+                return
+
+            // INVARIANT: _ *list num_kept *last_kept *read remaining
+            {main_loop}:
+                dup 0
+                push 0
+                eq
+                skiz return
+
+                dup 1
+                addi {element_size - 1}
+                {&read_element}     // _ *list num_kept *last_kept *read remaining [element]
+                dup {element_size + 2}
+                addi {element_size - 1}
+                {&read_element}     // _ *list num_kept *last_kept *read remaining [element] [last_kept]
+                {&self.element_type.compare()}
+                                    // _ *list num_kept *last_kept *read remaining (element == last_kept)
+                push 0
+                eq
+                skiz
+                    call {keep_element}
+
+                pick 1
+                addi {element_size}
+                place 1
+                addi -1             // _ *list num_kept *last_kept *read' (remaining - 1)
+                recurse
+
+            // BEFORE: _ *list num_kept *last_kept *read remaining
+            // AFTER:  _ *list (num_kept + 1) *last_kept' *read remaining
+            {keep_element}:
+                pick 3
+                addi 1
+                place 3
+                pick 2
+                addi {element_size}
+                place 2             // _ *list num_kept' *last_kept' *read remaining
+
+                dup 1
+                addi {element_size - 1}
+                {&read_element}     // _ *list num_kept' *last_kept' *read remaining [element]
+                dup {element_size + 2}
+                {&self.element_type.write_value_to_memory_pop_pointer()}
+                                    // _ *list num_kept' *last_kept' *read remaining
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_shadowing_helper_functions::list::list_dedup;
+    use crate::rust_shadowing_helper_functions::list::list_insert;
+    use crate::rust_shadowing_helper_functions::list::list_new;
+    use crate::rust_shadowing_helper_functions::list::list_push;
+    use crate::rust_shadowing_helper_functions::list::load_list_with_copy_elements;
+    use crate::test_helpers::test_rust_equivalence_given_complete_state;
+    use crate::test_prelude::*;
+
+    impl Dedup {
+        /// Draw the elements of the list from a small pool to get many repetitions.
+        fn set_up_initial_state(
+            &self,
+            list_length: usize,
+            pool_size: usize,
+            list_pointer: BFieldElement,
+            rng: &mut impl Rng,
+        ) -> FunctionInitialState {
+            let pool = (0..pool_size)
+                .map(|_| self.element_type.seeded_random_element(rng))
+                .collect_vec();
+
+            let mut memory = HashMap::default();
+            list_new(list_pointer, &mut memory);
+            for _ in 0..list_length {
+                let element = pool[rng.random_range(0..pool_size)].clone();
+                list_push(list_pointer, element, &mut memory);
+            }
+
+            let mut stack = self.init_stack_for_isolated_run();
+            stack.push(list_pointer);
+
+            FunctionInitialState { stack, memory }
+        }
+    }
+
+    impl Function for Dedup {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let list_pointer = stack.pop().unwrap();
+            list_dedup(list_pointer, memory, self.element_type.stack_size());
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let (list_length, pool_size) = match bench_case {
+                Some(BenchmarkCase::CommonCase) => (32, 4),
+                Some(BenchmarkCase::WorstCase) => (64, 64),
+                None => (rng.random_range(0..100), rng.random_range(1..5)),
+            };
+            let list_pointer = rng.random();
+
+            self.set_up_initial_state(list_length, pool_size, list_pointer, &mut rng)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let mut rng = StdRng::seed_from_u64(0xdedb);
+            let list_pointer = bfe!(1_u64 << 32);
+
+            [(0, 1), (1, 1), (2, 1), (2, 2), (5, 1)]
+                .map(|(list_length, pool_size)| {
+                    self.set_up_initial_state(list_length, pool_size, list_pointer, &mut rng)
+                })
+                .to_vec()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        for ty in [
+            DataType::Bool,
+            DataType::Bfe,
+            DataType::U32,
+            DataType::U64,
+            DataType::Xfe,
+            DataType::Digest,
+        ] {
+            ShadowedFunction::new(Dedup::new(ty)).test();
+        }
+    }
+
+    #[proptest]
+    fn deduplicating_agrees_with_host_dedup(
+        #[strategy(prop::collection::vec(0_u32..4, 0..50))] list: Vec<u32>,
+    ) {
+        let list_pointer = bfe!(1_u64 << 32);
+        let mut memory = HashMap::default();
+        list_insert(list_pointer, list.clone(), &mut memory);
+
+        let snippet = Dedup::new(DataType::U32);
+        let mut stack = snippet.init_stack_for_isolated_run();
+        let expected_stack = stack.clone();
+        stack.push(list_pointer);
+
+        let final_state = test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &stack,
+            &[],
+            &NonDeterminism::default().with_ram(memory),
+            &None,
+            Some(&expected_stack),
+        );
+
+        let mut list = list;
+        list.dedup();
+        let list = list.into_iter().map(|x| [bfe!(x)]).collect_vec();
+        let final_list = load_list_with_copy_elements(list_pointer, &final_state.ram);
+        prop_assert_eq!(list, final_list);
+    }
+
+    #[proptest]
+    fn deduplicating_sorted_list_removes_all_duplicates(
+        #[strategy(prop::collection::vec(0_u64..10, 0..50))] mut list: Vec<u64>,
+    ) {
+        list.sort_unstable();
+        let list_pointer = bfe!(1_u64 << 32);
+        let mut memory = HashMap::default();
+        list_insert(list_pointer, list.clone(), &mut memory);
+
+        let snippet = Dedup::new(DataType::U64);
+        let mut stack = snippet.init_stack_for_isolated_run();
+        stack.push(list_pointer);
+        let final_state = test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &stack,
+            &[],
+            &NonDeterminism::default().with_ram(memory),
+            &None,
+            None,
+        );
+
+        let final_list = load_list_with_copy_elements::<2>(list_pointer, &final_state.ram);
+        let num_unique_elements = list.iter().unique().count();
+        prop_assert_eq!(num_unique_elements, final_list.len());
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(Dedup::new(DataType::Digest)).bench();
+    }
+}
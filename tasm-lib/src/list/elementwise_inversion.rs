@@ -0,0 +1,200 @@
+use triton_vm::prelude::*;
+
+use crate::list::batch_inversion::BatchInversion;
+use crate::prelude::*;
+
+/// Invert all elements of a list of [`BFieldElement`]s or [`XFieldElement`]s
+/// in place, one element at a time.
+///
+/// This is the recommended way to invert many field elements. See also
+/// [`BatchInversion`], which performs only one (field) inversion in total, but
+/// is more expensive.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *list
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// - the argument `*list` points to a properly [`BFieldCodec`]-encoded list
+/// - no element of the list is zero
+///
+/// ### Postconditions
+///
+/// - every element of the list is replaced by its multiplicative inverse
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ElementwiseInversion {
+    element_type: DataType,
+}
+
+impl ElementwiseInversion {
+    pub const ZERO_ELEMENT_ERROR_ID: i128 = 640;
+
+    /// # Panics
+    ///
+    /// Panics if the element type is neither [`DataType::Bfe`] nor
+    /// [`DataType::Xfe`].
+    pub fn new(element_type: DataType) -> Self {
+        BatchInversion::assert_element_type_is_supported(&element_type);
+
+        Self { element_type }
+    }
+
+    fn self_type(&self) -> DataType {
+        DataType::List(Box::new(self.element_type.to_owned()))
+    }
+}
+
+impl BasicSnippet for ElementwiseInversion {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(self.self_type(), "self".to_owned())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        let element_type = self.element_type.label_friendly_name();
+        format!("tasmlib_list_elementwise_inversion_{element_type}")
+    }
+
+    fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let invert_elements = format!("{entrypoint}_invert_elements");
+
+        let element_size = self.element_type.stack_size();
+        let assert_non_zero =
+            BatchInversion::assert_non_zero_code(&self.element_type, Self::ZERO_ELEMENT_ERROR_ID);
+
+        triton_asm!(
+            // BEFORE: _ *list
+            // AFTER:  _
+            {entrypoint}:
+                dup 0
+                read_mem 1
+                pop 1               // _ *list len
+                push {element_size}
+                mul
+                dup 1
+                add                 // _ *list *last_word
+
+                call {invert_elements}
+                                    // _ *list *list
+                pop 2
+                return
+
+            // Walks the list from its end to its beginning.
+            //
+            // INVARIANT: _ *list *element_last_word
+            {invert_elements}:
+                dup 1
+                dup 1
+                eq
+                skiz return
+
+                read_mem {element_size}
+                place {element_size}
+                                    // _ *list *element_last_word' [element]
+
+                {&assert_non_zero}
+                {&BatchInversion::invert_code(&self.element_type)}
+                                    // _ *list *element_last_word' [element⁻¹]
+
+                dup {element_size}
+                addi 1
+                write_mem {element_size}
+                pop 1               // _ *list *element_last_word'
+
+                recurse
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::strategy::Union;
+
+    use super::*;
+    use crate::rust_shadowing_helper_functions::list::list_get;
+    use crate::rust_shadowing_helper_functions::list::list_get_length;
+    use crate::rust_shadowing_helper_functions::list::list_set;
+    use crate::test_prelude::*;
+
+    impl Function for ElementwiseInversion {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let list_pointer = stack.pop().unwrap();
+            let element_size = self.element_type.stack_size();
+            for i in 0..list_get_length(list_pointer, memory) {
+                let words = list_get(list_pointer, i, memory, element_size);
+                let inverse = match self.element_type {
+                    DataType::Bfe => BFieldElement::decode(&words).unwrap().inverse().encode(),
+                    DataType::Xfe => XFieldElement::decode(&words).unwrap().inverse().encode(),
+                    _ => unreachable!(),
+                };
+                list_set(list_pointer, i, inverse, memory);
+            }
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            BatchInversion::new(self.element_type.clone())
+                .pseudorandom_initial_state(seed, bench_case)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            BatchInversion::new(self.element_type.clone()).corner_case_initial_states()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        for element_type in [DataType::Bfe, DataType::Xfe] {
+            ShadowedFunction::new(ElementwiseInversion::new(element_type)).test();
+        }
+    }
+
+    #[proptest(cases = 50)]
+    fn zero_element_crashes_vm(
+        #[strategy(Union::new([DataType::Bfe, DataType::Xfe].map(Just)))] element_type: DataType,
+        #[strategy(1_usize..100)] list_length: usize,
+        #[strategy(0..#list_length)] zero_index: usize,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let initial_state = BatchInversion::set_up_initial_state(
+            &element_type,
+            list_pointer,
+            list_length,
+            Some(zero_index),
+        );
+
+        test_assertion_failure(
+            &ShadowedFunction::new(ElementwiseInversion::new(element_type)),
+            initial_state.into(),
+            &[ElementwiseInversion::ZERO_ELEMENT_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        for element_type in [DataType::Bfe, DataType::Xfe] {
+            ShadowedFunction::new(ElementwiseInversion::new(element_type)).bench();
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use triton_vm::prelude::*;
+
+use crate::list::get::Get;
+use crate::prelude::*;
+
+/// Append all elements of one list to another list. Mimics
+/// [`Vec::extend_from_slice`].
+///
+/// Only supports lists with [statically sized](BFieldCodec::static_length)
+/// elements.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *list *other
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// - the arguments `*list` and `*other` point to properly
+///   [`BFieldCodec`]-encoded lists
+/// - the extended list `*list` does not overlap with the list `*other`
+///
+/// ### Postconditions
+///
+/// - `*list` points to a properly [`BFieldCodec`]-encoded list
+/// - the list `*other` is unchanged
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Extend {
+    element_type: DataType,
+}
+
+impl Extend {
+    /// Any part of the extended list is outside the allocated memory page.
+    /// See the [memory convention][crate::memory] for more details.
+    pub const MEM_PAGE_ACCESS_VIOLATION_ERROR_ID: i128 = 700;
+
+    /// # Panics
+    ///
+    /// Panics if the element has [dynamic length][BFieldCodec::static_length], or
+    /// if the static length is 0.
+    pub fn new(element_type: DataType) -> Self {
+        Get::assert_element_type_is_supported(&element_type);
+
+        Self { element_type }
+    }
+}
+
+impl BasicSnippet for Extend {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let list_type = DataType::List(Box::new(self.element_type.clone()));
+
+        vec![
+            (list_type.clone(), "*list".to_string()),
+            (list_type, "*other".to_string()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        let element_type = self.element_type.label_friendly_name();
+        format!("tasmlib_list_extend___{element_type}")
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let mem_cpy = library.import(Box::new(MemCpy));
+        let mul_with_element_size = match self.element_type.stack_size() {
+            1 => triton_asm!(), // no-op
+            n => triton_asm!(push {n} mul),
+        };
+
+        triton_asm!(
+            // BEFORE: _ *list *other
+            // AFTER:  _
+            {self.entrypoint()}:
+                read_mem 1
+                addi 2              // _ *list other_len *other[0]
+                pick 2
+                read_mem 1
+                addi 1              // _ other_len *other[0] list_len *list
+
+                /* compute new length */
+                dup 1
+                dup 4
+                add                 // _ other_len *other[0] list_len *list new_len
+
+                /* assert access is within one memory page */
+                dup 0
+                {&mul_with_element_size}
+                                    // _ other_len *other[0] list_len *list highest_word_idx
+                split
+                pop 1
+                push 0
+                eq
+                assert error_id {Self::MEM_PAGE_ACCESS_VIOLATION_ERROR_ID}
+                                    // _ other_len *other[0] list_len *list new_len
+
+                /* update list length */
+                pick 1
+                write_mem 1         // _ other_len *other[0] list_len *list[0]
+
+                /* prepare mem_cpy: *write_dest */
+                pick 1
+                {&mul_with_element_size}
+                add                 // _ other_len *other[0] *list[list_len]
+                                    // _ other_len *read_source *write_dest
+
+                /* prepare mem_cpy: num_words */
+                pick 2
+                {&mul_with_element_size}
+                                    // _ *read_source *write_dest num_words
+
+                call {mem_cpy}      // _
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::strategy::Union;
+
+    use super::*;
+    use crate::rust_shadowing_helper_functions::list::insert_random_list;
+    use crate::rust_shadowing_helper_functions::list::list_extend;
+    use crate::rust_shadowing_helper_functions::list::list_insert;
+    use crate::rust_shadowing_helper_functions::list::load_list_with_copy_elements;
+    use crate::test_helpers::test_assertion_failure;
+    use crate::test_helpers::test_rust_equivalence_given_complete_state;
+    use crate::test_prelude::*;
+
+    impl Extend {
+        fn set_up_initial_state(
+            &self,
+            list_length: usize,
+            other_length: usize,
+            list_pointer: BFieldElement,
+            other_pointer: BFieldElement,
+        ) -> FunctionInitialState {
+            let mut memory = HashMap::default();
+            insert_random_list(&self.element_type, list_pointer, list_length, &mut memory);
+            insert_random_list(&self.element_type, other_pointer, other_length, &mut memory);
+
+            let mut stack = self.init_stack_for_isolated_run();
+            stack.push(list_pointer);
+            stack.push(other_pointer);
+
+            FunctionInitialState { stack, memory }
+        }
+    }
+
+    impl Function for Extend {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let other_pointer = stack.pop().unwrap();
+            let list_pointer = stack.pop().unwrap();
+            list_extend(
+                list_pointer,
+                other_pointer,
+                memory,
+                self.element_type.stack_size(),
+            );
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let (list_length, other_length) = match bench_case {
+                Some(BenchmarkCase::CommonCase) => (32, 32),
+                Some(BenchmarkCase::WorstCase) => (64, 256),
+                None => (rng.random_range(0..100), rng.random_range(0..100)),
+            };
+
+            // place lists on different memory pages to avoid any overlap
+            let list_page = rng.random_range(0_u64..1 << 20);
+            let list_pointer = bfe!(list_page << 32);
+            let other_pointer = bfe!((list_page + 1) << 32);
+
+            self.set_up_initial_state(list_length, other_length, list_pointer, other_pointer)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let list_pointer = bfe!(1_u64 << 32);
+            let other_pointer = bfe!(2_u64 << 32);
+
+            [(0, 0), (0, 1), (1, 0), (1, 1)]
+                .map(|(list_len, other_len)| {
+                    self.set_up_initial_state(list_len, other_len, list_pointer, other_pointer)
+                })
+                .to_vec()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        for ty in [
+            DataType::Bool,
+            DataType::Bfe,
+            DataType::U32,
+            DataType::U64,
+            DataType::Xfe,
+            DataType::Digest,
+        ] {
+            ShadowedFunction::new(Extend::new(ty)).test();
+        }
+    }
+
+    #[proptest]
+    fn extending_agrees_with_host_extend(
+        #[strategy(prop::collection::vec(arb(), 0..50))] list: Vec<Digest>,
+        #[strategy(prop::collection::vec(arb(), 0..50))] other: Vec<Digest>,
+    ) {
+        let list_pointer = bfe!(1_u64 << 32);
+        let other_pointer = bfe!(2_u64 << 32);
+
+        let mut memory = HashMap::default();
+        list_insert(list_pointer, list.clone(), &mut memory);
+        list_insert(other_pointer, other.clone(), &mut memory);
+
+        let snippet = Extend::new(DataType::Digest);
+        let mut stack = snippet.init_stack_for_isolated_run();
+        let expected_stack = stack.clone();
+        stack.push(list_pointer);
+        stack.push(other_pointer);
+
+        let final_state = test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &stack,
+            &[],
+            &NonDeterminism::default().with_ram(memory),
+            &None,
+            Some(&expected_stack),
+        );
+
+        let extended = [list, other.clone()].concat();
+        let extended = extended.iter().map(|d| d.values()).collect_vec();
+        let final_list = load_list_with_copy_elements(list_pointer, &final_state.ram);
+        let final_other = load_list_with_copy_elements(other_pointer, &final_state.ram);
+        let other = other.iter().map(|d| d.values()).collect_vec();
+        prop_assert_eq!(extended, final_list);
+        prop_assert_eq!(other, final_other);
+    }
+
+    #[proptest(cases = 100)]
+    fn too_large_lists_crash_vm(
+        #[strategy(Union::new(
+            [DataType::U32, DataType::U64, DataType::Xfe, DataType::Digest].map(Just)
+        ))]
+        element_type: DataType,
+        #[strategy(0_u64..1 << 10)] other_length: u64,
+        #[strategy(arb())] list_pointer: BFieldElement,
+        #[strategy(arb())] other_pointer: BFieldElement,
+    ) {
+        let max_len = (1_u64 << 32).div_ceil(element_type.stack_size() as u64);
+        let list_length = max_len - other_length;
+
+        // spare host machine RAM: pretend every element is all-zeros
+        let mut memory = HashMap::default();
+        memory.insert(list_pointer, bfe!(list_length));
+        memory.insert(other_pointer, bfe!(other_length));
+
+        let snippet = Extend::new(element_type);
+        let mut stack = snippet.init_stack_for_isolated_run();
+        stack.push(list_pointer);
+        stack.push(other_pointer);
+        let initial_state = FunctionInitialState { stack, memory };
+
+        test_assertion_failure(
+            &ShadowedFunction::new(snippet),
+            initial_state.into(),
+            &[Extend::MEM_PAGE_ACCESS_VIOLATION_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(Extend::new(DataType::Digest)).bench();
+    }
+}
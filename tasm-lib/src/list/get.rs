@@ -7,6 +7,8 @@ use crate::prelude::*;
 ///
 /// Only supports lists with [statically sized](BFieldCodec::static_length)
 /// elements.
+/// For lists with dynamically sized elements, see
+/// [`GetDynSized`](crate::list::get_dyn_sized::GetDynSized).
 ///
 /// ### Behavior
 ///
@@ -0,0 +1,391 @@
+use std::marker::PhantomData;
+
+use triton_vm::prelude::*;
+
+use crate::prelude::*;
+
+/// Get a pointer to an element of a list with
+/// [dynamically sized](BFieldCodec::static_length) elements, as well as that
+/// element's size. Performs bounds check.
+///
+/// The size indicators of all preceding elements are walked, making the cost
+/// linear in `index`. To iterate over all elements of a list, get the first
+/// element with this snippet, then use [`NextDynSized`] for the remaining ones.
+/// This keeps the total cost linear in the length of the list.
+///
+/// For lists with statically sized elements, use [`Get`] instead.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *list [index: u32]
+/// AFTER:  _ *element [element_size: u32]
+/// ```
+///
+/// ### Preconditions
+///
+/// - the argument `*list` points to a properly [`BFieldCodec`]-encoded list
+/// - all input arguments are properly [`BFieldCodec`] encoded
+///
+/// ### Postconditions
+///
+/// - the size indicators of all elements up to and including `index` are
+///   smaller than [`TasmObject::MAX_OFFSET`]
+/// - the size indicator of the element at `index` is consistent with its
+///   encoding
+///
+/// [`NextDynSized`]: crate::list::next_dyn_sized::NextDynSized
+/// [`Get`]: crate::list::get::Get
+#[derive(Debug, Clone)]
+pub struct GetDynSized<T: TasmObject> {
+    _phantom_data: PhantomData<T>,
+}
+
+impl<T: TasmObject> Default for GetDynSized<T> {
+    fn default() -> Self {
+        Self {
+            _phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<T: TasmObject> GetDynSized<T> {
+    pub const INDEX_OUT_OF_BOUNDS_ERROR_ID: i128 = 730;
+
+    /// A size indicator is larger than or equal to [`TasmObject::MAX_OFFSET`].
+    pub const SIZE_INDICATOR_TOO_LARGE_ERROR_ID: i128 = 731;
+
+    /// The size indicator of the element does not match its encoding.
+    pub const INVALID_SIZE_INDICATOR_ERROR_ID: i128 = 732;
+
+    /// # Panics
+    ///
+    /// Panics if the element has a [static length][BFieldCodec::static_length].
+    pub(crate) fn assert_element_type_is_supported() {
+        assert!(
+            T::static_length().is_none(),
+            "element must be dynamically sized"
+        );
+    }
+
+    /// Import the loop skipping a given number of elements, and return its label.
+    ///
+    /// ```text
+    /// BEFORE: _ num_elements *element[0]_si
+    /// AFTER:  _ 0 *element[num_elements]_si
+    /// ```
+    pub(crate) fn skip_elements(library: &mut Library) -> String {
+        let label = format!(
+            "tasmlib_list_skip_dyn_sized_elements___{}",
+            T::label_friendly_name()
+        );
+
+        let code = triton_asm!(
+            // INVARIANT: _ remaining *element_si
+            {label}:
+                dup 1
+                push 0
+                eq
+                skiz return
+
+                read_mem 1          // _ remaining element_si (*element_si - 1)
+
+                /* assert the size indicator does not jump too far */
+                push {T::MAX_OFFSET}
+                dup 2
+                lt
+                assert error_id {Self::SIZE_INDICATOR_TOO_LARGE_ERROR_ID}
+
+                addi 2
+                add                 // _ remaining *next_element_si
+                pick 1
+                addi -1
+                place 1             // _ (remaining - 1) *next_element_si
+                recurse
+        );
+
+        library.explicit_import(&label, &code)
+    }
+}
+
+impl<T: TasmObject> BasicSnippet for GetDynSized<T> {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*list".to_string()),
+            (DataType::U32, "index".to_string()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*element".to_string()),
+            (DataType::U32, "element_size".to_string()),
+        ]
+    }
+
+    fn entrypoint(&self) -> String {
+        let element_type = T::label_friendly_name();
+        format!("tasmlib_list_get_dyn_sized_element___{element_type}")
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        Self::assert_element_type_is_supported();
+        let skip_elements = Self::skip_elements(library);
+        let compute_size = T::compute_size_and_assert_valid_size_indicator(library);
+
+        triton_asm!(
+            // BEFORE: _ *list index
+            // AFTER:  _ *element element_size
+            {self.entrypoint()}:
+                /* assert access is in bounds */
+                dup 1
+                read_mem 1
+                pop 1               // _ *list index len
+                dup 1
+                lt                  // _ *list index (index < len)
+                assert error_id {Self::INDEX_OUT_OF_BOUNDS_ERROR_ID}
+
+                pick 1
+                addi 1              // _ index *element[0]_si
+                call {skip_elements}
+                                    // _ 0 *element_si
+                pick 1
+                pop 1
+                read_mem 1
+                addi 2              // _ element_si *element
+                pick 1              // _ *element element_si
+
+                /* assert the size indicator is valid */
+                push {T::MAX_OFFSET}
+                dup 1
+                lt
+                assert error_id {Self::SIZE_INDICATOR_TOO_LARGE_ERROR_ID}
+
+                dup 1
+                {&compute_size}     // _ *element element_si computed_size
+                dup 1
+                eq
+                assert error_id {Self::INVALID_SIZE_INDICATOR_ERROR_ID}
+                                    // _ *element element_size
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use arbitrary::Arbitrary;
+    use arbitrary::Unstructured;
+
+    use super::*;
+    use crate::list::get::Get;
+    use crate::rust_shadowing_helper_functions::list::list_dyn_sized_elem_pointer;
+    use crate::structure::tasm_object::decode_from_memory_with_size;
+    use crate::test_prelude::*;
+    use crate::U32_TO_USIZE_ERR;
+
+    /// A list of the given length with arbitrary elements.
+    pub(crate) fn random_dyn_sized_list<T>(list_length: usize, rng: &mut impl Rng) -> Vec<T>
+    where
+        T: for<'a> Arbitrary<'a>,
+    {
+        let mut randomness = vec![0u8; 1 << 16];
+        rng.fill(randomness.as_mut_slice());
+        let mut unstructured = Unstructured::new(&randomness);
+
+        (0..list_length)
+            .map(|_| T::arbitrary(&mut unstructured).unwrap())
+            .collect()
+    }
+
+    /// Mirror the validation performed by the snippets handling lists with
+    /// dynamically sized elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the element's size is too large or inconsistent with the
+    /// element's encoding.
+    pub(crate) fn assert_valid_element<T: TasmObject>(
+        element_pointer: BFieldElement,
+        element_size: usize,
+        memory: &HashMap<BFieldElement, BFieldElement>,
+    ) {
+        assert!(element_size < T::MAX_OFFSET.try_into().unwrap());
+        decode_from_memory_with_size::<T>(memory, element_pointer, element_size).unwrap();
+    }
+
+    impl<T: TasmObject + for<'a> Arbitrary<'a>> GetDynSized<T> {
+        fn set_up_initial_state(
+            &self,
+            list: Vec<T>,
+            index: usize,
+            list_pointer: BFieldElement,
+        ) -> AccessorInitialState {
+            let mut memory = HashMap::default();
+            encode_to_memory(&mut memory, list_pointer, &list);
+
+            let mut stack = self.init_stack_for_isolated_run();
+            stack.push(list_pointer);
+            stack.push(bfe!(index));
+
+            AccessorInitialState { stack, memory }
+        }
+    }
+
+    impl<T: TasmObject + for<'a> Arbitrary<'a>> Accessor for GetDynSized<T> {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let index = pop_encodable::<u32>(stack)
+                .try_into()
+                .expect(U32_TO_USIZE_ERR);
+            let list_pointer = stack.pop().unwrap();
+
+            let mut size_indicator_pointer = list_pointer + bfe!(1);
+            for _ in 0..index {
+                let size_indicator = memory[&size_indicator_pointer];
+                assert!(size_indicator.value() < u64::from(T::MAX_OFFSET));
+                size_indicator_pointer += size_indicator + bfe!(1);
+            }
+
+            let (element_size, element_pointer) =
+                list_dyn_sized_elem_pointer(list_pointer, index, memory);
+            assert_valid_element::<T>(element_pointer, element_size, memory);
+
+            stack.push(element_pointer);
+            stack.push(bfe!(element_size));
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> AccessorInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let (list_length, index, list_pointer) = Get::random_len_idx_ptr(bench_case, &mut rng);
+            let list = random_dyn_sized_list::<T>(list_length, &mut rng);
+
+            self.set_up_initial_state(list, index, list_pointer)
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedAccessor::new(GetDynSized::<Vec<BFieldElement>>::default()).test();
+        ShadowedAccessor::new(GetDynSized::<Vec<XFieldElement>>::default()).test();
+        ShadowedAccessor::new(GetDynSized::<Vec<Vec<Digest>>>::default()).test();
+    }
+
+    #[proptest(cases = 20)]
+    fn getting_agrees_with_host_indexing(
+        #[strategy(prop::collection::vec(arb(), 1..20))] list: Vec<Vec<XFieldElement>>,
+        #[strategy(0..#list.len())] index: usize,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let snippet = GetDynSized::<Vec<XFieldElement>>::default();
+        let initial_state = snippet.set_up_initial_state(list.clone(), index, list_pointer);
+
+        // skip the list's length indicator, and every element's size indicator
+        let preceding_words = list[..index]
+            .iter()
+            .map(|element| element.encode().len() + 1)
+            .sum::<usize>();
+        let element_pointer = list_pointer + bfe!(preceding_words + 2);
+        let element_size = list[index].encode().len();
+
+        let mut expected_stack = snippet.init_stack_for_isolated_run();
+        expected_stack.extend([element_pointer, bfe!(element_size)]);
+        test_rust_equivalence_given_complete_state(
+            &ShadowedAccessor::new(snippet),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory.clone()),
+            &None,
+            Some(&expected_stack),
+        );
+
+        let element = decode_from_memory_with_size::<Vec<XFieldElement>>(
+            &initial_state.memory,
+            element_pointer,
+            element_size,
+        )
+        .unwrap();
+        prop_assert_eq!(&list[index], &*element);
+    }
+
+    #[proptest]
+    fn out_of_bounds_access_crashes_vm(
+        #[strategy(0_usize..20)] list_length: usize,
+        #[strategy(#list_length..1 << 32)] index: usize,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let snippet = GetDynSized::<Vec<BFieldElement>>::default();
+        let list = vec![vec![]; list_length];
+        let initial_state = snippet.set_up_initial_state(list, index, list_pointer);
+
+        test_assertion_failure(
+            &ShadowedAccessor::new(snippet),
+            initial_state.into(),
+            &[GetDynSized::<Vec<BFieldElement>>::INDEX_OUT_OF_BOUNDS_ERROR_ID],
+        );
+    }
+
+    #[proptest]
+    fn too_large_size_indicator_crashes_vm(
+        #[strategy(prop::collection::vec(arb(), 1..20))] list: Vec<Vec<BFieldElement>>,
+        #[strategy(0..#list.len())] index: usize,
+        #[strategy(0..=#index)] messed_up_index: usize,
+        #[strategy(u64::from(Vec::<BFieldElement>::MAX_OFFSET)..1 << 32)] size_indicator: u64,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let snippet = GetDynSized::<Vec<BFieldElement>>::default();
+        let mut initial_state = snippet.set_up_initial_state(list, index, list_pointer);
+        let (_, element_pointer) =
+            list_dyn_sized_elem_pointer(list_pointer, messed_up_index, &initial_state.memory);
+        initial_state
+            .memory
+            .insert(element_pointer - bfe!(1), bfe!(size_indicator));
+
+        test_assertion_failure(
+            &ShadowedAccessor::new(snippet),
+            initial_state.into(),
+            &[GetDynSized::<Vec<BFieldElement>>::SIZE_INDICATOR_TOO_LARGE_ERROR_ID],
+        );
+    }
+
+    #[proptest]
+    fn inconsistent_size_indicator_crashes_vm(
+        #[strategy(prop::collection::vec(arb(), 1..20))] list: Vec<Vec<Digest>>,
+        #[strategy(0..#list.len())] index: usize,
+        #[strategy(1_u64..100)] size_offset: u64,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let snippet = GetDynSized::<Vec<Digest>>::default();
+        let mut initial_state = snippet.set_up_initial_state(list, index, list_pointer);
+        let (element_size, element_pointer) =
+            list_dyn_sized_elem_pointer(list_pointer, index, &initial_state.memory);
+        let size_indicator = bfe!(element_size as u64 + size_offset);
+        initial_state
+            .memory
+            .insert(element_pointer - bfe!(1), size_indicator);
+
+        test_assertion_failure(
+            &ShadowedAccessor::new(snippet),
+            initial_state.into(),
+            &[GetDynSized::<Vec<Digest>>::INVALID_SIZE_INDICATOR_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedAccessor::new(GetDynSized::<Vec<Digest>>::default()).bench();
+    }
+}
@@ -1,5 +1,11 @@
 pub mod all;
+pub mod any;
+pub mod enumerated_map;
 pub mod filter;
+pub mod find_first;
+pub mod flat_map;
+pub mod fold;
 pub mod inner_function;
 pub mod map;
+pub mod position;
 pub mod zip;
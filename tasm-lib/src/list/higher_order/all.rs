@@ -1,6 +1,10 @@
 use itertools::Itertools;
+use strum::EnumCount;
+use triton_vm::isa::op_stack::OpStackElement;
 use triton_vm::prelude::*;
 
+use super::inner_function::copy_environment;
+use super::inner_function::drop_environment;
 use super::inner_function::InnerFunction;
 use crate::list::get::Get;
 use crate::list::length::Length;
@@ -8,12 +12,26 @@ use crate::prelude::*;
 
 /// Runs a predicate over all elements of a list and returns true only if all elements satisfy the
 /// predicate.
+///
+/// The predicate's [environment](InnerFunction::environment), if any, is taken as the first input
+/// and passed to every invocation of the predicate.
 pub struct All {
     pub f: InnerFunction,
 }
 
 impl All {
+    /// The number of registers required internally.
+    pub const NUM_INTERNAL_REGISTERS: usize = 3;
+
+    /// # Panics
+    ///
+    /// - if the [environment](InnerFunction::environment) is too large to be
+    ///   copied past the internal registers
     pub fn new(f: InnerFunction) -> Self {
+        // need instruction `dup {Self::NUM_INTERNAL_REGISTERS + env_size - 1}`
+        let env_size = f.environment_size();
+        assert!(env_size == 0 || Self::NUM_INTERNAL_REGISTERS + env_size <= OpStackElement::COUNT);
+
         Self { f }
     }
 }
@@ -22,7 +40,9 @@ impl BasicSnippet for All {
     fn inputs(&self) -> Vec<(DataType, String)> {
         let element_type = self.f.domain();
         let list_type = DataType::List(Box::new(element_type));
-        vec![(list_type, "*input_list".to_string())]
+        let input_list = (list_type, "*input_list".to_string());
+
+        [self.f.environment(), vec![input_list]].concat()
     }
 
     fn outputs(&self) -> Vec<(DataType, String)> {
@@ -38,6 +58,7 @@ impl BasicSnippet for All {
         let output_type = self.f.range();
         assert_eq!(output_type, DataType::Bool);
 
+        let env_size = self.f.environment_size();
         let get_length = library.import(Box::new(Length));
         let list_get = library.import(Box::new(Get::new(input_type)));
 
@@ -79,6 +100,7 @@ impl BasicSnippet for All {
                         // _ res input_list 0
 
                 pop 2   // _ res
+                {&drop_environment(env_size, 1)}
                 return
 
             // INVARIANT: _ res input_list index
@@ -96,10 +118,11 @@ impl BasicSnippet for All {
                 // body
 
                 // read
-                dup 1 dup 1
-                        // _ res input_list index input_list index
+                {&copy_environment(env_size, Self::NUM_INTERNAL_REGISTERS)}
+                dup {1 + env_size} dup {1 + env_size}
+                        // _ res input_list index [env] input_list index
                 call {list_get}
-                        // _ res input_list index [input_elements]
+                        // _ res input_list index [env] [input_elements]
 
                 // compute predicate
                 call {inner_function_name}
@@ -119,7 +142,7 @@ impl BasicSnippet for All {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use num::One;
     use num::Zero;
 
@@ -181,6 +204,7 @@ mod tests {
         ) {
             let input_type = self.f.domain();
             let list_pointer = stack.pop().unwrap();
+            let environment = stack.split_off(stack.len() - self.f.environment_size());
 
             // forall elements, read + map + maybe copy
             let list_length =
@@ -188,6 +212,7 @@ mod tests {
             let mut satisfied = true;
             for i in 0..list_length {
                 let input_item = list_get(list_pointer, i, memory, input_type.stack_size());
+                stack.extend_from_slice(&environment);
                 for bfe in input_item.into_iter().rev() {
                     stack.push(bfe);
                 }
@@ -206,7 +231,7 @@ mod tests {
             seed: [u8; 32],
             bench_case: Option<BenchmarkCase>,
         ) -> FunctionInitialState {
-            let (stack, memory) = match bench_case {
+            let (mut stack, memory) = match bench_case {
                 Some(BenchmarkCase::CommonCase) => {
                     let list_pointer = BFieldElement::new(5);
                     let list_length = 10;
@@ -231,6 +256,12 @@ mod tests {
                 }
             };
 
+            let environment = self
+                .f
+                .seeded_random_environment(&mut StdRng::from_seed(seed));
+            let list_pointer_position = stack.len() - 1;
+            stack.splice(list_pointer_position..list_pointer_position, environment);
+
             FunctionInitialState { stack, memory }
         }
     }
@@ -352,9 +383,31 @@ mod tests {
         ShadowedFunction::new(snippet).test();
     }
 
+    #[test]
+    fn test_with_raw_function_lsb_of_sum_with_environment() {
+        let raw_code = RawCode::new(
+            triton_asm!(
+                lsb_of_sum:
+                    add     // _ (k + x)
+                    split   // _ hi lo
+                    push 2  // _ hi lo 2
+                    pick 1  // _ hi 2 lo
+                    div_mod // _ hi q r
+                    place 2 // _ r hi q
+                    pop 2   // _ r
+                    return
+            ),
+            DataType::Bfe,
+            DataType::Bool,
+        )
+        .with_environment(vec![(DataType::Bfe, "k".to_string())]);
+        let snippet = All::new(InnerFunction::RawCode(raw_code));
+        ShadowedFunction::new(snippet).test();
+    }
+
     /// Only used for tests. Please don't export this.
     #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
-    pub(super) struct TestHashXFieldElementLsb;
+    pub(crate) struct TestHashXFieldElementLsb;
 
     impl BasicSnippet for TestHashXFieldElementLsb {
         fn inputs(&self) -> Vec<(DataType, String)> {
@@ -414,6 +467,21 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    #[should_panic]
+    fn too_large_environment_is_rejected() {
+        let rawcode = RawCode::new(
+            triton_asm!(always_true: pop 1 push 1 return),
+            DataType::Bfe,
+            DataType::Bool,
+        )
+        .with_environment(vec![(
+            DataType::Tuple(vec![DataType::Digest; 3]),
+            "env".to_string(),
+        )]);
+        All::new(InnerFunction::RawCode(rawcode));
+    }
 }
 
 #[cfg(test)]
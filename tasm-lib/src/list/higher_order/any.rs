@@ -0,0 +1,176 @@
+use triton_vm::prelude::*;
+
+use super::inner_function::drop_environment;
+use super::inner_function::InnerFunction;
+use super::position::assert_predicate_on_static_length_elements;
+use super::position::first_match_loop;
+use super::position::pointer_to_end_of_list;
+use crate::prelude::*;
+
+/// Runs a predicate over the elements of a list and returns true if any element
+/// satisfies the predicate.
+///
+/// Stops at the first element that satisfies the predicate; later elements are
+/// neither read nor passed to the predicate. The list's elements must have a
+/// [statically known][len] length. The predicate's [environment][env], if any,
+/// is passed to every invocation of the predicate.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ [environment] *list
+/// AFTER:  _ any_true
+/// ```
+///
+/// [len]: BFieldCodec::static_length
+/// [env]: InnerFunction::environment
+pub struct Any {
+    pub f: InnerFunction,
+}
+
+impl Any {
+    pub fn new(f: InnerFunction) -> Self {
+        assert_predicate_on_static_length_elements(&f);
+        Self { f }
+    }
+}
+
+impl BasicSnippet for Any {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let list_type = DataType::List(Box::new(self.f.domain()));
+        let list = (list_type, "*list".to_string());
+
+        [self.f.environment(), vec![list]].concat()
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::Bool, "any_true".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!("tasmlib_list_higher_order_u32_any_{}", self.f.entrypoint())
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let element_size = self.f.domain().stack_size();
+        let entrypoint = self.entrypoint();
+        let main_loop = format!("{entrypoint}_loop");
+
+        triton_asm!(
+            // BEFORE: _ [env] *list
+            // AFTER:  _ any_true
+            {entrypoint}:
+                dup 0
+                {&pointer_to_end_of_list(element_size)}
+                place 1     // _ *end *list
+
+                call {main_loop}
+                            // _ *end *p
+
+                eq
+                push 0
+                eq          // _ any_true
+                {&drop_environment(self.f.environment_size(), 1)}
+                return
+
+            {&first_match_loop(&self.f, &main_loop, 2, library)}
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::empty_stack;
+    use crate::list::higher_order::all::tests::TestHashXFieldElementLsb;
+    use crate::list::higher_order::position::tests::*;
+    use crate::test_helpers::test_rust_equivalence_given_complete_state;
+    use crate::test_prelude::*;
+
+    impl Function for Any {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let list_pointer = stack.pop().unwrap();
+            let position = first_match(&self.f, list_pointer, stack, memory);
+            stack.push(bfe!(position.is_some() as u64));
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            initial_state(&self.f, seed, bench_case)
+        }
+    }
+
+    #[test]
+    fn rust_shadow_with_basic_snippet() {
+        let f = InnerFunction::BasicSnippet(Box::new(TestHashXFieldElementLsb));
+        ShadowedFunction::new(Any::new(f)).test();
+    }
+
+    #[test]
+    fn rust_shadow_with_raw_code() {
+        ShadowedFunction::new(Any::new(lt_2_pow_28())).test();
+        ShadowedFunction::new(Any::new(never_on_xfe())).test();
+        ShadowedFunction::new(Any::new(always_on_digest())).test();
+    }
+
+    #[test]
+    fn rust_shadow_with_environment() {
+        ShadowedFunction::new(Any::new(eq_to_environment())).test();
+    }
+
+    #[test]
+    fn elements_after_first_match_are_not_inspected() {
+        let snippet = Any::new(is_zero_or_crash());
+        let list = vec![bfe!(0), bfe!(1_u64 << 40)];
+        let initial_state = initial_state_from_encoded_list(vec![], bfe!(42), list.encode());
+
+        let expected_stack = [empty_stack(), bfe_vec![1]].concat();
+        test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            &None,
+            Some(&expected_stack),
+        );
+    }
+
+    #[proptest(cases = 20)]
+    fn any_agrees_with_host_any(
+        #[strategy(prop::collection::vec(arb(), 0..50))] list: Vec<u32>,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let snippet = Any::new(lt_2_pow_28());
+        let initial_state = initial_state_from_encoded_list(vec![], list_pointer, list.encode());
+
+        let any_true = list.iter().any(|&x| x < 1 << 28);
+        let expected_stack = [empty_stack(), bfe_vec![any_true as u64]].concat();
+        test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            &None,
+            Some(&expected_stack),
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::list::higher_order::position::tests::never_on_xfe;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(Any::new(never_on_xfe())).bench();
+    }
+}
@@ -0,0 +1,463 @@
+use strum::EnumCount;
+use triton_vm::isa::op_stack::OpStackElement;
+use triton_vm::prelude::*;
+
+use crate::list::higher_order::inner_function::copy_environment;
+use crate::list::higher_order::inner_function::drop_environment;
+use crate::list::higher_order::inner_function::InnerFunction;
+use crate::list::new::New;
+use crate::prelude::*;
+
+const INNER_FN_INCORRECT_DOMAIN: &str = "Inner function in `enumerated_map` must take a tuple \
+                                         `(index: u32, elem)` as input.";
+
+/// Applies a given function `f` to every element of a list together with that
+/// element's index, and collects the new elements into a new list.
+///
+/// The function `f` must take the tuple `(index, elem)` as input, where `index`
+/// is a [`u32`][u32]. Both the list's elements and the elements produced by
+/// `f` must be of a type for which the encoded length is
+/// [statically known][len].
+///
+/// Like for [`ChainMap`][map], the stack layout is independent of the list's
+/// length. The function's [environment][env], if any, is taken as the first
+/// input and passed to every invocation of `f`. Upon starting execution of
+/// `f`, the stack layout is:
+///
+/// ```txt
+/// // _ <accessible> [env] [_; EnumeratedMap::NUM_INTERNAL_REGISTERS] [env] index [elem; len]
+/// ```
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ [environment] *list
+/// AFTER:  _ *output_list
+/// ```
+///
+/// [u32]: DataType::U32
+/// [len]: BFieldCodec::static_length
+/// [map]: crate::list::higher_order::map::ChainMap
+/// [env]: InnerFunction::environment
+pub struct EnumeratedMap {
+    f: InnerFunction,
+}
+
+impl EnumeratedMap {
+    /// The number of registers required internally. See [`EnumeratedMap`] for
+    /// additional details.
+    pub const NUM_INTERNAL_REGISTERS: usize = 4;
+
+    /// # Panics
+    ///
+    /// - if the inner function's domain is not of shape `(u32, elem)`
+    /// - if the element type or the output type does not have a
+    ///   [static length][len]
+    /// - if the element type has length 0
+    /// - if the output type takes up [`OpStackElement::COUNT`]` - 4` or more
+    ///   words
+    /// - if the [environment][env] is too large to be copied past the internal
+    ///   registers
+    ///
+    /// [len]: BFieldCodec::static_length
+    /// [env]: InnerFunction::environment
+    pub fn new(f: InnerFunction) -> Self {
+        let map = Self { f };
+        let element_len = map
+            .element_type()
+            .static_length()
+            .expect("element type's encoding length must be static");
+        assert_ne!(0, element_len, "element type must not be empty");
+        let output_len = map
+            .f
+            .range()
+            .static_length()
+            .expect("output type's encoding length must be static");
+
+        // need instruction `dup {output_type.stack_size() + 4}`
+        assert!(output_len + 4 < OpStackElement::COUNT);
+
+        // need instruction `dup {Self::NUM_INTERNAL_REGISTERS + env_size - 1}`
+        let env_size = map.f.environment_size();
+        assert!(Self::NUM_INTERNAL_REGISTERS + env_size <= OpStackElement::COUNT);
+
+        map
+    }
+
+    fn element_type(&self) -> DataType {
+        let DataType::Tuple(tuple) = self.f.domain() else {
+            panic!("{INNER_FN_INCORRECT_DOMAIN}");
+        };
+        let [DataType::U32, ref element_type] = tuple[..] else {
+            panic!("{INNER_FN_INCORRECT_DOMAIN}");
+        };
+
+        element_type.clone()
+    }
+}
+
+impl BasicSnippet for EnumeratedMap {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let list_type = DataType::List(Box::new(self.element_type()));
+        let input_list = (list_type, "*input_list".to_string());
+
+        [self.f.environment(), vec![input_list]].concat()
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        let list_type = DataType::List(Box::new(self.f.range()));
+        vec![(list_type, "*output_list".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!(
+            "tasmlib_list_higher_order_u32_enumerated_map_{}",
+            self.f.entrypoint()
+        )
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let element_type = self.element_type();
+        let elem_size = element_type.stack_size();
+        let output_type = self.f.range();
+        let output_size = output_type.stack_size();
+        let env_size = self.f.environment_size();
+
+        let new_list = library.import(Box::new(New));
+        let entrypoint = self.entrypoint();
+        let main_loop = format!("{entrypoint}_loop");
+
+        let (exec_or_call, fn_body) = match &self.f {
+            InnerFunction::RawCode(code) => {
+                // Inlining saves two clock cycles per iteration. If the function cannot be
+                // inlined, it needs to be appended to the function body.
+                match code.inlined_body() {
+                    Some(body) => (body, vec![]),
+                    None => (triton_asm!(call {code.entrypoint()}), code.function.clone()),
+                }
+            }
+            InnerFunction::BasicSnippet(snippet) => {
+                let labelled_instructions = snippet.annotated_code(library);
+                let label = library.explicit_import(&snippet.entrypoint(), &labelled_instructions);
+                (triton_asm!(call { label }), vec![])
+            }
+            InnerFunction::NoFunctionBody(lnat) => (triton_asm!(call { lnat.label_name }), vec![]),
+        };
+
+        let mul_by = |n| match n {
+            1 => triton_asm!(),
+            n => triton_asm!(push {n} mul),
+        };
+
+        triton_asm!(
+            // BEFORE: _ [env] *in_list
+            // AFTER:  _ *out_list
+            {entrypoint}:
+                call {new_list}
+                            // _ [env] *in_list *out_list
+
+                pick 1
+                read_mem 1
+                addi 1      // _ [env] *out_list len *in_list
+
+                /* set out_list's length */
+                dup 1
+                dup 3
+                write_mem 1
+                pop 1       // _ [env] *out_list len *in_list
+
+                /* prepare in_list pointers for main loop */
+                pick 1
+                {&mul_by(elem_size)}
+                dup 1
+                add
+                place 1     // _ [env] *out_list *in_list_last_elem_last_word *in_list
+
+                push 0      // _ [env] *out_list *in_list_last_elem_last_word *in_list 0
+                call {main_loop}
+                            // _ [env] *out_list *in_list_last_elem_last_word *in_list_last_elem_last_word len
+
+                pop 3       // _ [env] *out_list
+                {&drop_environment(env_size, 1)}
+                return
+
+            // INVARIANT: _ [env] *out_list *in_list_last_elem_last_word *prev_elem_last_word i
+            {main_loop}:
+                /* maybe return */
+                dup 2
+                dup 2
+                eq
+                skiz return
+
+                /* read */
+                {&copy_environment(env_size, Self::NUM_INTERNAL_REGISTERS)}
+                dup {env_size}
+                pick {env_size + 2}
+                addi {elem_size}
+                dup 0
+                place {env_size + 3}
+                            // _ [env] *out_list *in_list_last_elem_last_word *elem_last_word i [env] i *elem_last_word
+                {&element_type.read_value_from_memory_pop_pointer()}
+                            // _ [env] *out_list *in_list_last_elem_last_word *elem_last_word i [env] i [elem]
+
+                /* map */
+                {&exec_or_call}
+                            // _ [env] *out_list *in_list_last_elem_last_word *elem_last_word i [out_elem]
+
+                /* write */
+                dup {output_size}
+                {&mul_by(output_size)}
+                dup {output_size + 4}
+                add
+                addi 1      // _ [env] *out_list *in_list_last_elem_last_word *elem_last_word i [out_elem] *out_elem
+                {&output_type.write_value_to_memory_pop_pointer()}
+                            // _ [env] *out_list *in_list_last_elem_last_word *elem_last_word i
+
+                addi 1
+                recurse
+
+            {&fn_body}
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::empty_stack;
+    use crate::list::higher_order::inner_function::RawCode;
+    use crate::rust_shadowing_helper_functions::dyn_malloc::dynamic_allocator;
+    use crate::rust_shadowing_helper_functions::list::list_get;
+    use crate::rust_shadowing_helper_functions::list::list_get_length;
+    use crate::rust_shadowing_helper_functions::list::list_push;
+    use crate::test_helpers::test_rust_equivalence_given_complete_state;
+    use crate::test_prelude::*;
+
+    impl EnumeratedMap {
+        fn init_state(
+            &self,
+            environment: Vec<BFieldElement>,
+            list: Vec<BFieldElement>,
+        ) -> FunctionInitialState {
+            let mut stack = self.init_stack_for_isolated_run();
+            let mut memory = HashMap::default();
+
+            let list_pointer = dynamic_allocator(&mut memory);
+            let indexed_list = list
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| (list_pointer + bfe!(i), v));
+            memory.extend(indexed_list);
+
+            stack.extend(environment);
+            stack.push(list_pointer);
+
+            FunctionInitialState { stack, memory }
+        }
+    }
+
+    impl Function for EnumeratedMap {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let element_size = self.element_type().stack_size();
+            let output_size = self.f.range().stack_size();
+
+            let list_pointer = stack.pop().unwrap();
+            let environment = stack.split_off(stack.len() - self.f.environment_size());
+
+            New.rust_shadow(stack, memory);
+            let output_list_pointer = stack.pop().unwrap();
+
+            // the inner function _must not_ rely on these elements
+            let buffer = (0..Self::NUM_INTERNAL_REGISTERS).map(|_| rand::random::<BFieldElement>());
+            stack.extend(buffer);
+
+            for i in 0..list_get_length(list_pointer, memory) {
+                let element = list_get(list_pointer, i, memory, element_size);
+                stack.extend_from_slice(&environment);
+                stack.push(bfe!(i));
+                stack.extend(element.into_iter().rev());
+                self.f.apply(stack, memory);
+                let mut output_element = stack.split_off(stack.len() - output_size);
+                output_element.reverse();
+                list_push(output_list_pointer, output_element, memory);
+            }
+
+            for _ in 0..Self::NUM_INTERNAL_REGISTERS {
+                stack.pop();
+            }
+            stack.push(output_list_pointer);
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let list_length = match bench_case {
+                Some(BenchmarkCase::CommonCase) => 10,
+                Some(BenchmarkCase::WorstCase) => 100,
+                None => rng.random_range(0..100),
+            };
+            let list = self.element_type().random_list(&mut rng, list_length);
+            let environment = self.f.seeded_random_environment(&mut rng);
+
+            self.init_state(environment, list)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let mut rng = StdRng::seed_from_u64(0x_e7a1);
+
+            (0..=2)
+                .map(|list_length| {
+                    let list = self.element_type().random_list(&mut rng, list_length);
+                    let environment = self.f.seeded_random_environment(&mut rng);
+                    self.init_state(environment, list)
+                })
+                .collect()
+        }
+    }
+
+    fn u32_and(element_type: DataType) -> DataType {
+        DataType::Tuple(vec![DataType::U32, element_type])
+    }
+
+    /// Hashes an element together with its index: `(i, elem) -> hash(elem, i)`.
+    #[derive(Debug, Clone)]
+    pub(crate) struct TestHashDigestAtIndex;
+
+    impl BasicSnippet for TestHashDigestAtIndex {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            vec![(u32_and(DataType::Digest), "indexed_digest".to_string())]
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            vec![(DataType::Digest, "digest".to_string())]
+        }
+
+        fn entrypoint(&self) -> String {
+            "test_hash_digest_at_index".to_string()
+        }
+
+        fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+            triton_asm!(
+                // BEFORE: _ i [elem: Digest]
+                // AFTER:  _ [digest: Digest]
+                {self.entrypoint()}:
+                    pick 5
+                    push 0
+                    push 0
+                    push 0
+                    push 0
+                    hash
+                    return
+            )
+        }
+    }
+
+    #[test]
+    fn rust_shadow_with_basic_snippet() {
+        let f = InnerFunction::BasicSnippet(Box::new(TestHashDigestAtIndex));
+        ShadowedFunction::new(EnumeratedMap::new(f)).test();
+    }
+
+    #[test]
+    fn rust_shadow_with_raw_code() {
+        let index_only = InnerFunction::RawCode(RawCode::new(
+            triton_asm!(index_only: pop 3 return),
+            u32_and(DataType::Xfe),
+            DataType::U32,
+        ));
+        ShadowedFunction::new(EnumeratedMap::new(index_only)).test();
+
+        let add_index = InnerFunction::RawCode(RawCode::new(
+            triton_asm!(add_index: add return),
+            u32_and(DataType::Bfe),
+            DataType::Bfe,
+        ));
+        ShadowedFunction::new(EnumeratedMap::new(add_index)).test();
+
+        let to_unit = InnerFunction::RawCode(RawCode::new(
+            triton_asm!(to_unit: pop 3 return),
+            u32_and(DataType::U64),
+            DataType::Tuple(vec![]),
+        ));
+        ShadowedFunction::new(EnumeratedMap::new(to_unit)).test();
+    }
+
+    #[test]
+    fn rust_shadow_with_environment() {
+        let scaled_index_plus_elem = RawCode::new(
+            triton_asm!(scaled_index_plus_elem: pick 1 pick 2 mul add return),
+            u32_and(DataType::Bfe),
+            DataType::Bfe,
+        )
+        .with_environment(vec![(DataType::Bfe, "scalar".to_string())]);
+        let f = InnerFunction::RawCode(scaled_index_plus_elem);
+        ShadowedFunction::new(EnumeratedMap::new(f)).test();
+    }
+
+    #[test]
+    #[should_panic]
+    fn inner_function_without_index_is_rejected() {
+        let f = InnerFunction::RawCode(RawCode::new(
+            triton_asm!(identity: return),
+            DataType::Bfe,
+            DataType::Bfe,
+        ));
+        EnumeratedMap::new(f);
+    }
+
+    #[proptest(cases = 20)]
+    fn enumerated_map_agrees_with_host_enumerate_map(
+        #[strategy(prop::collection::vec(arb(), 0..50))] list: Vec<BFieldElement>,
+    ) {
+        let f = InnerFunction::RawCode(RawCode::new(
+            triton_asm!(add_index: add return),
+            u32_and(DataType::Bfe),
+            DataType::Bfe,
+        ));
+        let snippet = EnumeratedMap::new(f);
+        let initial_state = snippet.init_state(vec![], list.encode());
+
+        let mut memory = initial_state.memory.clone();
+        let output_list_pointer = dynamic_allocator(&mut memory);
+        let expected_stack = [empty_stack(), vec![output_list_pointer]].concat();
+        let mapped = list
+            .iter()
+            .enumerate()
+            .map(|(i, &elem)| elem + bfe!(i))
+            .collect_vec();
+
+        let mut final_state = test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            &None,
+            Some(&expected_stack),
+        );
+        let ram = &mut final_state.ram;
+        let output_list = (0..mapped.len())
+            .map(|i| list_get(output_list_pointer, i, ram, 1)[0])
+            .collect_vec();
+        prop_assert_eq!(mapped, output_list);
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::tests::TestHashDigestAtIndex;
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        let f = InnerFunction::BasicSnippet(Box::new(TestHashDigestAtIndex));
+        ShadowedFunction::new(EnumeratedMap::new(f)).bench();
+    }
+}
@@ -1,6 +1,10 @@
 use itertools::Itertools;
+use strum::EnumCount;
+use triton_vm::isa::op_stack::OpStackElement;
 use triton_vm::prelude::*;
 
+use super::inner_function::copy_environment;
+use super::inner_function::drop_environment;
 use super::inner_function::InnerFunction;
 use crate::list::get::Get;
 use crate::list::length::Length;
@@ -13,15 +17,37 @@ use crate::prelude::*;
 /// Filters a given list for elements that satisfy a predicate. A new
 /// list is created, containing only those elements that satisfy the
 /// predicate. The predicate must be given as an [`InnerFunction`].
+///
+/// The predicate's [environment](InnerFunction::environment), if any, is taken
+/// as the first input and passed to every invocation of the predicate.
 pub struct Filter {
     pub f: InnerFunction,
 }
 
+impl Filter {
+    /// The number of registers required internally.
+    pub const NUM_INTERNAL_REGISTERS: usize = 5;
+
+    /// # Panics
+    ///
+    /// - if the [environment](InnerFunction::environment) is too large to be
+    ///   copied past the internal registers
+    pub fn new(f: InnerFunction) -> Self {
+        // need instruction `dup {Self::NUM_INTERNAL_REGISTERS + env_size - 1}`
+        let env_size = f.environment_size();
+        assert!(env_size == 0 || Self::NUM_INTERNAL_REGISTERS + env_size <= OpStackElement::COUNT);
+
+        Self { f }
+    }
+}
+
 impl BasicSnippet for Filter {
     fn inputs(&self) -> Vec<(DataType, String)> {
         let element_type = self.f.domain();
         let list_type = DataType::List(Box::new(element_type));
-        vec![(list_type, "*input_list".to_string())]
+        let input_list = (list_type, "*input_list".to_string());
+
+        [self.f.environment(), vec![input_list]].concat()
     }
 
     fn outputs(&self) -> Vec<(DataType, String)> {
@@ -48,6 +74,7 @@ impl BasicSnippet for Filter {
         let new_list = library.import(Box::new(New));
         let set_length = library.import(Box::new(SetLength));
         let element_size = self.f.domain().stack_size();
+        let env_size = self.f.environment_size();
 
         let inner_function_name = match &self.f {
             InnerFunction::RawCode(rc) => rc.entrypoint(),
@@ -90,6 +117,7 @@ impl BasicSnippet for Filter {
 
                 swap 1              // _ *output_list *input_list
                 pop 1               // _ *output_list
+                {&drop_environment(env_size, 1)}
                 return
 
             // INVARIANT:  _ *input_list *output_list input_len input_index output_index
@@ -104,9 +132,10 @@ impl BasicSnippet for Filter {
                 // body
 
                 // read
-                dup 4 // _ *input_list *output_list input_len input_index output_index *input_list
-                dup 2 // _ *input_list *output_list input_len input_index output_index *input_list input_index
-                call {list_get} // _ *input_list *output_list input_len input_index output_index [input_elements]
+                {&copy_environment(env_size, Self::NUM_INTERNAL_REGISTERS)}
+                dup {4 + env_size} // _ *input_list *output_list input_len input_index output_index [env] *input_list
+                dup {2 + env_size} // _ *input_list *output_list input_len input_index output_index [env] *input_list input_index
+                call {list_get} // _ *input_list *output_list input_len input_index output_index [env] [input_elements]
 
                 // map
                 call {inner_function_name} // _ *input_list *output_list input_len input_index output_index b
@@ -172,6 +201,7 @@ mod tests {
             let safety_offset = LIST_METADATA_SIZE;
 
             let list_pointer = stack.pop().unwrap();
+            let environment = stack.split_off(stack.len() - self.f.environment_size());
 
             // get list length
             let len = rust_shadowing_helper_functions::list::list_get_length(list_pointer, memory);
@@ -194,6 +224,7 @@ mod tests {
                 let mut input_item = get_element(list_pointer, i, memory, input_type.stack_size());
 
                 // put on stack
+                stack.extend_from_slice(&environment);
                 while let Some(element) = input_item.pop() {
                     stack.push(element);
                 }
@@ -257,7 +288,8 @@ mod tests {
                 }
             }
 
-            let stack = [empty_stack(), vec![list_pointer]].concat();
+            let environment = self.f.seeded_random_environment(&mut rng);
+            let stack = [empty_stack(), environment, vec![list_pointer]].concat();
 
             FunctionInitialState { stack, memory }
         }
@@ -366,6 +398,65 @@ mod tests {
         })
         .test();
     }
+
+    #[test]
+    fn test_with_raw_function_lsb_of_sum_with_environment() {
+        let rawcode = RawCode::new(
+            triton_asm!(
+                lsb_of_sum:
+                    add     // _ (k + x)
+                    split   // _ hi lo
+                    push 2  // _ hi lo 2
+                    pick 1  // _ hi 2 lo
+                    div_mod // _ hi q r
+                    place 2 // _ r hi q
+                    pop 2   // _ r
+                    return
+            ),
+            DataType::Bfe,
+            DataType::Bool,
+        )
+        .with_environment(vec![(DataType::Bfe, "k".to_string())]);
+        ShadowedFunction::new(Filter {
+            f: InnerFunction::RawCode(rawcode),
+        })
+        .test();
+    }
+
+    #[test]
+    fn test_with_raw_function_not_equal_to_digest_in_environment() {
+        let rawcode = RawCode::new(
+            triton_asm!(
+                digest_ne:
+                    {&DataType::Digest.compare()}
+                    push 0
+                    eq
+                    return
+            ),
+            DataType::Digest,
+            DataType::Bool,
+        )
+        .with_environment(vec![(DataType::Digest, "d".to_string())]);
+        ShadowedFunction::new(Filter {
+            f: InnerFunction::RawCode(rawcode),
+        })
+        .test();
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_large_environment_is_rejected() {
+        let rawcode = RawCode::new(
+            triton_asm!(always_true: pop 1 push 1 return),
+            DataType::Bfe,
+            DataType::Bool,
+        )
+        .with_environment(vec![(
+            DataType::Tuple(vec![DataType::Digest; 3]),
+            "env".to_string(),
+        )]);
+        Filter::new(InnerFunction::RawCode(rawcode));
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,225 @@
+use triton_vm::prelude::*;
+
+use super::inner_function::drop_environment;
+use super::inner_function::InnerFunction;
+use super::position::assert_predicate_on_static_length_elements;
+use super::position::first_match_loop;
+use super::position::pointer_to_end_of_list;
+use crate::prelude::*;
+
+/// Finds the first element of a list that satisfies a predicate.
+///
+/// Stops at the first element that satisfies the predicate; later elements are
+/// neither read nor passed to the predicate. The list's elements must have a
+/// [statically known][len] length. The predicate's [environment][env], if any,
+/// is passed to every invocation of the predicate.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ [environment] *list
+/// AFTER:  _ [element: ElementType] is_some
+/// ```
+///
+/// If no element satisfies the predicate, `is_some` is `false` and all words
+/// of `element` are 0.
+///
+/// [len]: BFieldCodec::static_length
+/// [env]: InnerFunction::environment
+pub struct FindFirst {
+    pub f: InnerFunction,
+}
+
+impl FindFirst {
+    pub fn new(f: InnerFunction) -> Self {
+        assert_predicate_on_static_length_elements(&f);
+        Self { f }
+    }
+}
+
+impl BasicSnippet for FindFirst {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let list_type = DataType::List(Box::new(self.f.domain()));
+        let list = (list_type, "*list".to_string());
+
+        [self.f.environment(), vec![list]].concat()
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (self.f.domain(), "element".to_string()),
+            (DataType::Bool, "is_some".to_string()),
+        ]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!(
+            "tasmlib_list_higher_order_u32_find_first_{}",
+            self.f.entrypoint()
+        )
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let element_type = self.f.domain();
+        let element_size = element_type.stack_size();
+        let entrypoint = self.entrypoint();
+        let main_loop = format!("{entrypoint}_loop");
+        let read_element = format!("{entrypoint}_read_element");
+        let push_zeros = format!("{entrypoint}_push_zeros");
+        let zeros_and_false = vec![triton_asm!(push 0); element_size + 1].concat();
+
+        triton_asm!(
+            // BEFORE: _ [env] *list
+            // AFTER:  _ [element] is_some
+            {entrypoint}:
+                dup 0
+                {&pointer_to_end_of_list(element_size)}
+                place 1     // _ *end *list
+
+                call {main_loop}
+                            // _ *end *p
+
+                dup 0
+                pick 2
+                eq
+                push 0
+                eq          // _ *p is_some
+
+                push 1
+                pick 1      // _ *p 1 is_some
+                skiz call {read_element}
+                skiz call {push_zeros}
+                            // _ [element] is_some
+                {&drop_environment(self.f.environment_size(), element_size + 1)}
+                return
+
+            // BEFORE: _ *p 1
+            // AFTER:  _ [element] 1 0
+            {read_element}:
+                pop 1
+                addi {element_size}
+                {&element_type.read_value_from_memory_pop_pointer()}
+                push 1
+                push 0
+                return
+
+            // BEFORE: _ *p
+            // AFTER:  _ [0; element_size] 0
+            {push_zeros}:
+                pop 1
+                {&zeros_and_false}
+                return
+
+            {&first_match_loop(&self.f, &main_loop, 2, library)}
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::empty_stack;
+    use crate::list::higher_order::all::tests::TestHashXFieldElementLsb;
+    use crate::list::higher_order::position::tests::*;
+    use crate::rust_shadowing_helper_functions::list::list_get;
+    use crate::test_helpers::test_rust_equivalence_given_complete_state;
+    use crate::test_prelude::*;
+
+    impl Function for FindFirst {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let element_size = self.f.domain().stack_size();
+            let list_pointer = stack.pop().unwrap();
+            let position = first_match(&self.f, list_pointer, stack, memory);
+
+            let element = match position {
+                Some(i) => list_get(list_pointer, i, memory, element_size),
+                None => vec![bfe!(0); element_size],
+            };
+            stack.extend(element.into_iter().rev());
+            stack.push(bfe!(position.is_some() as u64));
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            initial_state(&self.f, seed, bench_case)
+        }
+    }
+
+    #[test]
+    fn rust_shadow_with_basic_snippet() {
+        let f = InnerFunction::BasicSnippet(Box::new(TestHashXFieldElementLsb));
+        ShadowedFunction::new(FindFirst::new(f)).test();
+    }
+
+    #[test]
+    fn rust_shadow_with_raw_code() {
+        ShadowedFunction::new(FindFirst::new(lt_2_pow_28())).test();
+        ShadowedFunction::new(FindFirst::new(never_on_xfe())).test();
+        ShadowedFunction::new(FindFirst::new(always_on_digest())).test();
+    }
+
+    #[test]
+    fn rust_shadow_with_environment() {
+        ShadowedFunction::new(FindFirst::new(eq_to_environment())).test();
+    }
+
+    #[test]
+    fn elements_after_first_match_are_not_inspected() {
+        let snippet = FindFirst::new(is_zero_or_crash());
+        let list = vec![bfe!(3), bfe!(0), bfe!(1_u64 << 40)];
+        let initial_state = initial_state_from_encoded_list(vec![], bfe!(42), list.encode());
+
+        let expected_stack = [empty_stack(), bfe_vec![0, 1]].concat();
+        test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            &None,
+            Some(&expected_stack),
+        );
+    }
+
+    #[proptest(cases = 20)]
+    fn find_first_agrees_with_host_find(
+        #[strategy(prop::collection::vec(arb(), 0..50))] list: Vec<u32>,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let snippet = FindFirst::new(lt_2_pow_28());
+        let initial_state = initial_state_from_encoded_list(vec![], list_pointer, list.encode());
+
+        let element = list.iter().find(|&&x| x < 1 << 28);
+        let expected_stack = [
+            empty_stack(),
+            bfe_vec![element.copied().unwrap_or(0), element.is_some() as u64],
+        ]
+        .concat();
+        test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            &None,
+            Some(&expected_stack),
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::list::higher_order::position::tests::never_on_xfe;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(FindFirst::new(never_on_xfe())).bench();
+    }
+}
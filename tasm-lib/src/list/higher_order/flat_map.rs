@@ -0,0 +1,484 @@
+use strum::EnumCount;
+use triton_vm::isa::op_stack::OpStackElement;
+use triton_vm::prelude::*;
+
+use crate::list::higher_order::inner_function::copy_environment;
+use crate::list::higher_order::inner_function::drop_environment;
+use crate::list::higher_order::inner_function::InnerFunction;
+use crate::list::new::New;
+use crate::memory::memcpy::MemCpy;
+use crate::prelude::*;
+
+const INNER_FN_INCORRECT_RANGE: &str = "Inner function in `flat_map` must return a list.";
+
+/// Applies a given function `f` to every element of a list, and appends the
+/// elements of all lists returned by `f` to one new list.
+///
+/// Both the input list's elements and the elements of the lists returned by
+/// `f` must be of a type for which the encoded length is
+/// [statically known][len]. The function's [environment][env], if any, is
+/// taken as the first input and passed to every invocation of `f`. Upon
+/// starting execution of `f`, the stack layout is:
+///
+/// ```txt
+/// // _ <accessible> [env] [_; FlatMap::NUM_INTERNAL_REGISTERS] [env] [elem; len]
+/// ```
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ [environment] *list
+/// AFTER:  _ *output_list
+/// ```
+///
+/// [len]: BFieldCodec::static_length
+/// [env]: InnerFunction::environment
+pub struct FlatMap {
+    f: InnerFunction,
+}
+
+impl FlatMap {
+    /// The number of registers required internally. See [`FlatMap`] for
+    /// additional details.
+    pub const NUM_INTERNAL_REGISTERS: usize = 3;
+
+    /// # Panics
+    ///
+    /// - if the inner function's range is not a list
+    /// - if the input list's element type or the element type of the lists
+    ///   returned by the inner function does not have a [static length][len]
+    /// - if the input list's element type has length 0
+    /// - if the [environment][env] is too large to be copied past the internal
+    ///   registers
+    ///
+    /// [len]: BFieldCodec::static_length
+    /// [env]: InnerFunction::environment
+    pub fn new(f: InnerFunction) -> Self {
+        let flat_map = Self { f };
+        flat_map
+            .output_element_type()
+            .static_length()
+            .expect("output element type's encoding length must be static");
+        let element_len = flat_map
+            .f
+            .domain()
+            .static_length()
+            .expect("input element type's encoding length must be static");
+        assert_ne!(0, element_len, "input element type must not be empty");
+
+        // need instruction `dup {Self::NUM_INTERNAL_REGISTERS + env_size - 1}`
+        let env_size = flat_map.f.environment_size();
+        assert!(Self::NUM_INTERNAL_REGISTERS + env_size <= OpStackElement::COUNT);
+
+        flat_map
+    }
+
+    fn output_element_type(&self) -> DataType {
+        let DataType::List(output_element_type) = self.f.range() else {
+            panic!("{INNER_FN_INCORRECT_RANGE}");
+        };
+
+        *output_element_type
+    }
+}
+
+impl BasicSnippet for FlatMap {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let list_type = DataType::List(Box::new(self.f.domain()));
+        let input_list = (list_type, "*input_list".to_string());
+
+        [self.f.environment(), vec![input_list]].concat()
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(self.f.range(), "*output_list".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!(
+            "tasmlib_list_higher_order_u32_flat_map_{}",
+            self.f.entrypoint()
+        )
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let element_type = self.f.domain();
+        let elem_size = element_type.stack_size();
+        let output_elem_size = self.output_element_type().stack_size();
+        let env_size = self.f.environment_size();
+
+        let new_list = library.import(Box::new(New));
+        let memcpy = library.import(Box::new(MemCpy));
+        let entrypoint = self.entrypoint();
+        let main_loop = format!("{entrypoint}_loop");
+
+        let (exec_or_call, fn_body) = match &self.f {
+            InnerFunction::RawCode(code) => {
+                // Inlining saves two clock cycles per iteration. If the function cannot be
+                // inlined, it needs to be appended to the function body.
+                match code.inlined_body() {
+                    Some(body) => (body, vec![]),
+                    None => (triton_asm!(call {code.entrypoint()}), code.function.clone()),
+                }
+            }
+            InnerFunction::BasicSnippet(snippet) => {
+                let labelled_instructions = snippet.annotated_code(library);
+                let label = library.explicit_import(&snippet.entrypoint(), &labelled_instructions);
+                (triton_asm!(call { label }), vec![])
+            }
+            InnerFunction::NoFunctionBody(lnat) => (triton_asm!(call { lnat.label_name }), vec![]),
+        };
+
+        let mul_by = |n| match n {
+            1 => triton_asm!(),
+            n => triton_asm!(push {n} mul),
+        };
+
+        triton_asm!(
+            // BEFORE: _ [env] *in_list
+            // AFTER:  _ *out_list
+            {entrypoint}:
+                call {new_list}
+                            // _ [env] *in_list *out_list
+
+                pick 1
+                read_mem 1
+                addi 1      // _ [env] *out_list len *in_list
+
+                /* prepare in_list pointers for main loop */
+                pick 1
+                {&mul_by(elem_size)}
+                dup 1
+                add
+                place 1     // _ [env] *out_list *in_list_last_elem_last_word *in_list
+
+                call {main_loop}
+                            // _ [env] *out_list *in_list_last_elem_last_word *in_list_last_elem_last_word
+
+                pop 2       // _ [env] *out_list
+                {&drop_environment(env_size, 1)}
+                return
+
+            // INVARIANT: _ [env] *out_list *in_list_last_elem_last_word *prev_elem_last_word
+            {main_loop}:
+                /* maybe return */
+                dup 1
+                dup 1
+                eq
+                skiz return
+
+                /* read */
+                {&copy_environment(env_size, Self::NUM_INTERNAL_REGISTERS)}
+                pick {env_size}
+                addi {elem_size}
+                dup 0
+                place {env_size + 1}
+                            // _ [env] *out_list *in_list_last_elem_last_word *elem_last_word [env] *elem_last_word
+                {&element_type.read_value_from_memory_pop_pointer()}
+                            // _ [env] *out_list *in_list_last_elem_last_word *elem_last_word [env] [elem]
+
+                /* map */
+                {&exec_or_call}
+                            // _ [env] *out_list *in_list_last_elem_last_word *elem_last_word *inner_list
+
+                /* update out_list's length */
+                read_mem 1
+                addi 2      // _ [env] *out_list *in_list_last_elem_last_word *elem_last_word inner_len *inner_list[0]
+                dup 4
+                read_mem 1
+                pop 1       // _ [env] *out_list *in_list_last_elem_last_word *elem_last_word inner_len *inner_list[0] out_len
+                dup 0
+                dup 3
+                add
+                dup 6
+                write_mem 1
+                pop 1       // _ [env] *out_list *in_list_last_elem_last_word *elem_last_word inner_len *inner_list[0] out_len
+
+                /* append inner_list's elements */
+                {&mul_by(output_elem_size)}
+                dup 5
+                add
+                addi 1      // _ [env] *out_list *in_list_last_elem_last_word *elem_last_word inner_len *inner_list[0] *out_list[out_len]
+                pick 2
+                {&mul_by(output_elem_size)}
+                call {memcpy}
+                            // _ [env] *out_list *in_list_last_elem_last_word *elem_last_word
+
+                recurse
+
+            {&fn_body}
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::empty_stack;
+    use crate::list::higher_order::inner_function::RawCode;
+    use crate::rust_shadowing_helper_functions::dyn_malloc::dynamic_allocator;
+    use crate::rust_shadowing_helper_functions::list::list_get;
+    use crate::rust_shadowing_helper_functions::list::list_get_length;
+    use crate::rust_shadowing_helper_functions::list::list_insert;
+    use crate::rust_shadowing_helper_functions::list::list_push;
+    use crate::test_helpers::test_rust_equivalence_given_complete_state;
+    use crate::test_prelude::*;
+
+    impl FlatMap {
+        fn init_state(
+            &self,
+            environment: Vec<BFieldElement>,
+            list: Vec<BFieldElement>,
+        ) -> FunctionInitialState {
+            let mut stack = self.init_stack_for_isolated_run();
+            let mut memory = HashMap::default();
+
+            let list_pointer = dynamic_allocator(&mut memory);
+            let indexed_list = list
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| (list_pointer + bfe!(i), v));
+            memory.extend(indexed_list);
+
+            stack.extend(environment);
+            stack.push(list_pointer);
+
+            FunctionInitialState { stack, memory }
+        }
+    }
+
+    impl Function for FlatMap {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let element_size = self.f.domain().stack_size();
+            let output_element_size = self.output_element_type().stack_size();
+
+            let list_pointer = stack.pop().unwrap();
+            let environment = stack.split_off(stack.len() - self.f.environment_size());
+
+            New.rust_shadow(stack, memory);
+            let output_list_pointer = stack.pop().unwrap();
+
+            // the inner function _must not_ rely on these elements
+            let buffer = (0..Self::NUM_INTERNAL_REGISTERS).map(|_| rand::random::<BFieldElement>());
+            stack.extend(buffer);
+
+            for i in 0..list_get_length(list_pointer, memory) {
+                let element = list_get(list_pointer, i, memory, element_size);
+                stack.extend_from_slice(&environment);
+                stack.extend(element.into_iter().rev());
+                self.f.apply_with_memory(stack, memory);
+
+                let inner_list_pointer = stack.pop().unwrap();
+                for j in 0..list_get_length(inner_list_pointer, memory) {
+                    let inner_element =
+                        list_get(inner_list_pointer, j, memory, output_element_size);
+                    list_push(output_list_pointer, inner_element, memory);
+                }
+            }
+
+            for _ in 0..Self::NUM_INTERNAL_REGISTERS {
+                stack.pop();
+            }
+            stack.push(output_list_pointer);
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let list_length = match bench_case {
+                Some(BenchmarkCase::CommonCase) => 10,
+                Some(BenchmarkCase::WorstCase) => 100,
+                None => rng.random_range(0..100),
+            };
+            let list = self.f.domain().random_list(&mut rng, list_length);
+            let environment = self.f.seeded_random_environment(&mut rng);
+
+            self.init_state(environment, list)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let mut rng = StdRng::seed_from_u64(0x_f1a7);
+
+            (0..=2)
+                .map(|list_length| {
+                    let list = self.f.domain().random_list(&mut rng, list_length);
+                    let environment = self.f.seeded_random_environment(&mut rng);
+                    self.init_state(environment, list)
+                })
+                .collect()
+        }
+    }
+
+    /// Expands an extension field element into the list of its coefficients.
+    #[derive(Debug, Clone)]
+    pub(crate) struct TestXfeCoefficients;
+
+    impl BasicSnippet for TestXfeCoefficients {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            vec![(DataType::Xfe, "element".to_string())]
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            let list_type = DataType::List(Box::new(DataType::Bfe));
+            vec![(list_type, "*coefficients".to_string())]
+        }
+
+        fn entrypoint(&self) -> String {
+            "test_xfe_coefficients".to_string()
+        }
+
+        fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+            let new_list = library.import(Box::new(New));
+
+            triton_asm!(
+                // BEFORE: _ [element: Xfe]
+                // AFTER:  _ *coefficients
+                {self.entrypoint()}:
+                    call {new_list}
+                    dup 0
+                    place 4     // _ *list [element] *list
+                    push 3
+                    pick 1
+                    write_mem 1
+                    write_mem 3
+                    pop 1       // _ *list
+                    return
+            )
+        }
+    }
+
+    /// Pairs an element with the environment: `(env, elem) -> [env, elem]`.
+    #[derive(Debug, Clone)]
+    struct TestPairWithEnvironment;
+
+    impl BasicSnippet for TestPairWithEnvironment {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            vec![
+                (DataType::Bfe, "environment".to_string()),
+                (DataType::Bfe, "element".to_string()),
+            ]
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            let list_type = DataType::List(Box::new(DataType::Bfe));
+            vec![(list_type, "*pair".to_string())]
+        }
+
+        fn entrypoint(&self) -> String {
+            "test_pair_with_environment".to_string()
+        }
+
+        fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+            let new_list = library.import(Box::new(New));
+
+            triton_asm!(
+                // BEFORE: _ env elem
+                // AFTER:  _ *pair
+                {self.entrypoint()}:
+                    call {new_list}
+                    dup 0
+                    place 3     // _ *list env elem *list
+                    push 2
+                    pick 1
+                    write_mem 1 // _ *list env elem (*list + 1)
+                    pick 2
+                    place 1     // _ *list elem env (*list + 1)
+                    write_mem 2
+                    pop 1       // _ *list
+                    return
+            )
+        }
+    }
+
+    #[test]
+    fn rust_shadow_with_basic_snippet() {
+        let f = InnerFunction::BasicSnippet(Box::new(TestXfeCoefficients));
+        ShadowedFunction::new(FlatMap::new(f)).test();
+    }
+
+    #[test]
+    fn rust_shadow_with_environment() {
+        let f = InnerFunction::BasicSnippet(Box::new(TestPairWithEnvironment));
+        ShadowedFunction::new(FlatMap::new(f)).test();
+    }
+
+    #[test]
+    #[should_panic]
+    fn inner_function_not_returning_a_list_is_rejected() {
+        let f = InnerFunction::RawCode(RawCode::new(
+            triton_asm!(identity: return),
+            DataType::Bfe,
+            DataType::Bfe,
+        ));
+        FlatMap::new(f);
+    }
+
+    #[proptest(cases = 20)]
+    fn flattening_agrees_with_host_flatten(
+        #[strategy(prop::collection::vec(prop::collection::vec(arb(), 0..10), 0..10))] lists: Vec<
+            Vec<Digest>,
+        >,
+    ) {
+        let list_type = DataType::List(Box::new(DataType::Digest));
+        let flatten = InnerFunction::RawCode(RawCode::new(
+            triton_asm!(identity: return),
+            DataType::Bfe,
+            list_type,
+        ));
+        let snippet = FlatMap::new(flatten);
+
+        let mut memory = HashMap::default();
+        let list_pointers = lists
+            .iter()
+            .map(|list| {
+                let list_pointer = dynamic_allocator(&mut memory);
+                list_insert(list_pointer, list.clone(), &mut memory);
+                list_pointer
+            })
+            .collect_vec();
+        let list_of_lists_pointer = dynamic_allocator(&mut memory);
+        list_insert(list_of_lists_pointer, list_pointers, &mut memory);
+
+        let mut output_list_memory = memory.clone();
+        let output_list_pointer = dynamic_allocator(&mut output_list_memory);
+        let stack = [empty_stack(), vec![list_of_lists_pointer]].concat();
+        let expected_stack = [empty_stack(), vec![output_list_pointer]].concat();
+
+        let final_state = test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &stack,
+            &[],
+            &NonDeterminism::default().with_ram(memory),
+            &None,
+            Some(&expected_stack),
+        );
+
+        let flattened = lists.concat();
+        let ram = &final_state.ram;
+        prop_assert_eq!(flattened.len(), list_get_length(output_list_pointer, ram));
+        for (i, digest) in flattened.into_iter().enumerate() {
+            let output_element = list_get(output_list_pointer, i, ram, Digest::LEN);
+            prop_assert_eq!(digest.encode(), output_element);
+        }
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::tests::TestXfeCoefficients;
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        let f = InnerFunction::BasicSnippet(Box::new(TestXfeCoefficients));
+        ShadowedFunction::new(FlatMap::new(f)).bench();
+    }
+}
@@ -0,0 +1,687 @@
+use strum::EnumCount;
+use triton_vm::isa::op_stack::OpStackElement;
+use triton_vm::prelude::*;
+
+use crate::list::higher_order::inner_function::drop_environment;
+use crate::list::higher_order::inner_function::InnerFunction;
+use crate::prelude::*;
+
+const INNER_FN_INCORRECT_DOMAIN: &str = "Inner function in `fold` must take an accumulator and \
+                                         an element, either as two inputs or as a tuple \
+                                         `(acc, elem)`.";
+
+/// Folds a list into a single accumulator by repeatedly applying a given
+/// function `f` of shape `(acc, elem) -> acc`, starting with an initial
+/// accumulator and the list's first element.
+///
+/// Both the accumulator and the list's elements must be of a type for which the
+/// encoded length is [statically known][len]. The function `f` is either a
+/// [`BasicSnippet`] whose last two inputs are the accumulator and the element,
+/// or some other [`InnerFunction`] whose domain is the tuple `(acc, elem)`. In
+/// either case, its range must be the accumulator type.
+///
+/// Any inputs of a [`BasicSnippet`] preceding the accumulator, or the
+/// [environment][env] of any other [`InnerFunction`], are inputs to the fold.
+/// They are passed to every invocation of `f`, and consumed by the fold.
+///
+/// Like for [`ChainMap`][map], the stack layout is independent of the list's
+/// length. This allows `f` to use runtime parameters from the stack. Note that
+/// the fold requires [`Fold::NUM_INTERNAL_REGISTERS`] stack registers for
+/// internal purposes. Upon starting execution of `f`, the stack layout is:
+///
+/// ```txt
+/// // _ <accessible> [env] [_; Fold::NUM_INTERNAL_REGISTERS] [env] [acc; len] [elem; len]
+/// ```
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ [environment] [initial_accumulator: AccType] *list
+/// AFTER:  _ [accumulator: AccType]
+/// ```
+///
+/// [len]: BFieldCodec::static_length
+/// [env]: InnerFunction::environment
+/// [map]: crate::list::higher_order::map::ChainMap
+pub struct Fold {
+    f: InnerFunction,
+}
+
+impl Fold {
+    /// The number of registers required internally. See [`Fold`] for additional
+    /// details.
+    pub const NUM_INTERNAL_REGISTERS: usize = 2;
+
+    /// # Panics
+    ///
+    /// - if the inner function's domain is not of shape `(acc, elem)`
+    /// - if the inner function's range is not the accumulator type
+    /// - if the accumulator or element type does not have a [static length][len]
+    /// - if the element type has length 0
+    /// - if the accumulator type and the environment together take up
+    ///   [`OpStackElement::COUNT`]` - 1` or more words
+    ///
+    /// [len]: BFieldCodec::static_length
+    pub fn new(f: InnerFunction) -> Self {
+        let fold = Self { f };
+        let (accumulator_type, element_type) = fold.accumulator_and_element_types();
+        assert_eq!(accumulator_type, fold.f.range());
+
+        let accumulator_len = accumulator_type
+            .static_length()
+            .expect("accumulator type's encoding length must be static");
+        let element_len = element_type
+            .static_length()
+            .expect("element type's encoding length must be static");
+        assert_ne!(0, element_len, "element type must not be empty");
+
+        // need instruction `dup {acc_size + env_size + 1}`
+        let environment_size = fold.environment_size();
+        assert!(accumulator_len + environment_size + 1 < OpStackElement::COUNT);
+
+        fold
+    }
+
+    fn accumulator_and_element_types(&self) -> (DataType, DataType) {
+        if let InnerFunction::BasicSnippet(snippet) = &self.f {
+            if let [.., (ref acc, _), (ref elem, _)] = snippet.inputs()[..] {
+                return (acc.clone(), elem.clone());
+            }
+        }
+
+        let DataType::Tuple(tuple) = self.f.domain() else {
+            panic!("{INNER_FN_INCORRECT_DOMAIN}");
+        };
+        let [ref acc, ref elem] = tuple[..] else {
+            panic!("{INNER_FN_INCORRECT_DOMAIN}");
+        };
+
+        (acc.clone(), elem.clone())
+    }
+
+    fn environment(&self) -> Vec<(DataType, String)> {
+        match &self.f {
+            InnerFunction::BasicSnippet(snippet) => {
+                let mut inputs = snippet.inputs();
+                inputs.truncate(inputs.len().saturating_sub(2));
+                inputs
+            }
+            _ => self.f.environment(),
+        }
+    }
+
+    fn environment_size(&self) -> usize {
+        self.environment()
+            .iter()
+            .map(|(data_type, _)| data_type.stack_size())
+            .sum()
+    }
+}
+
+impl BasicSnippet for Fold {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let (accumulator_type, element_type) = self.accumulator_and_element_types();
+        let list_type = DataType::List(Box::new(element_type));
+
+        let accumulator_and_list = vec![
+            (accumulator_type, "initial_accumulator".to_string()),
+            (list_type, "*list".to_string()),
+        ];
+
+        [self.environment(), accumulator_and_list].concat()
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(self.f.range(), "accumulator".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!("tasmlib_list_higher_order_u32_fold_{}", self.f.entrypoint())
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let (accumulator_type, element_type) = self.accumulator_and_element_types();
+        let acc_size = accumulator_type.stack_size();
+        let elem_size = element_type.stack_size();
+        let env_size = self.environment_size();
+
+        let entrypoint = self.entrypoint();
+        let main_loop = format!("{entrypoint}_loop");
+
+        let (exec_or_call, fn_body) = match &self.f {
+            InnerFunction::RawCode(code) => {
+                // Inlining saves two clock cycles per iteration. If the function cannot be
+                // inlined, it needs to be appended to the function body.
+                match code.inlined_body() {
+                    Some(body) => (body, vec![]),
+                    None => (triton_asm!(call {code.entrypoint()}), code.function.clone()),
+                }
+            }
+            InnerFunction::BasicSnippet(snippet) => {
+                let labelled_instructions = snippet.annotated_code(library);
+                let label = library.explicit_import(&snippet.entrypoint(), &labelled_instructions);
+                (triton_asm!(call { label }), vec![])
+            }
+            InnerFunction::NoFunctionBody(lnat) => (triton_asm!(call { lnat.label_name }), vec![]),
+        };
+
+        let copy_deepest_env_word = triton_asm!(dup {acc_size + env_size + 1} place {acc_size});
+        let copy_environment_below_accumulator = vec![copy_deepest_env_word; env_size].concat();
+
+        let mul_elem_size = match elem_size {
+            1 => triton_asm!(),
+            n => triton_asm!(push {n} mul),
+        };
+
+        triton_asm!(
+            // BEFORE: _ [env] [acc] *list
+            // AFTER:  _ [acc']
+            {entrypoint}:
+                dup 0
+                read_mem 1
+                pop 1       // _ [acc] *list len
+
+                {&mul_elem_size}
+                dup 1
+                add         // _ [acc] *list *last_elem_last_word
+
+                place {acc_size + 1}
+                place {acc_size}
+                            // _ *last_elem_last_word *list [acc]
+
+                call {main_loop}
+                            // _ *last_elem_last_word *last_elem_last_word [acc']
+
+                pick {acc_size + 1}
+                pick {acc_size + 1}
+                pop 2       // _ [env] [acc']
+                {&drop_environment(env_size, acc_size)}
+                return
+
+            // INVARIANT: _ [env] *last_elem_last_word *prev_elem_last_word [acc]
+            {main_loop}:
+                /* maybe return */
+                // not using `recurse_or_return` to have more room for parameters
+                // that might live on the stack, to be used by the inner function
+                dup {acc_size + 1}
+                dup {acc_size + 1}
+                eq
+                skiz return
+
+                /* copy environment below accumulator */
+                {&copy_environment_below_accumulator}
+                            // _ [env] *last_elem_last_word *prev_elem_last_word [env] [acc]
+
+                /* read */
+                pick {acc_size + env_size}
+                addi {elem_size}
+                dup 0
+                place {acc_size + env_size + 1}
+                            // _ [env] *last_elem_last_word *elem_last_word [env] [acc] *elem_last_word
+                {&element_type.read_value_from_memory_pop_pointer()}
+                            // _ [env] *last_elem_last_word *elem_last_word [env] [acc] [elem]
+
+                /* fold */
+                {&exec_or_call}
+                            // _ [env] *last_elem_last_word *elem_last_word [acc']
+
+                recurse
+
+            {&fn_body}
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::list::higher_order::inner_function::NoFunctionBody;
+    use crate::list::higher_order::inner_function::RawCode;
+    use crate::rust_shadowing_helper_functions::dyn_malloc::dynamic_allocator;
+    use crate::rust_shadowing_helper_functions::list::list_get;
+    use crate::rust_shadowing_helper_functions::list::list_get_length;
+    use crate::test_helpers::test_rust_equivalence_given_complete_state;
+    use crate::test_prelude::*;
+
+    impl Fold {
+        fn init_state(
+            &self,
+            environment_args: impl IntoIterator<Item = BFieldElement>,
+            initial_accumulator: Vec<BFieldElement>,
+            list: Vec<BFieldElement>,
+        ) -> FunctionInitialState {
+            let mut stack = self.init_stack_for_isolated_run();
+            let mut memory = HashMap::default();
+
+            let list_pointer = dynamic_allocator(&mut memory);
+            let indexed_list = list
+                .into_iter()
+                .enumerate()
+                .map(|(i, v)| (list_pointer + bfe!(i), v));
+            memory.extend(indexed_list);
+
+            stack.extend(environment_args);
+            stack.extend(initial_accumulator.into_iter().rev());
+            stack.push(list_pointer);
+
+            FunctionInitialState { stack, memory }
+        }
+
+        fn seeded_random_environment(&self, rng: &mut impl Rng) -> Vec<BFieldElement> {
+            self.environment()
+                .iter()
+                .flat_map(|(data_type, _)| {
+                    let mut value = data_type.seeded_random_element(rng);
+                    value.reverse();
+                    value
+                })
+                .collect()
+        }
+    }
+
+    impl Function for Fold {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let (accumulator_type, element_type) = self.accumulator_and_element_types();
+            let acc_size = accumulator_type.stack_size();
+
+            let list_pointer = stack.pop().unwrap();
+            let mut accumulator = stack.split_off(stack.len() - acc_size);
+            let environment = stack.split_off(stack.len() - self.environment_size());
+
+            // the inner function _must not_ rely on these elements
+            let buffer = (0..Self::NUM_INTERNAL_REGISTERS).map(|_| rand::random::<BFieldElement>());
+            stack.extend(buffer);
+
+            for i in 0..list_get_length(list_pointer, memory) {
+                let elem = list_get(list_pointer, i, memory, element_type.stack_size());
+                stack.extend_from_slice(&environment);
+                stack.extend(accumulator);
+                stack.extend(elem.into_iter().rev());
+                self.f.apply(stack, memory);
+                accumulator = stack.split_off(stack.len() - acc_size);
+            }
+
+            for _ in 0..Self::NUM_INTERNAL_REGISTERS {
+                stack.pop();
+            }
+            stack.extend(accumulator);
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let (accumulator_type, element_type) = self.accumulator_and_element_types();
+            let mut rng = StdRng::from_seed(seed);
+            let environment_args = rng.random::<[BFieldElement; OpStackElement::COUNT]>();
+
+            let list_length = match bench {
+                None => rng.random_range(0..200),
+                Some(BenchmarkCase::CommonCase) => 10,
+                Some(BenchmarkCase::WorstCase) => 100,
+            };
+            let initial_accumulator = accumulator_type.seeded_random_element(&mut rng);
+            let list = element_type.random_list(&mut rng, list_length);
+            let environment = self.seeded_random_environment(&mut rng);
+            let environment_args = environment_args.into_iter().chain(environment);
+
+            self.init_state(environment_args, initial_accumulator, list)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let (accumulator_type, element_type) = self.accumulator_and_element_types();
+            let mut rng = StdRng::seed_from_u64(0x_f01d);
+
+            (0..=2)
+                .map(|list_length| {
+                    let initial_accumulator = accumulator_type.seeded_random_element(&mut rng);
+                    let list = element_type.random_list(&mut rng, list_length);
+                    let environment = self.seeded_random_environment(&mut rng);
+                    self.init_state(environment, initial_accumulator, list)
+                })
+                .collect()
+        }
+    }
+
+    /// Absorbs a digest into an accumulating digest: `(acc, elem) -> hash(acc, elem)`.
+    #[derive(Debug, Clone)]
+    pub(crate) struct TestHashChain;
+
+    impl BasicSnippet for TestHashChain {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            vec![
+                (DataType::Digest, "accumulator".to_string()),
+                (DataType::Digest, "element".to_string()),
+            ]
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            vec![(DataType::Digest, "accumulator".to_string())]
+        }
+
+        fn entrypoint(&self) -> String {
+            "test_hash_chain".to_string()
+        }
+
+        fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+            triton_asm!(
+                // BEFORE: _ [acc: Digest] [elem: Digest]
+                // AFTER:  _ [acc': Digest]
+                {self.entrypoint()}:
+                    hash
+                    return
+            )
+        }
+    }
+
+    /// Adds a scaled element to an accumulator: `(s, acc, elem) -> acc + s·elem`.
+    /// The scalar `s` is the fold's environment.
+    #[derive(Debug, Clone)]
+    struct TestScaledSum;
+
+    impl BasicSnippet for TestScaledSum {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            vec![
+                (DataType::Xfe, "scalar".to_string()),
+                (DataType::Xfe, "accumulator".to_string()),
+                (DataType::Bfe, "element".to_string()),
+            ]
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            vec![(DataType::Xfe, "accumulator".to_string())]
+        }
+
+        fn entrypoint(&self) -> String {
+            "test_scaled_sum".to_string()
+        }
+
+        fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+            triton_asm!(
+                // BEFORE: _ [s: Xfe] [acc: Xfe] elem
+                // AFTER:  _ [acc': Xfe]
+                {self.entrypoint()}:
+                    pick 6
+                    pick 6
+                    pick 6  // _ [acc] elem [s]
+                    pick 3  // _ [acc] [s] elem
+                    xb_mul
+                    xx_add
+                    return
+            )
+        }
+    }
+
+    fn bfe_pair() -> DataType {
+        DataType::Tuple(vec![DataType::Bfe, DataType::Bfe])
+    }
+
+    fn sum_bfes() -> InnerFunction {
+        InnerFunction::RawCode(RawCode::new(
+            triton_asm!(sum_bfes: add return),
+            bfe_pair(),
+            DataType::Bfe,
+        ))
+    }
+
+    fn triple_and_add() -> InnerFunction {
+        InnerFunction::RawCode(RawCode::new(
+            triton_asm!(triple_and_add: pick 1 push 3 mul add return),
+            bfe_pair(),
+            DataType::Bfe,
+        ))
+    }
+
+    #[test]
+    fn rust_shadow_with_raw_code_sum_of_bfes() {
+        ShadowedFunction::new(Fold::new(sum_bfes())).test();
+    }
+
+    #[test]
+    fn rust_shadow_with_raw_code_max_of_u32s() {
+        let f = InnerFunction::RawCode(RawCode::new(
+            triton_asm!(
+                max_u32:
+                    // _ acc elem
+                    dup 1 dup 1 lt  // _ acc elem (elem < acc)
+                    pick 2 dup 2    // _ elem (elem < acc) acc elem
+                    push -1 mul add // _ elem (elem < acc) (acc - elem)
+                    mul add         // _ max
+                    return
+            ),
+            DataType::Tuple(vec![DataType::U32, DataType::U32]),
+            DataType::U32,
+        ));
+        ShadowedFunction::new(Fold::new(f)).test();
+    }
+
+    #[test]
+    fn rust_shadow_with_raw_code_xfe_accumulator_and_u64_elements() {
+        let f = InnerFunction::RawCode(RawCode::new(
+            triton_asm!(
+                scale_xfe_by_u64_lo:
+                    // _ [acc: Xfe] hi lo
+                    pick 1 pop 1 // _ [acc: Xfe] lo
+                    xb_mul       // _ [acc * lo: Xfe]
+                    return
+            ),
+            DataType::Tuple(vec![DataType::Xfe, DataType::U64]),
+            DataType::Xfe,
+        ));
+        ShadowedFunction::new(Fold::new(f)).test();
+    }
+
+    #[test]
+    fn rust_shadow_with_raw_code_that_cannot_be_inlined() {
+        let f = InnerFunction::RawCode(RawCode::new(
+            triton_asm!(
+                add_after_count_down:
+                    // _ acc elem
+                    add
+                    push 3
+                    call add_after_count_down_loop
+                    pop 1
+                    return
+                add_after_count_down_loop:
+                    // _ acc counter
+                    dup 0 push 0 eq
+                    skiz return
+                    addi -1
+                    recurse
+            ),
+            bfe_pair(),
+            DataType::Bfe,
+        ));
+        ShadowedFunction::new(Fold::new(f)).test();
+    }
+
+    #[test]
+    fn rust_shadow_with_basic_snippet_hash_chain() {
+        let f = InnerFunction::BasicSnippet(Box::new(TestHashChain));
+        ShadowedFunction::new(Fold::new(f)).test();
+    }
+
+    #[test]
+    fn rust_shadow_with_raw_code_using_environment_args() {
+        // `|acc, elem| -> acc + elem * n`, where `n` lives on the stack below the fold's
+        // internal registers
+        let offset = Fold::NUM_INTERNAL_REGISTERS;
+        let f = InnerFunction::RawCode(RawCode::new(
+            triton_asm!(sum_scaled: dup {2 + offset} mul add return),
+            bfe_pair(),
+            DataType::Bfe,
+        ));
+        ShadowedFunction::new(Fold::new(f)).test();
+    }
+
+    #[test]
+    fn rust_shadow_with_raw_code_using_environment() {
+        let f = RawCode::new(
+            triton_asm!(sum_scaled_by_env: pick 2 mul add return),
+            bfe_pair(),
+            DataType::Bfe,
+        )
+        .with_environment(vec![(DataType::Bfe, "scalar".to_string())]);
+        ShadowedFunction::new(Fold::new(InnerFunction::RawCode(f))).test();
+    }
+
+    #[test]
+    fn rust_shadow_with_basic_snippet_using_environment() {
+        let f = InnerFunction::BasicSnippet(Box::new(TestScaledSum));
+        ShadowedFunction::new(Fold::new(f)).test();
+    }
+
+    #[test]
+    fn environment_is_consumed() {
+        let f = RawCode::new(
+            triton_asm!(sum_scaled_by_env: pick 2 mul add return),
+            bfe_pair(),
+            DataType::Bfe,
+        )
+        .with_environment(vec![(DataType::Bfe, "scalar".to_string())]);
+        let fold = Fold::new(InnerFunction::RawCode(f));
+
+        let list = bfe_vec![1, 2, 3].encode();
+        let initial_state = fold.init_state(bfe_vec![10], bfe_vec![5], list);
+        let expected_stack = [fold.init_stack_for_isolated_run(), bfe_vec![65]].concat();
+
+        test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(fold),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            &None,
+            Some(&expected_stack),
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn inner_function_with_non_tuple_domain_is_rejected() {
+        let f = InnerFunction::RawCode(RawCode::new(
+            triton_asm!(identity: return),
+            DataType::Bfe,
+            DataType::Bfe,
+        ));
+        Fold::new(f);
+    }
+
+    #[test]
+    #[should_panic]
+    fn inner_function_with_mismatching_range_is_rejected() {
+        let f = InnerFunction::RawCode(RawCode::new(
+            triton_asm!(lift: add push 0 push 0 pick 2 return),
+            bfe_pair(),
+            DataType::Xfe,
+        ));
+        Fold::new(f);
+    }
+
+    #[proptest(cases = 20)]
+    fn fold_agrees_with_host_fold_from_first_to_last_element(
+        #[strategy(prop::collection::vec(arb(), 0..50))] list: Vec<BFieldElement>,
+        #[strategy(arb())] initial_accumulator: BFieldElement,
+    ) {
+        let fold = Fold::new(triple_and_add());
+        let initial_state = fold.init_state([], vec![initial_accumulator], list.encode());
+
+        let accumulator = list
+            .iter()
+            .fold(initial_accumulator, |acc, &elem| bfe!(3) * acc + elem);
+        let mut expected_stack = fold.init_stack_for_isolated_run();
+        expected_stack.push(accumulator);
+
+        test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(fold),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            &None,
+            Some(&expected_stack),
+        );
+    }
+
+    /// Folds with an inner function that is declared by this snippet, not by the
+    /// [`Fold`].
+    struct FoldWithExternalInnerFunction;
+
+    impl FoldWithExternalInnerFunction {
+        const INNER_FUNCTION_LABEL: &'static str = "external_triple_and_add";
+
+        fn fold(&self) -> Fold {
+            Fold::new(InnerFunction::NoFunctionBody(NoFunctionBody::new(
+                Self::INNER_FUNCTION_LABEL.to_string(),
+                bfe_pair(),
+                DataType::Bfe,
+            )))
+        }
+    }
+
+    impl BasicSnippet for FoldWithExternalInnerFunction {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            self.fold().inputs()
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            self.fold().outputs()
+        }
+
+        fn entrypoint(&self) -> String {
+            "test_fold_with_external_inner_function".to_string()
+        }
+
+        fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+            let fold = library.import(Box::new(self.fold()));
+
+            triton_asm!(
+                {self.entrypoint()}:
+                    call {fold}
+                    return
+                {Self::INNER_FUNCTION_LABEL}:
+                    pick 1 push 3 mul add
+                    return
+            )
+        }
+    }
+
+    impl Function for FoldWithExternalInnerFunction {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            Fold::new(triple_and_add()).rust_shadow(stack, memory);
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            Fold::new(triple_and_add()).pseudorandom_initial_state(seed, bench_case)
+        }
+    }
+
+    #[test]
+    fn rust_shadow_with_no_function_body() {
+        ShadowedFunction::new(FoldWithExternalInnerFunction).test();
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::tests::TestHashChain;
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn fold_benchmark() {
+        let f = InnerFunction::BasicSnippet(Box::new(TestHashChain));
+        ShadowedFunction::new(Fold::new(f)).bench();
+    }
+}
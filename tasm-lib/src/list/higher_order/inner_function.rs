@@ -5,9 +5,10 @@ use triton_vm::prelude::*;
 
 use crate::prelude::*;
 
-const MORE_THAN_ONE_INPUT_OR_OUTPUT_TYPE_IN_INNER_FUNCTION: &str = "higher-order functions \
-currently only work with *one* input element in inner function. \
+const MORE_THAN_ONE_OUTPUT_TYPE_IN_INNER_FUNCTION: &str = "higher-order functions \
+currently only work with *one* output element in inner function. \
 Use a tuple data type to circumvent this.";
+const NO_INPUT_TYPE_IN_INNER_FUNCTION: &str = "inner function must take at least one input";
 
 /// A data structure for describing an inner function predicate to filter with,
 /// or a function to map with.
@@ -16,6 +17,10 @@ pub struct RawCode {
     pub function: Vec<LabelledInstruction>,
     pub input_type: DataType,
     pub output_type: DataType,
+
+    /// Arguments the function takes from the stack in addition to its input.
+    /// See [`InnerFunction::environment`].
+    pub environment: Vec<(DataType, String)>,
 }
 
 impl RawCode {
@@ -53,8 +58,16 @@ impl RawCode {
             function,
             input_type,
             output_type,
+            environment: vec![],
         }
     }
+
+    /// Declare arguments the function takes from the stack in addition to its
+    /// input. See [`InnerFunction::environment`].
+    pub fn with_environment(mut self, environment: Vec<(DataType, String)>) -> Self {
+        self.environment = environment;
+        self
+    }
 }
 
 impl RawCode {
@@ -131,6 +144,28 @@ pub struct NoFunctionBody {
     pub label_name: String,
     pub input_type: DataType,
     pub output_type: DataType,
+
+    /// Arguments the function takes from the stack in addition to its input.
+    /// See [`InnerFunction::environment`].
+    pub environment: Vec<(DataType, String)>,
+}
+
+impl NoFunctionBody {
+    pub fn new(label_name: String, input_type: DataType, output_type: DataType) -> Self {
+        Self {
+            label_name,
+            input_type,
+            output_type,
+            environment: vec![],
+        }
+    }
+
+    /// Declare arguments the function takes from the stack in addition to its
+    /// input. See [`InnerFunction::environment`].
+    pub fn with_environment(mut self, environment: Vec<(DataType, String)>) -> Self {
+        self.environment = environment;
+        self
+    }
 }
 
 impl InnerFunction {
@@ -139,21 +174,56 @@ impl InnerFunction {
             InnerFunction::RawCode(raw) => raw.input_type.clone(),
             InnerFunction::NoFunctionBody(f) => f.input_type.clone(),
             InnerFunction::BasicSnippet(bs) => {
-                let [(ref input, _)] = bs.inputs()[..] else {
-                    panic!("{MORE_THAN_ONE_INPUT_OR_OUTPUT_TYPE_IN_INNER_FUNCTION}");
+                let Some((input, _)) = bs.inputs().pop() else {
+                    panic!("{NO_INPUT_TYPE_IN_INNER_FUNCTION}");
                 };
-                input.clone()
+                input
             }
         }
     }
 
+    /// Arguments the inner function takes from the stack in addition to the
+    /// element of its [domain](Self::domain), for example, runtime parameters
+    /// like an offset to add to every element. For a [`BasicSnippet`], these are
+    /// all inputs but the last one.
+    ///
+    /// A higher-order function takes the environment as its first inputs, keeps
+    /// it below its own loop state, and copies it onto the stack for every
+    /// invocation of the inner function. The environment is consumed by the
+    /// higher-order function. The inner function must behave as follows:
+    ///
+    /// ```text
+    /// BEFORE: _ [environment] [element]
+    /// AFTER:  _ [output]
+    /// ```
+    pub fn environment(&self) -> Vec<(DataType, String)> {
+        match self {
+            InnerFunction::RawCode(rc) => rc.environment.clone(),
+            InnerFunction::NoFunctionBody(f) => f.environment.clone(),
+            InnerFunction::BasicSnippet(bs) => {
+                let mut inputs = bs.inputs();
+                inputs.pop().expect(NO_INPUT_TYPE_IN_INNER_FUNCTION);
+                inputs
+            }
+        }
+    }
+
+    /// The number of words the [environment](Self::environment) takes up on the
+    /// stack.
+    pub fn environment_size(&self) -> usize {
+        self.environment()
+            .iter()
+            .map(|(data_type, _)| data_type.stack_size())
+            .sum()
+    }
+
     pub fn range(&self) -> DataType {
         match self {
             InnerFunction::RawCode(rc) => rc.output_type.clone(),
             InnerFunction::NoFunctionBody(lnat) => lnat.output_type.clone(),
             InnerFunction::BasicSnippet(bs) => {
                 let [(ref output, _)] = bs.outputs()[..] else {
-                    panic!("{MORE_THAN_ONE_INPUT_OR_OUTPUT_TYPE_IN_INNER_FUNCTION}");
+                    panic!("{MORE_THAN_ONE_OUTPUT_TYPE_IN_INNER_FUNCTION}");
                 };
                 output.clone()
             }
@@ -175,6 +245,26 @@ impl InnerFunction {
         stack: &mut Vec<BFieldElement>,
         memory: &HashMap<BFieldElement, BFieldElement>,
     ) {
+        self.run(stack, memory);
+    }
+
+    /// Like [`apply`](Self::apply), but also applies the inner function's memory
+    /// writes, for example, to a list the inner function allocates.
+    pub fn apply_with_memory(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) {
+        *memory = self.run(stack, memory);
+    }
+
+    /// Computes the inner function, applies the resulting change to the given
+    /// stack, and returns the resulting memory.
+    fn run(
+        &self,
+        stack: &mut Vec<BFieldElement>,
+        memory: &HashMap<BFieldElement, BFieldElement>,
+    ) -> HashMap<BFieldElement, BFieldElement> {
         match &self {
             InnerFunction::RawCode(rc) => Self::run_vm(&rc.function, stack, memory),
             InnerFunction::NoFunctionBody(_lnat) => {
@@ -189,19 +279,20 @@ impl InnerFunction {
                     {&imports}
                 );
 
-                Self::run_vm(&code, stack, memory);
+                Self::run_vm(&code, stack, memory)
             }
-        };
+        }
     }
 
     /// Run the VM for on a given stack and memory to observe how it manipulates the
-    /// stack. This is a helper function for [`apply`](Self::apply), which in some cases
-    /// just grabs the inner function's code and then needs a VM to apply it.
+    /// stack and memory. This is a helper function for [`apply`](Self::apply), which
+    /// in some cases just grabs the inner function's code and then needs a VM to
+    /// apply it.
     fn run_vm(
         instructions: &[LabelledInstruction],
         stack: &mut Vec<BFieldElement>,
         memory: &HashMap<BFieldElement, BFieldElement>,
-    ) {
+    ) -> HashMap<BFieldElement, BFieldElement> {
         let Some(LabelledInstruction::Label(label)) = instructions.first() else {
             panic!();
         };
@@ -216,19 +307,82 @@ impl InnerFunction {
         vmstate.ram.clone_from(memory);
         vmstate.run().unwrap();
         *stack = vmstate.op_stack.stack;
+        vmstate.ram
     }
 }
 
+/// Copies an [environment](InnerFunction::environment) of the given size onto
+/// the stack.
+///
+/// ```text
+/// BEFORE: _ [environment] [_; num_registers]
+/// AFTER:  _ [environment] [_; num_registers] [environment]
+/// ```
+pub(crate) fn copy_environment(
+    environment_size: usize,
+    num_registers: usize,
+) -> Vec<LabelledInstruction> {
+    if environment_size == 0 {
+        return vec![];
+    }
+
+    let copy_deepest_word = triton_asm!(dup { num_registers + environment_size - 1 });
+    vec![copy_deepest_word; environment_size].concat()
+}
+
+/// Removes an [environment](InnerFunction::environment) of the given size from
+/// below some output.
+///
+/// ```text
+/// BEFORE: _ [environment] [output]
+/// AFTER:  _ [output]
+/// ```
+pub(crate) fn drop_environment(
+    environment_size: usize,
+    output_size: usize,
+) -> Vec<LabelledInstruction> {
+    if environment_size == 0 {
+        return vec![];
+    }
+
+    let pick_deepest_word = triton_asm!(pick { output_size + environment_size - 1 });
+    let pick_environment = vec![pick_deepest_word; environment_size].concat();
+    let pop_environment = (0..environment_size)
+        .step_by(5)
+        .flat_map(|i| triton_asm!(pop { (environment_size - i).min(5) }))
+        .collect();
+
+    [pick_environment, pop_environment].concat()
+}
+
 #[cfg(test)]
 mod tests {
+    use rand::prelude::*;
+
     use super::*;
 
+    impl InnerFunction {
+        /// A random [environment](Self::environment), in the order it is expected
+        /// on the stack.
+        pub(crate) fn seeded_random_environment(&self, rng: &mut impl Rng) -> Vec<BFieldElement> {
+            self.environment()
+                .iter()
+                .flat_map(|(data_type, _)| {
+                    let mut value = data_type.seeded_random_element(rng);
+                    value.reverse();
+                    value
+                })
+                .collect()
+        }
+    }
+
     #[test]
     fn breakpoint_does_not_influence_raw_code_inlining() {
         let raw_code = RawCode {
             function: triton_asm! { my_label: return break },
             input_type: DataType::VoidPointer,
             output_type: DataType::VoidPointer,
+            environment: vec![],
         };
         let inlined_code = raw_code.inlined_body().unwrap();
         assert_eq!(triton_asm!(), inlined_code);
@@ -240,6 +394,7 @@ mod tests {
             function: triton_asm! { my_label: hint a = stack[0] hint b = stack[1] return },
             input_type: DataType::VoidPointer,
             output_type: DataType::VoidPointer,
+            environment: vec![],
         };
         let inlined_code = raw_code.inlined_body().unwrap();
         assert_eq!(triton_asm!(), inlined_code);
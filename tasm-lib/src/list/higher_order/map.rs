@@ -1,5 +1,7 @@
 use itertools::Itertools;
 use strum::EnumCount;
+use tasm_lib::list::higher_order::inner_function::copy_environment;
+use tasm_lib::list::higher_order::inner_function::drop_environment;
 use tasm_lib::list::higher_order::inner_function::InnerFunction;
 use tasm_lib::structure::tasm_object::DEFAULT_MAX_DYN_FIELD_SIZE;
 use triton_vm::isa::op_stack::OpStackElement;
@@ -9,8 +11,8 @@ use crate::list::new::New;
 use crate::list::push::Push;
 use crate::prelude::*;
 
-const INNER_FN_INCORRECT_NUM_INPUTS: &str = "Inner function in `map` only works with *one* input. \
-                                             Use a tuple as a workaround.";
+const INNER_FN_INCORRECT_NUM_INPUTS: &str = "Inner function in `map` only works with *one* input \
+                                             besides its environment. Use a tuple as a workaround.";
 const INNER_FN_INCORRECT_INPUT_DYN_LEN: &str = "An input type of dynamic length to `map`s inner \
                                                 function must be a tuple of form `(bfe, _)`.";
 
@@ -53,7 +55,14 @@ pub type Map = ChainMap<1>;
 /// // _ <accessible> [_; ChainMap::<N>::NUM_INTERNAL_REGISTERS] *elem_i elem_i_len
 /// ```
 ///
+/// Instead of reaching past the internal registers, `f` can declare the runtime
+/// parameters it needs as its [environment][env]. The environment then becomes
+/// the first input of the chain map, is kept below the internal registers, and
+/// is copied onto the stack right below the input element for every invocation
+/// of `f`.
+///
 /// [len]: BFieldCodec::static_length
+/// [env]: InnerFunction::environment
 pub struct ChainMap<const NUM_INPUT_LISTS: usize> {
     f: InnerFunction,
 }
@@ -78,14 +87,23 @@ impl<const NUM_INPUT_LISTS: usize> ChainMap<NUM_INPUT_LISTS> {
     ///   `(_, `[`BFieldElement`][bfe]`)`
     /// - if the output type takes up [`OpStackElement::COUNT`]` - 1` or more words
     /// - if the output type does not have a [static length][len]
+    /// - if the [environment][env] is too large to be copied past the internal
+    ///   registers
     ///
     /// [len]: BFieldCodec::static_length
     /// [bfe]: DataType::Bfe
+    /// [env]: InnerFunction::environment
     pub fn new(f: InnerFunction) -> Self {
         let domain = f.domain();
+        let env_size = f.environment_size();
         if let Some(input_len) = domain.static_length() {
-            // need instruction `place {input_type.stack_size()}`
-            assert!(input_len < OpStackElement::COUNT);
+            // need instruction `place {input_type.stack_size() + env_size}`
+            assert!(input_len + env_size < OpStackElement::COUNT);
+
+            // need instruction `dup {Self::NUM_INTERNAL_REGISTERS + env_size - 1}`
+            assert!(
+                env_size == 0 || Self::NUM_INTERNAL_REGISTERS + env_size <= OpStackElement::COUNT
+            );
         } else {
             let DataType::Tuple(tuple) = domain else {
                 panic!("{INNER_FN_INCORRECT_INPUT_DYN_LEN}");
@@ -93,6 +111,12 @@ impl<const NUM_INPUT_LISTS: usize> ChainMap<NUM_INPUT_LISTS> {
             let [_, DataType::Bfe] = tuple[..] else {
                 panic!("{INNER_FN_INCORRECT_INPUT_DYN_LEN}");
             };
+
+            // need instruction `dup {Self::NUM_INTERNAL_REGISTERS + 3 + env_size - 1}`
+            assert!(
+                env_size == 0
+                    || Self::NUM_INTERNAL_REGISTERS + 3 + env_size <= OpStackElement::COUNT
+            );
         }
 
         // need instruction `pick {output_type.stack_size() + 1}`
@@ -109,9 +133,13 @@ impl<const NUM_INPUT_LISTS: usize> ChainMap<NUM_INPUT_LISTS> {
 impl<const NUM_INPUT_LISTS: usize> BasicSnippet for ChainMap<NUM_INPUT_LISTS> {
     fn inputs(&self) -> Vec<(DataType, String)> {
         let list_type = DataType::List(Box::new(self.f.domain()));
+        let input_lists =
+            (0..NUM_INPUT_LISTS).map(|i| (list_type.clone(), format!("*input_list_{i}")));
 
-        (0..NUM_INPUT_LISTS)
-            .map(|i| (list_type.clone(), format!("*input_list_{i}")))
+        self.f
+            .environment()
+            .into_iter()
+            .chain(input_lists)
             .collect_vec()
     }
 
@@ -166,6 +194,13 @@ impl<const NUM_INPUT_LISTS: usize> ChainMap<NUM_INPUT_LISTS> {
             n => triton_asm!(addi {-(n as i32 - 1)}),
         };
 
+        let env_size = self.f.environment_size();
+        let copy_environment = copy_environment(env_size, Self::NUM_INTERNAL_REGISTERS);
+        let pick_input_elem_pointer = match env_size {
+            0 => triton_asm!(),
+            n => triton_asm!(pick { n }),
+        };
+
         let main_loop_body = triton_asm! {
             // INVARIANT: _ *end_condition_in_list *output_elem *input_elem
 
@@ -176,9 +211,12 @@ impl<const NUM_INPUT_LISTS: usize> ChainMap<NUM_INPUT_LISTS> {
             skiz return
 
             /* read */
+            {&copy_environment}
+            {&pick_input_elem_pointer}
+                        // _ *end_condition_in_list *output_elem [env] *input_elem
             {&input_type.read_value_from_memory_leave_pointer()}
-            place {input_type.stack_size()}
-                        // _ *end_condition_in_list *output_elem *prev_input_elem [input_elem]
+            place {input_type.stack_size() + env_size}
+                        // _ *end_condition_in_list *output_elem *prev_input_elem [env] [input_elem]
 
             /* map */
             {&inner_fn.exec_or_call}
@@ -253,6 +291,7 @@ impl<const NUM_INPUT_LISTS: usize> ChainMap<NUM_INPUT_LISTS> {
                 {&map_all_lists}
                 place {NUM_INPUT_LISTS}
                 {&Self::pop_input_lists()}
+                {&drop_environment(self.f.environment_size(), 1)}
                 return
             {main_loop_fn}:
                 {&main_loop_body}
@@ -272,6 +311,15 @@ impl<const NUM_INPUT_LISTS: usize> ChainMap<NUM_INPUT_LISTS> {
         let entrypoint = self.entrypoint();
         let main_loop_fn = format!("{entrypoint}_loop");
 
+        // the registers `fill i in_list_len *out_list *in_list[i+1]_si` come on top
+        // of the input lists, and the inner function's input takes up 2 words
+        let env_size = self.f.environment_size();
+        let copy_environment = copy_environment(env_size, Self::NUM_INTERNAL_REGISTERS + 3);
+        let pick_input_elem = match env_size {
+            0 => triton_asm!(),
+            n => triton_asm!(pick {n + 1} pick {n + 1}),
+        };
+
         let main_loop_body = triton_asm! {
             //                ⬐ for Self::NUM_INTERNAL_REGISTERS
             // BEFORE:    _ fill 0           in_list_len *out_list *in_list[0]_si
@@ -303,6 +351,9 @@ impl<const NUM_INPUT_LISTS: usize> ChainMap<NUM_INPUT_LISTS> {
 
             /* prepare for inner function */
             place 1     // _ fill i in_list_len *out_list *in_list[i+1]_si *in_list[i] l[i]_len
+            {&copy_environment}
+            {&pick_input_elem}
+                        // _ fill i in_list_len *out_list *in_list[i+1]_si [env] *in_list[i] l[i]_len
 
             /* map */
             {&inner_fn.exec_or_call}
@@ -362,6 +413,7 @@ impl<const NUM_INPUT_LISTS: usize> ChainMap<NUM_INPUT_LISTS> {
                 {&map_all_lists}
                 place {NUM_INPUT_LISTS}
                 {&Self::pop_input_lists()}
+                {&drop_environment(self.f.environment_size(), 1)}
                 return
             {main_loop_fn}:
                 {&main_loop_body}
@@ -378,7 +430,12 @@ impl<const NUM_INPUT_LISTS: usize> ChainMap<NUM_INPUT_LISTS> {
                     .unwrap_or(triton_asm!(call {code.entrypoint()}))
             }
             InnerFunction::BasicSnippet(snippet) => {
-                assert_eq!(1, snippet.inputs().len(), "{INNER_FN_INCORRECT_NUM_INPUTS}");
+                let num_inputs = self.f.environment().len() + 1;
+                assert_eq!(
+                    num_inputs,
+                    snippet.inputs().len(),
+                    "{INNER_FN_INCORRECT_NUM_INPUTS}"
+                );
                 let labelled_instructions = snippet.annotated_code(library);
                 let label = library.explicit_import(&snippet.entrypoint(), &labelled_instructions);
                 triton_asm!(call { label })
@@ -475,6 +532,7 @@ mod tests {
             let input_list_pointers = (0..NUM_INPUT_LISTS)
                 .map(|_| stack.pop().unwrap())
                 .collect_vec();
+            let environment = stack.split_off(stack.len() - self.f.environment_size());
 
             // the inner function _must not_ rely on these elements
             let buffer = (0..Self::NUM_INTERNAL_REGISTERS).map(|_| rand::random::<BFieldElement>());
@@ -488,6 +546,7 @@ mod tests {
                 list_set_length(output_list_pointer, new_output_list_len, memory);
 
                 for i in (0..input_list_len).rev() {
+                    stack.extend_from_slice(&environment);
                     if input_type.static_length().is_some() {
                         let elem = list_get(input_list_pointer, i, memory, input_type.stack_size());
                         stack.extend(elem.into_iter().rev());
@@ -532,8 +591,43 @@ mod tests {
                 Some(BenchmarkCase::WorstCase) => [100; NUM_INPUT_LISTS],
             };
             let list_lengths = list_lengths.map(Into::into);
+            let list_seed = rng.random();
+
+            let environment = self.f.seeded_random_environment(&mut rng);
+            let environment_args = environment_args.into_iter().chain(environment);
+
+            self.init_state(environment_args, list_lengths, list_seed)
+        }
+    }
+
+    /// Multiplies an element with a scalar that is passed as the environment.
+    #[derive(Debug, Clone)]
+    struct TestScaleXFieldElement;
+
+    impl BasicSnippet for TestScaleXFieldElement {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            vec![
+                (DataType::Xfe, "scalar".to_string()),
+                (DataType::Xfe, "element".to_string()),
+            ]
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            vec![(DataType::Xfe, "scaled_element".to_string())]
+        }
+
+        fn entrypoint(&self) -> String {
+            "test_scale_xfe".to_string()
+        }
 
-            self.init_state(environment_args, list_lengths, rng.random())
+        fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+            triton_asm!(
+                // BEFORE: _ [scalar: Xfe] [element: Xfe]
+                // AFTER:  _ [scalar * element: Xfe]
+                {self.entrypoint()}:
+                    xx_mul
+                    return
+            )
         }
     }
 
@@ -781,6 +875,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_with_raw_function_add_offset_from_environment() {
+        fn test_case<const N: usize>() {
+            let raw_code = RawCode::new(
+                triton_asm!(add_offset: add return),
+                DataType::Bfe,
+                DataType::Bfe,
+            )
+            .with_environment(vec![(DataType::Bfe, "offset".to_string())]);
+            let snippet = ChainMap::<N>::new(InnerFunction::RawCode(raw_code));
+            ShadowedFunction::new(snippet).test();
+        }
+
+        test_case::<0>();
+        test_case::<1>();
+        test_case::<2>();
+        test_case::<5>();
+        test_case::<7>();
+        test_case::<12>();
+    }
+
+    #[test]
+    fn test_with_basic_snippet_scaling_by_environment() {
+        let f = || InnerFunction::BasicSnippet(Box::new(TestScaleXFieldElement));
+        assert_eq!(3, f().environment_size());
+
+        ShadowedFunction::new(ChainMap::<0>::new(f())).test();
+        ShadowedFunction::new(ChainMap::<1>::new(f())).test();
+        ShadowedFunction::new(ChainMap::<3>::new(f())).test();
+        ShadowedFunction::new(ChainMap::<10>::new(f())).test();
+    }
+
+    #[test]
+    fn mapping_over_dynamic_length_items_with_environment_works() {
+        fn test_case<const N: usize>() {
+            let list_type = DataType::List(Box::new(DataType::Bfe));
+            let raw_code = RawCode::new(
+                triton_asm!(write_environment: pop 2 return),
+                DataType::Tuple(vec![list_type, DataType::Bfe]),
+                DataType::Digest,
+            )
+            .with_environment(vec![(DataType::Digest, "constant".to_string())]);
+            let snippet = ChainMap::<N>::new(InnerFunction::RawCode(raw_code));
+            ShadowedFunction::new(snippet).test();
+        }
+
+        test_case::<0>();
+        test_case::<1>();
+        test_case::<2>();
+        test_case::<5>();
+    }
+
     #[proptest(cases = 10)]
     fn num_internal_registers_is_correct(#[strategy(arb())] guard: BFieldElement) {
         fn test_case<const N: usize>(guard: BFieldElement) {
@@ -0,0 +1,437 @@
+use strum::EnumCount;
+use triton_vm::isa::op_stack::OpStackElement;
+use triton_vm::prelude::*;
+
+use super::inner_function::copy_environment;
+use super::inner_function::drop_environment;
+use super::inner_function::InnerFunction;
+use crate::prelude::*;
+
+/// Finds the index of the first element of a list that satisfies a predicate.
+///
+/// Stops at the first element that satisfies the predicate; later elements are
+/// neither read nor passed to the predicate. The list's elements must have a
+/// [statically known][len] length. The predicate's [environment][env], if any,
+/// is passed to every invocation of the predicate.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ [environment] *list
+/// AFTER:  _ index is_some
+/// ```
+///
+/// If no element satisfies the predicate, `is_some` is `false` and `index` is
+/// the length of the list.
+///
+/// [len]: BFieldCodec::static_length
+/// [env]: InnerFunction::environment
+pub struct Position {
+    pub f: InnerFunction,
+}
+
+impl Position {
+    pub fn new(f: InnerFunction) -> Self {
+        assert_predicate_on_static_length_elements(&f);
+        Self { f }
+    }
+}
+
+impl BasicSnippet for Position {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let list_type = DataType::List(Box::new(self.f.domain()));
+        let list = (list_type, "*list".to_string());
+
+        [self.f.environment(), vec![list]].concat()
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::U32, "index".to_string()),
+            (DataType::Bool, "is_some".to_string()),
+        ]
+    }
+
+    fn entrypoint(&self) -> String {
+        format!(
+            "tasmlib_list_higher_order_u32_position_{}",
+            self.f.entrypoint()
+        )
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let element_size = self.f.domain().stack_size();
+        let entrypoint = self.entrypoint();
+        let main_loop = format!("{entrypoint}_loop");
+
+        let div_elem_size = match element_size {
+            1 => triton_asm!(),
+            n => triton_asm!(push {n} pick 1 div_mod pop 1),
+        };
+
+        triton_asm!(
+            // BEFORE: _ [env] *list
+            // AFTER:  _ index is_some
+            {entrypoint}:
+                dup 0
+                {&pointer_to_end_of_list(element_size)}
+                dup 1       // _ *list *end *list
+
+                call {main_loop}
+                            // _ *list *end *p
+
+                dup 0
+                pick 2
+                eq
+                push 0
+                eq          // _ *list *p is_some
+
+                place 2
+                pick 1
+                push -1
+                mul
+                add         // _ is_some (*p - *list)
+
+                {&div_elem_size}
+                pick 1      // _ index is_some
+                {&drop_environment(self.f.environment_size(), 2)}
+                return
+
+            {&first_match_loop(&self.f, &main_loop, 3, library)}
+        )
+    }
+}
+
+/// # Panics
+///
+/// - if the predicate does not return a [`Bool`](DataType::Bool)
+/// - if the predicate's input does not have a [static length][len]
+/// - if the predicate's input has length 0
+/// - if the predicate's input and [environment][env] are too large to be
+///   handled on the stack
+///
+/// [len]: BFieldCodec::static_length
+/// [env]: InnerFunction::environment
+pub(super) fn assert_predicate_on_static_length_elements(f: &InnerFunction) {
+    assert_eq!(DataType::Bool, f.range(), "predicate must return a bool");
+    let element_len = f
+        .domain()
+        .static_length()
+        .expect("element type's encoding length must be static");
+    assert_ne!(0, element_len, "element type must not be empty");
+
+    // need instructions `dup {num_registers + env_size - 1}` for at most 3
+    // registers, and `pick {element_len + env_size}`
+    let env_size = f.environment_size();
+    assert!(env_size + 3 <= OpStackElement::COUNT);
+    assert!(element_len + env_size < OpStackElement::COUNT);
+}
+
+/// ```text
+/// BEFORE: _ *list
+/// AFTER:  _ *last_elem_last_word
+/// ```
+pub(super) fn pointer_to_end_of_list(element_size: usize) -> Vec<LabelledInstruction> {
+    let mul_elem_size = match element_size {
+        1 => triton_asm!(),
+        n => triton_asm!(push {n} mul),
+    };
+
+    triton_asm!(
+        dup 0
+        read_mem 1
+        pop 1       // _ *list len
+        {&mul_elem_size}
+        add         // _ *last_elem_last_word
+    )
+}
+
+/// The loop shared by [`Position`], [`Any`][any], and [`FindFirst`][find_first].
+/// Includes the inner function's body, if it needs one.
+///
+/// Returns as soon as the predicate `f` is satisfied by some element. That
+/// element's last word is then at address `*p + element_size`. If no element
+/// satisfies `f`, then `*p` equals `*end`.
+///
+/// The predicate's [environment](InnerFunction::environment) is expected right
+/// below the `num_registers` topmost stack elements, which include `*end` and
+/// `*list`.
+///
+/// ```text
+/// BEFORE: _ *end *list
+/// AFTER:  _ *end *p
+/// ```
+///
+/// [any]: super::any::Any
+/// [find_first]: super::find_first::FindFirst
+pub(super) fn first_match_loop(
+    f: &InnerFunction,
+    loop_label: &str,
+    num_registers: usize,
+    library: &mut Library,
+) -> Vec<LabelledInstruction> {
+    let element_type = f.domain();
+    let element_size = element_type.stack_size();
+    let env_size = f.environment_size();
+
+    let (exec_or_call, fn_body) = match f {
+        InnerFunction::RawCode(code) => {
+            // Inlining saves two clock cycles per iteration. If the function cannot be
+            // inlined, it needs to be appended to the function body.
+            match code.inlined_body() {
+                Some(body) => (body, vec![]),
+                None => (triton_asm!(call {code.entrypoint()}), code.function.clone()),
+            }
+        }
+        InnerFunction::BasicSnippet(snippet) => {
+            let labelled_instructions = snippet.annotated_code(library);
+            let label = library.explicit_import(&snippet.entrypoint(), &labelled_instructions);
+            (triton_asm!(call { label }), vec![])
+        }
+        InnerFunction::NoFunctionBody(lnat) => (triton_asm!(call { lnat.label_name }), vec![]),
+    };
+
+    triton_asm!(
+        // INVARIANT: _ *end *prev_elem_last_word
+        {loop_label}:
+            dup 1
+            dup 1
+            eq
+            skiz return
+
+            {&copy_environment(env_size, num_registers)}
+            dup {env_size}
+            addi {element_size}
+            {&element_type.read_value_from_memory_pop_pointer()}
+                        // _ *end *prev_elem_last_word [env] [elem]
+
+            {&exec_or_call}
+                        // _ *end *prev_elem_last_word is_match
+
+            skiz return
+            addi {element_size}
+            recurse
+
+        {&fn_body}
+    )
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::empty_stack;
+    use crate::list::higher_order::all::tests::TestHashXFieldElementLsb;
+    use crate::list::higher_order::inner_function::RawCode;
+    use crate::rust_shadowing_helper_functions::list::list_get;
+    use crate::rust_shadowing_helper_functions::list::list_get_length;
+    use crate::test_helpers::test_rust_equivalence_given_complete_state;
+    use crate::test_prelude::*;
+
+    /// Apply the predicate `f` to the elements of the list, in order, until it is
+    /// satisfied. Returns the index of the satisfying element, if any. Consumes
+    /// the predicate's environment from the top of the stack.
+    pub(crate) fn first_match(
+        f: &InnerFunction,
+        list_pointer: BFieldElement,
+        stack: &mut Vec<BFieldElement>,
+        memory: &mut HashMap<BFieldElement, BFieldElement>,
+    ) -> Option<usize> {
+        let element_size = f.domain().stack_size();
+        let environment = stack.split_off(stack.len() - f.environment_size());
+        (0..list_get_length(list_pointer, memory)).find(|&i| {
+            let element = list_get(list_pointer, i, memory, element_size);
+            stack.extend_from_slice(&environment);
+            stack.extend(element.into_iter().rev());
+            f.apply(stack, memory);
+            stack.pop().unwrap().value() != 0
+        })
+    }
+
+    pub(crate) fn initial_state(
+        f: &InnerFunction,
+        seed: [u8; 32],
+        bench_case: Option<BenchmarkCase>,
+    ) -> FunctionInitialState {
+        let mut rng = StdRng::from_seed(seed);
+        let list_length = match bench_case {
+            Some(BenchmarkCase::CommonCase) => 10,
+            Some(BenchmarkCase::WorstCase) => 100,
+            None => rng.random_range(0..50),
+        };
+        let list_pointer = bfe!(rng.random_range(0..1_u64 << 30));
+        let list = f.domain().random_list(&mut rng, list_length);
+        let environment = f.seeded_random_environment(&mut rng);
+
+        initial_state_from_encoded_list(environment, list_pointer, list)
+    }
+
+    pub(crate) fn initial_state_from_encoded_list(
+        environment: Vec<BFieldElement>,
+        list_pointer: BFieldElement,
+        list: Vec<BFieldElement>,
+    ) -> FunctionInitialState {
+        let memory = (0..).map(|i| list_pointer + bfe!(i)).zip(list).collect();
+        let stack = [empty_stack(), environment, vec![list_pointer]].concat();
+
+        FunctionInitialState { stack, memory }
+    }
+
+    pub(crate) fn lt_2_pow_28() -> InnerFunction {
+        InnerFunction::RawCode(RawCode::new(
+            triton_asm!(lt_2_pow_28: push {1_u32 << 28} pick 1 lt return),
+            DataType::U32,
+            DataType::Bool,
+        ))
+    }
+
+    pub(crate) fn never_on_xfe() -> InnerFunction {
+        InnerFunction::RawCode(RawCode::new(
+            triton_asm!(never: pop 3 push 0 return),
+            DataType::Xfe,
+            DataType::Bool,
+        ))
+    }
+
+    pub(crate) fn always_on_digest() -> InnerFunction {
+        InnerFunction::RawCode(RawCode::new(
+            triton_asm!(always: pop 5 push 1 return),
+            DataType::Digest,
+            DataType::Bool,
+        ))
+    }
+
+    /// Is `true` iff the element equals the `needle` in the environment.
+    pub(crate) fn eq_to_environment() -> InnerFunction {
+        let raw_code = RawCode::new(
+            triton_asm!(eq_to_environment: eq return),
+            DataType::U32,
+            DataType::Bool,
+        );
+        InnerFunction::RawCode(
+            raw_code.with_environment(vec![(DataType::U32, "needle".to_string())]),
+        )
+    }
+
+    /// Is `true` for 0, `false` for other u32s, and crashes the VM for everything
+    /// else.
+    pub(crate) fn is_zero_or_crash() -> InnerFunction {
+        InnerFunction::RawCode(RawCode::new(
+            triton_asm!(is_zero_or_crash: dup 0 split pop 1 push 0 eq assert push 0 eq return),
+            DataType::Bfe,
+            DataType::Bool,
+        ))
+    }
+
+    impl Function for Position {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let list_pointer = stack.pop().unwrap();
+            let list_length = list_get_length(list_pointer, memory);
+            let position = first_match(&self.f, list_pointer, stack, memory);
+
+            stack.push(bfe!(position.unwrap_or(list_length)));
+            stack.push(bfe!(position.is_some() as u64));
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            initial_state(&self.f, seed, bench_case)
+        }
+    }
+
+    #[test]
+    fn rust_shadow_with_basic_snippet() {
+        let f = InnerFunction::BasicSnippet(Box::new(TestHashXFieldElementLsb));
+        ShadowedFunction::new(Position::new(f)).test();
+    }
+
+    #[test]
+    fn rust_shadow_with_raw_code() {
+        ShadowedFunction::new(Position::new(lt_2_pow_28())).test();
+        ShadowedFunction::new(Position::new(never_on_xfe())).test();
+        ShadowedFunction::new(Position::new(always_on_digest())).test();
+    }
+
+    #[test]
+    fn rust_shadow_with_environment() {
+        ShadowedFunction::new(Position::new(eq_to_environment())).test();
+    }
+
+    #[test]
+    fn elements_after_first_match_are_not_inspected() {
+        let snippet = Position::new(is_zero_or_crash());
+        let list = vec![bfe!(5), bfe!(0), bfe!(1_u64 << 40)];
+        let initial_state = initial_state_from_encoded_list(vec![], bfe!(42), list.encode());
+
+        let expected_stack = [empty_stack(), bfe_vec![1, 1]].concat();
+        test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            &None,
+            Some(&expected_stack),
+        );
+    }
+
+    #[proptest(cases = 20)]
+    fn position_agrees_with_host_position(
+        #[strategy(prop::collection::vec(arb(), 0..50))] list: Vec<u32>,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let snippet = Position::new(lt_2_pow_28());
+        let initial_state = initial_state_from_encoded_list(vec![], list_pointer, list.encode());
+
+        let position = list.iter().position(|&x| x < 1 << 28);
+        let index = position.unwrap_or(list.len());
+        let expected_stack = [empty_stack(), bfe_vec![index, position.is_some() as u64]].concat();
+        test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            &None,
+            Some(&expected_stack),
+        );
+    }
+
+    #[proptest(cases = 20)]
+    fn position_of_needle_from_environment_agrees_with_host_position(
+        #[strategy(prop::collection::vec(0_u32..10, 0..50))] list: Vec<u32>,
+        #[strategy(0_u32..10)] needle: u32,
+    ) {
+        let snippet = Position::new(eq_to_environment());
+        let initial_state =
+            initial_state_from_encoded_list(bfe_vec![needle], bfe!(42), list.encode());
+
+        let position = list.iter().position(|&x| x == needle);
+        let index = position.unwrap_or(list.len());
+        let expected_stack = [empty_stack(), bfe_vec![index, position.is_some() as u64]].concat();
+        test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            &None,
+            Some(&expected_stack),
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::tests::never_on_xfe;
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(Position::new(never_on_xfe())).bench();
+    }
+}
@@ -0,0 +1,320 @@
+use triton_vm::prelude::*;
+
+use crate::list::get::Get;
+use crate::prelude::*;
+
+/// Insert an element into a list at position `index`, shifting all elements
+/// after it to the right. Mimics [`Vec::insert`].
+///
+/// Only supports lists with [statically sized](BFieldCodec::static_length)
+/// elements. The element's static size must be in range `1..=14`.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *list [index: u32] [element: ElementType]
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// - the argument `*list` points to a properly [`BFieldCodec`]-encoded list
+/// - all input arguments are properly [`BFieldCodec`] encoded
+///
+/// ### Postconditions
+///
+/// - `*list` points to a properly [`BFieldCodec`]-encoded list
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Insert {
+    element_type: DataType,
+}
+
+impl Insert {
+    pub const INDEX_OUT_OF_BOUNDS_ERROR_ID: i128 = 710;
+
+    /// Any part of the list is outside the allocated memory page.
+    /// See the [memory convention][crate::memory] for more details.
+    pub const MEM_PAGE_ACCESS_VIOLATION_ERROR_ID: i128 = 711;
+
+    /// # Panics
+    ///
+    /// Panics
+    /// - if the element has [dynamic length][BFieldCodec::static_length], or
+    /// - if the static length is 0, or
+    /// - if the static length is larger than 14.
+    pub fn new(element_type: DataType) -> Self {
+        // need to access arguments `*list` and `index`
+        assert!(element_type.stack_size() <= 14);
+        Get::assert_element_type_is_supported(&element_type);
+
+        Self { element_type }
+    }
+}
+
+impl BasicSnippet for Insert {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let element_type = self.element_type.clone();
+        let list_type = DataType::List(Box::new(element_type.clone()));
+
+        vec![
+            (list_type, "*list".to_string()),
+            (DataType::U32, "index".to_string()),
+            (element_type, "element".to_string()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        let element_type = self.element_type.label_friendly_name();
+        format!("tasmlib_list_insert___{element_type}")
+    }
+
+    fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+        let element_size = self.element_type.stack_size();
+        let mul_with_element_size = match element_size {
+            1 => triton_asm!(), // no-op
+            n => triton_asm!(push {n} mul),
+        };
+
+        let entrypoint = self.entrypoint();
+        let shift_right_loop = format!("{entrypoint}_shift_right");
+
+        triton_asm!(
+            // BEFORE: _ *list index [element]
+            // AFTER:  _
+            {entrypoint}:
+                pick {element_size + 1}
+                pick {element_size + 1}
+                                    // _ [element] *list index
+                dup 1
+                read_mem 1
+                pop 1               // _ [element] *list index len
+
+                /* assert index is in bounds */
+                dup 1
+                dup 1
+                lt                  // _ [element] *list index len (len < index)
+                push 0
+                eq
+                assert error_id {Self::INDEX_OUT_OF_BOUNDS_ERROR_ID}
+                                    // _ [element] *list index len
+
+                /* assert access is within one memory page */
+                dup 0
+                addi 1              // _ [element] *list index len new_len
+                dup 0
+                {&mul_with_element_size}
+                split
+                pop 1
+                push 0
+                eq
+                assert error_id {Self::MEM_PAGE_ACCESS_VIOLATION_ERROR_ID}
+                                    // _ [element] *list index len new_len
+
+                /* update list length */
+                dup 3
+                write_mem 1
+                pop 1               // _ [element] *list index len
+
+                /* shift elements at and after index to the right */
+                pick 1
+                {&mul_with_element_size}
+                dup 2
+                add                 // _ [element] *list len *stop
+                pick 1
+                {&mul_with_element_size}
+                pick 2
+                add                 // _ [element] *stop *last_word
+                call {shift_right_loop}
+                                    // _ [element] *stop *stop
+
+                /* write element */
+                pop 1
+                addi 1              // _ [element] *list[index]
+                {&self.element_type.write_value_to_memory_pop_pointer()}
+                                    // _
+                return
+
+            // INVARIANT: _ *stop *word
+            {shift_right_loop}:
+                dup 1
+                dup 1
+                eq
+                skiz return
+
+                read_mem 1          // _ *stop word (*word - 1)
+                pick 1
+                dup 1
+                addi {element_size + 1}
+                                    // _ *stop (*word - 1) word (*word + element_size)
+                write_mem 1
+                pop 1               // _ *stop (*word - 1)
+                recurse
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::strategy::Union;
+
+    use super::*;
+    use crate::rust_shadowing_helper_functions::list::insert_random_list;
+    use crate::rust_shadowing_helper_functions::list::list_insert;
+    use crate::rust_shadowing_helper_functions::list::list_insert_at;
+    use crate::rust_shadowing_helper_functions::list::load_list_with_copy_elements;
+    use crate::test_helpers::test_assertion_failure;
+    use crate::test_helpers::test_rust_equivalence_given_complete_state;
+    use crate::test_prelude::*;
+    use crate::U32_TO_USIZE_ERR;
+
+    impl Insert {
+        fn set_up_initial_state(
+            &self,
+            list_length: usize,
+            index: usize,
+            list_pointer: BFieldElement,
+            element: Vec<BFieldElement>,
+        ) -> FunctionInitialState {
+            let mut memory = HashMap::default();
+            insert_random_list(&self.element_type, list_pointer, list_length, &mut memory);
+
+            let mut stack = self.init_stack_for_isolated_run();
+            stack.push(list_pointer);
+            stack.push(bfe!(index));
+            stack.extend(element.into_iter().rev());
+
+            FunctionInitialState { stack, memory }
+        }
+    }
+
+    impl Function for Insert {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let element = (0..self.element_type.stack_size())
+                .map(|_| stack.pop().unwrap())
+                .collect_vec();
+            let index = pop_encodable::<u32>(stack)
+                .try_into()
+                .expect(U32_TO_USIZE_ERR);
+            let list_pointer = stack.pop().unwrap();
+
+            list_insert_at(list_pointer, index, element, memory);
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let (list_length, index) = match bench_case {
+                Some(BenchmarkCase::CommonCase) => (32, 16),
+                Some(BenchmarkCase::WorstCase) => (64, 0),
+                None => {
+                    let list_length = rng.random_range(0..=100);
+                    (list_length, rng.random_range(0..=list_length))
+                }
+            };
+            let list_pointer = rng.random();
+            let element = self.element_type.seeded_random_element(&mut rng);
+
+            self.set_up_initial_state(list_length, index, list_pointer, element)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let list_pointer = bfe!(1_u64 << 32);
+            let element = vec![bfe!(1); self.element_type.stack_size()];
+
+            [(0, 0), (1, 0), (1, 1), (2, 1)]
+                .map(|(list_length, index)| {
+                    self.set_up_initial_state(list_length, index, list_pointer, element.clone())
+                })
+                .to_vec()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        for ty in [
+            DataType::Bool,
+            DataType::Bfe,
+            DataType::U32,
+            DataType::U64,
+            DataType::Xfe,
+            DataType::Digest,
+        ] {
+            ShadowedFunction::new(Insert::new(ty)).test();
+        }
+    }
+
+    #[proptest]
+    fn inserting_agrees_with_host_insert(
+        #[strategy(prop::collection::vec(arb(), 0..50))] list: Vec<XFieldElement>,
+        #[strategy(0..=#list.len())] index: usize,
+        #[strategy(arb())] element: XFieldElement,
+    ) {
+        let list_pointer = bfe!(1_u64 << 32);
+        let mut memory = HashMap::default();
+        list_insert(list_pointer, list.clone(), &mut memory);
+
+        let snippet = Insert::new(DataType::Xfe);
+        let mut stack = snippet.init_stack_for_isolated_run();
+        let expected_stack = stack.clone();
+        stack.push(list_pointer);
+        stack.push(bfe!(index));
+        stack.extend(element.coefficients.into_iter().rev());
+
+        let final_state = test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &stack,
+            &[],
+            &NonDeterminism::default().with_ram(memory),
+            &None,
+            Some(&expected_stack),
+        );
+
+        let mut list = list.into_iter().map(|x| x.coefficients).collect_vec();
+        list.insert(index, element.coefficients);
+        let final_list = load_list_with_copy_elements(list_pointer, &final_state.ram);
+        prop_assert_eq!(list, final_list);
+    }
+
+    #[proptest]
+    fn out_of_bounds_index_crashes_vm(
+        #[strategy(Union::new(
+            [DataType::U32, DataType::U64, DataType::Xfe, DataType::Digest].map(Just)
+        ))]
+        element_type: DataType,
+        #[strategy(0_usize..100)] list_length: usize,
+        #[strategy(#list_length + 1..1 << 32)] index: usize,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let snippet = Insert::new(element_type);
+        let element = vec![bfe!(0); snippet.element_type.stack_size()];
+        let initial_state = snippet.set_up_initial_state(list_length, index, list_pointer, element);
+
+        test_assertion_failure(
+            &ShadowedFunction::new(snippet),
+            initial_state.into(),
+            &[Insert::INDEX_OUT_OF_BOUNDS_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(Insert::new(DataType::Digest)).bench();
+    }
+}
@@ -0,0 +1,490 @@
+use triton_vm::prelude::*;
+
+use crate::hashing::algebraic_hasher::hash_varlen::HashVarlen;
+use crate::list::get::Get;
+use crate::prelude::*;
+
+/// Determine whether two lists are equal up to permutation.
+///
+/// Supports lists with elements of any [statically sized][static] type. Both
+/// lists are hashed to derive two challenges, `α` and `x`, by Fiat-Shamir.
+/// Every element is compressed into a single [`XFieldElement`] by weighing its
+/// words with the powers of `α`, _i.e._, by evaluating the polynomial defined
+/// by its words in `α`. The running products of `(compressed_element - x)`
+/// over both lists are then compared.
+///
+/// For lists of [`u64`]s and [`Digest`]s, the specialized
+/// [`MultisetEqualityU64s`] and [`MultisetEqualityDigests`] are cheaper.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *list_a *list_b
+/// AFTER:  _ [multisets_are_equal: bool]
+/// ```
+///
+/// ### Preconditions
+///
+/// - the arguments `*list_a` and `*list_b` point to properly
+///   [`BFieldCodec`]-encoded lists
+///
+/// ### Postconditions
+///
+/// None.
+///
+/// [static]: BFieldCodec::static_length
+/// [`MultisetEqualityU64s`]: crate::list::multiset_equality_u64s::MultisetEqualityU64s
+/// [`MultisetEqualityDigests`]: crate::list::multiset_equality_digests::MultisetEqualityDigests
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct MultisetEquality {
+    element_type: DataType,
+}
+
+impl MultisetEquality {
+    /// # Panics
+    ///
+    /// Panics if the element has [dynamic length][BFieldCodec::static_length], or
+    /// if the static length is 0.
+    pub fn new(element_type: DataType) -> Self {
+        Get::assert_element_type_is_supported(&element_type);
+
+        Self { element_type }
+    }
+
+    fn element_size(&self) -> usize {
+        self.element_type.static_length().unwrap()
+    }
+}
+
+impl BasicSnippet for MultisetEquality {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let list_type = DataType::List(Box::new(self.element_type.clone()));
+
+        vec![
+            (list_type.clone(), "*list_a".to_string()),
+            (list_type, "*list_b".to_string()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::Bool, "multisets_are_equal".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        let element_type = self.element_type.label_friendly_name();
+        format!("tasmlib_list_multiset_equality___{element_type}")
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let hash_varlen = library.import(Box::new(HashVarlen));
+        let compare_xfes = DataType::Xfe.compare();
+
+        let not_equal_length = format!("{entrypoint}_not_equal_length");
+        let equal_length = format!("{entrypoint}_equal_length");
+        let running_product_loop = format!("{entrypoint}_running_product");
+
+        let element_size = self.element_size();
+        let horner_step = triton_asm!(
+            // _ [-x] [α] *end *word [rp] [acc]
+            dup 10
+            dup 10
+            dup 10
+            xx_mul              // _ [-x] [α] *end *word [rp] [acc·α]
+            dup 6
+            read_mem 1          // _ [-x] [α] *end *word [rp] [acc·α] word (*word - 1)
+            swap 8
+            pop 1
+            add                 // _ [-x] [α] *end (*word - 1) [rp] [acc·α + word]
+        );
+        let compress_element = triton_asm!(
+            // _ [-x] [α] *end *element_last_word [rp]
+            push 0
+            push 0
+            dup 5
+            read_mem 1
+            swap 7
+            pop 1               // _ [-x] [α] *end *word [rp] [acc]
+            {&vec![horner_step; element_size - 1].concat()}
+                                // _ [-x] [α] *end *previous_element_last_word [rp] [compressed_element]
+        );
+
+        triton_asm!(
+            // BEFORE: _ *list_a *list_b
+            // AFTER:  _ multisets_are_equal
+            {entrypoint}:
+                dup 1
+                read_mem 1
+                pop 1
+                dup 1
+                read_mem 1
+                pop 1               // _ *list_a *list_b len_a len_b
+                dup 1
+                eq                  // _ *list_a *list_b len_a (len_a == len_b)
+                push 1
+                swap 1
+                push 0
+                eq                  // _ *list_a *list_b len_a 1 (len_a != len_b)
+                skiz call {not_equal_length}
+                skiz call {equal_length}
+                                    // _ multisets_are_equal
+                return
+
+            // BEFORE: _ *list_a *list_b len 1
+            // AFTER:  _ 0 0
+            {not_equal_length}:
+                pop 4
+                push 0              // _ 0
+
+                /* ensure `else` branch is not taken */
+                push 0
+                return
+
+            // BEFORE: _ *list_a *list_b len
+            // AFTER:  _ multisets_are_equal
+            {equal_length}:
+                push {element_size}
+                mul                 // _ *list_a *list_b size
+
+                /* hash both lists, including their length indicators */
+                dup 2
+                dup 1
+                addi 1
+                call {hash_varlen}  // _ *list_a *list_b size [a_digest]
+                dup 6
+                dup 6
+                addi 1
+                call {hash_varlen}  // _ *list_a *list_b size [a_digest] [b_digest]
+                hash                // _ *list_a *list_b size [digest]
+
+                /* derive challenges */
+                dup 4
+                dup 4
+                dup 4
+                dup 4
+                dup 4
+                push 0
+                push 0
+                push 0
+                push 0
+                push 0
+                hash                // _ *list_a *list_b size [digest] [another_digest]
+                pop 2
+                pick 7
+                pick 7
+                pop 2               // _ *list_a *list_b size [-x] [α]
+
+                /* running product of list a */
+                pick 8
+                dup 0
+                dup 8
+                add                 // _ *list_b size [-x] [α] *list_a *list_a_last_word
+                push 0
+                push 0
+                push 1
+                call {running_product_loop}
+                                    // _ *list_b size [-x] [α] *list_a *list_a [rp_a]
+                pick 4
+                pop 1
+                pick 3
+                pop 1               // _ *list_b size [-x] [α] [rp_a]
+                place 10
+                place 10
+                place 10            // _ [rp_a] *list_b size [-x] [α]
+
+                /* running product of list b */
+                pick 7
+                dup 0
+                pick 8
+                add                 // _ [rp_a] [-x] [α] *list_b *list_b_last_word
+                push 0
+                push 0
+                push 1
+                call {running_product_loop}
+                                    // _ [rp_a] [-x] [α] *list_b *list_b [rp_b]
+                place 10
+                place 10
+                place 10            // _ [rp_a] [rp_b] [-x] [α] *list_b *list_b
+                pop 5
+                pop 3               // _ [rp_a] [rp_b]
+                {&compare_xfes}     // _ (rp_a == rp_b)
+                return
+
+            // INVARIANT: _ [-x] [α] *end *element_last_word [rp]
+            {running_product_loop}:
+                dup 4
+                dup 4
+                eq
+                skiz return
+
+                {&compress_element} // _ [-x] [α] *end *element_last_word' [rp] [compressed_element]
+                dup 13
+                dup 13
+                dup 13
+                xx_add
+                xx_mul              // _ [-x] [α] *end *element_last_word' [rp']
+                recurse
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::One;
+    use num::Zero;
+    use rand::seq::SliceRandom;
+
+    use super::*;
+    use crate::rust_shadowing_helper_functions::list::list_get_length;
+    use crate::rust_shadowing_helper_functions::list::list_insert;
+    use crate::rust_shadowing_helper_functions::list::load_list_unstructured;
+    use crate::test_prelude::*;
+
+    impl MultisetEquality {
+        fn set_up_initial_state(
+            &self,
+            list_a: Vec<Vec<BFieldElement>>,
+            list_b: Vec<Vec<BFieldElement>>,
+        ) -> FunctionInitialState {
+            let list_a_pointer = bfe!(1_u64 << 32);
+            let list_b_pointer = bfe!(2_u64 << 32);
+
+            let mut memory = HashMap::default();
+            let mut insert_list = |pointer: BFieldElement, list: Vec<Vec<_>>| {
+                let words = [bfe_vec![list.len()], list.concat()].concat();
+                for (i, word) in words.into_iter().enumerate() {
+                    memory.insert(pointer + bfe!(i), word);
+                }
+            };
+            insert_list(list_a_pointer, list_a);
+            insert_list(list_b_pointer, list_b);
+
+            let mut stack = self.init_stack_for_isolated_run();
+            stack.push(list_a_pointer);
+            stack.push(list_b_pointer);
+
+            FunctionInitialState { stack, memory }
+        }
+
+        fn random_elements(&self, len: usize, rng: &mut impl Rng) -> Vec<Vec<BFieldElement>> {
+            (0..len)
+                .map(|_| (0..self.element_size()).map(|_| rng.random()).collect())
+                .collect()
+        }
+
+        fn running_product(
+            elements: &[Vec<BFieldElement>],
+            neg_x: XFieldElement,
+            alpha: XFieldElement,
+        ) -> XFieldElement {
+            elements
+                .iter()
+                .map(|element| {
+                    element
+                        .iter()
+                        .rev()
+                        .fold(XFieldElement::zero(), |acc, &word| acc * alpha + word)
+                })
+                .fold(
+                    XFieldElement::one(),
+                    |running_product, compressed_element| {
+                        running_product * (compressed_element + neg_x)
+                    },
+                )
+        }
+    }
+
+    impl Function for MultisetEquality {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let list_b_pointer = stack.pop().unwrap();
+            let list_a_pointer = stack.pop().unwrap();
+
+            let len_a = list_get_length(list_a_pointer, memory);
+            let len_b = list_get_length(list_b_pointer, memory);
+            if len_a != len_b {
+                stack.push(bfe!(0));
+                return;
+            }
+
+            // hash to get Fiat-Shamir challenges
+            let list_size = len_a * self.element_size() + 1;
+            let load_words = |pointer: BFieldElement| {
+                (0..list_size)
+                    .map(|i| memory[&(pointer + bfe!(i))])
+                    .collect_vec()
+            };
+            let a_digest = Tip5::hash_varlen(&load_words(list_a_pointer));
+            let b_digest = Tip5::hash_varlen(&load_words(list_b_pointer));
+            let digest = Tip5::hash_pair(b_digest, a_digest);
+            let another_digest = Tip5::hash_pair(Digest::default(), digest);
+            let neg_x = XFieldElement::new(digest.values()[..3].try_into().unwrap());
+            let alpha = XFieldElement::new(another_digest.values()[2..].try_into().unwrap());
+
+            let a = load_list_unstructured(self.element_size(), list_a_pointer, memory);
+            let b = load_list_unstructured(self.element_size(), list_b_pointer, memory);
+            let running_product_a = Self::running_product(&a, neg_x, alpha);
+            let running_product_b = Self::running_product(&b, neg_x, alpha);
+
+            stack.push(bfe!(u64::from(running_product_a == running_product_b)));
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let length = match bench_case {
+                Some(BenchmarkCase::CommonCase) => 45,
+                Some(BenchmarkCase::WorstCase) => 180,
+                None => rng.random_range(0..50),
+            };
+            let list_a = self.random_elements(length, &mut rng);
+            let mut list_b = list_a.clone();
+            list_b.shuffle(&mut rng);
+
+            if bench_case.is_none() && length > 0 {
+                match rng.random_range(0..=3) {
+                    0 => (), // equal multisets
+                    1 => {
+                        let index = rng.random_range(0..length);
+                        let word_index = rng.random_range(0..self.element_size());
+                        list_b[index][word_index] += bfe!(1);
+                    }
+                    2 => list_b = self.random_elements(length, &mut rng),
+                    3 => {
+                        list_b.pop();
+                    }
+                    _ => unreachable!(),
+                }
+            }
+
+            self.set_up_initial_state(list_a, list_b)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let mut rng = StdRng::seed_from_u64(0x5e7);
+            let element = self.random_elements(1, &mut rng).pop().unwrap();
+            let other_element = self.random_elements(1, &mut rng).pop().unwrap();
+
+            let empty = self.set_up_initial_state(vec![], vec![]);
+            let empty_and_singleton = self.set_up_initial_state(vec![], vec![element.clone()]);
+            let equal_singletons =
+                self.set_up_initial_state(vec![element.clone()], vec![element.clone()]);
+            let unequal_singletons =
+                self.set_up_initial_state(vec![element.clone()], vec![other_element.clone()]);
+            let different_multiplicities = self.set_up_initial_state(
+                vec![element.clone(), element.clone(), other_element.clone()],
+                vec![element.clone(), other_element.clone(), other_element],
+            );
+
+            vec![
+                empty,
+                empty_and_singleton,
+                equal_singletons,
+                unequal_singletons,
+                different_multiplicities,
+            ]
+        }
+    }
+
+    fn struct_type() -> DataType {
+        DataType::StructRef(crate::data_type::StructType {
+            name: "Utxo".to_string(),
+            fields: vec![
+                ("amount".to_string(), DataType::U128),
+                ("lock".to_string(), DataType::Digest),
+                ("spent".to_string(), DataType::Bool),
+            ],
+        })
+    }
+
+    #[test]
+    fn rust_shadow() {
+        for element_type in [
+            DataType::Bool,
+            DataType::U64,
+            DataType::Xfe,
+            DataType::Digest,
+            DataType::Tuple(vec![DataType::U64, DataType::Digest]),
+            struct_type(),
+        ] {
+            ShadowedFunction::new(MultisetEquality::new(element_type)).test();
+        }
+    }
+
+    #[proptest(cases = 20)]
+    fn permuted_lists_of_xfes_are_equal_multisets(
+        #[strategy(prop::collection::vec(arb(), 0..50))] list: Vec<XFieldElement>,
+        #[strategy(Just(#list.clone()).prop_shuffle())] permuted_list: Vec<XFieldElement>,
+    ) {
+        let list_a_pointer = bfe!(1_u64 << 32);
+        let list_b_pointer = bfe!(2_u64 << 32);
+        let mut memory = HashMap::default();
+        list_insert(list_a_pointer, list, &mut memory);
+        list_insert(list_b_pointer, permuted_list, &mut memory);
+
+        let snippet = MultisetEquality::new(DataType::Xfe);
+        let mut stack = snippet.init_stack_for_isolated_run();
+        let expected_stack = [stack.clone(), vec![bfe!(1)]].concat();
+        stack.push(list_a_pointer);
+        stack.push(list_b_pointer);
+
+        test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &stack,
+            &[],
+            &NonDeterminism::default().with_ram(memory),
+            &None,
+            Some(&expected_stack),
+        );
+    }
+
+    #[proptest(cases = 20)]
+    fn lists_of_tuples_differing_in_one_word_are_unequal_multisets(
+        #[strategy(prop::collection::vec(arb(), 1..50))] list: Vec<(u64, Digest)>,
+        #[strategy(0..#list.len())] index: usize,
+        #[strategy(0..7_usize)] word_index: usize,
+        #[filter(!#difference.is_zero())]
+        #[strategy(arb())]
+        difference: BFieldElement,
+    ) {
+        let snippet = MultisetEquality::new(DataType::Tuple(vec![DataType::U64, DataType::Digest]));
+        let list_a = list.iter().map(|element| element.encode()).collect_vec();
+        let mut list_b = list_a.clone();
+        list_b[index][word_index] += difference;
+
+        let initial_state = snippet.set_up_initial_state(list_a, list_b);
+        let expected_stack = [snippet.init_stack_for_isolated_run(), vec![bfe!(0)]].concat();
+        test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            &None,
+            Some(&expected_stack),
+        );
+    }
+
+    #[test]
+    fn running_product_of_empty_list_is_one() {
+        let product =
+            MultisetEquality::running_product(&[], XFieldElement::one(), XFieldElement::one());
+        assert!(product.is_one());
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(MultisetEquality::new(DataType::Xfe)).bench();
+    }
+}
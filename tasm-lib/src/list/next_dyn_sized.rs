@@ -0,0 +1,277 @@
+use std::marker::PhantomData;
+
+use triton_vm::prelude::*;
+
+use crate::list::get_dyn_sized::GetDynSized;
+use crate::prelude::*;
+
+/// Advance from one element of a list with
+/// [dynamically sized](BFieldCodec::static_length) elements to the next one.
+///
+/// The cost of this snippet does not depend on the position of the element in
+/// the list. Iterating over a list by getting its first element with
+/// [`GetDynSized`] and all subsequent ones with this snippet has a total cost
+/// linear in the list's length.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *element [element_size: u32]
+/// AFTER:  _ *next_element [next_element_size: u32]
+/// ```
+///
+/// ### Preconditions
+///
+/// - `*element` points to an element of a properly [`BFieldCodec`]-encoded
+///   list, for example as returned by [`GetDynSized`] or this snippet
+/// - `element_size` is the size of that element
+/// - the element is not the last element of the list
+///
+/// ### Postconditions
+///
+/// - the size indicator of the next element is smaller than
+///   [`TasmObject::MAX_OFFSET`] and consistent with its encoding
+#[derive(Debug, Clone)]
+pub struct NextDynSized<T: TasmObject> {
+    _phantom_data: PhantomData<T>,
+}
+
+impl<T: TasmObject> Default for NextDynSized<T> {
+    fn default() -> Self {
+        Self {
+            _phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<T: TasmObject> NextDynSized<T> {
+    /// The size indicator is larger than or equal to [`TasmObject::MAX_OFFSET`].
+    pub const SIZE_INDICATOR_TOO_LARGE_ERROR_ID: i128 = 740;
+
+    /// The size indicator of the element does not match its encoding.
+    pub const INVALID_SIZE_INDICATOR_ERROR_ID: i128 = 741;
+}
+
+impl<T: TasmObject> BasicSnippet for NextDynSized<T> {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*element".to_string()),
+            (DataType::U32, "element_size".to_string()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*next_element".to_string()),
+            (DataType::U32, "next_element_size".to_string()),
+        ]
+    }
+
+    fn entrypoint(&self) -> String {
+        let element_type = T::label_friendly_name();
+        format!("tasmlib_list_next_dyn_sized_element___{element_type}")
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        GetDynSized::<T>::assert_element_type_is_supported();
+        let compute_size = T::compute_size_and_assert_valid_size_indicator(library);
+
+        triton_asm!(
+            // BEFORE: _ *element element_size
+            // AFTER:  _ *next_element next_element_size
+            {self.entrypoint()}:
+                add                 // _ *next_element_si
+                read_mem 1
+                addi 2              // _ next_element_si *next_element
+                pick 1              // _ *next_element next_element_si
+
+                /* assert the size indicator is valid */
+                push {T::MAX_OFFSET}
+                dup 1
+                lt
+                assert error_id {Self::SIZE_INDICATOR_TOO_LARGE_ERROR_ID}
+
+                dup 1
+                {&compute_size}     // _ *next_element next_element_si computed_size
+                dup 1
+                eq
+                assert error_id {Self::INVALID_SIZE_INDICATOR_ERROR_ID}
+                                    // _ *next_element next_element_size
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::Arbitrary;
+
+    use super::*;
+    use crate::list::get_dyn_sized::tests::assert_valid_element;
+    use crate::list::get_dyn_sized::tests::random_dyn_sized_list;
+    use crate::rust_shadowing_helper_functions::list::list_dyn_sized_elem_pointer;
+    use crate::test_prelude::*;
+    use crate::U32_TO_USIZE_ERR;
+
+    impl<T: TasmObject + for<'a> Arbitrary<'a>> NextDynSized<T> {
+        /// The initial state for advancing from the element at `index`.
+        fn set_up_initial_state(
+            &self,
+            list: Vec<T>,
+            index: usize,
+            list_pointer: BFieldElement,
+        ) -> AccessorInitialState {
+            let mut memory = HashMap::default();
+            encode_to_memory(&mut memory, list_pointer, &list);
+            let (element_size, element_pointer) =
+                list_dyn_sized_elem_pointer(list_pointer, index, &memory);
+
+            let mut stack = self.init_stack_for_isolated_run();
+            stack.push(element_pointer);
+            stack.push(bfe!(element_size));
+
+            AccessorInitialState { stack, memory }
+        }
+    }
+
+    impl<T: TasmObject + for<'a> Arbitrary<'a>> Accessor for NextDynSized<T> {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let element_size: usize = pop_encodable::<u32>(stack)
+                .try_into()
+                .expect(U32_TO_USIZE_ERR);
+            let element_pointer = stack.pop().unwrap();
+
+            let next_size_indicator_pointer = element_pointer + bfe!(element_size);
+            let next_element_size = memory[&next_size_indicator_pointer].value();
+            let next_element_size = next_element_size.try_into().unwrap();
+            let next_element_pointer = next_size_indicator_pointer + bfe!(1);
+            assert_valid_element::<T>(next_element_pointer, next_element_size, memory);
+
+            stack.push(next_element_pointer);
+            stack.push(bfe!(next_element_size));
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> AccessorInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let (list_length, index) = match bench_case {
+                Some(BenchmarkCase::CommonCase) => (32, 16),
+                Some(BenchmarkCase::WorstCase) => (64, 62),
+                None => {
+                    let list_length = rng.random_range(2..=20);
+                    (list_length, rng.random_range(0..list_length - 1))
+                }
+            };
+            let list = random_dyn_sized_list::<T>(list_length, &mut rng);
+
+            self.set_up_initial_state(list, index, rng.random())
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedAccessor::new(NextDynSized::<Vec<BFieldElement>>::default()).test();
+        ShadowedAccessor::new(NextDynSized::<Vec<XFieldElement>>::default()).test();
+        ShadowedAccessor::new(NextDynSized::<Vec<Vec<Digest>>>::default()).test();
+    }
+
+    #[proptest(cases = 20)]
+    fn iterating_visits_all_elements(
+        #[strategy(prop::collection::vec(arb(), 1..20))] list: Vec<Vec<Digest>>,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let mut memory = HashMap::default();
+        encode_to_memory(&mut memory, list_pointer, &list);
+        let (mut element_size, mut element_pointer) =
+            list_dyn_sized_elem_pointer(list_pointer, 0, &memory);
+
+        let snippet = ShadowedAccessor::new(NextDynSized::<Vec<Digest>>::default());
+        let init_stack = NextDynSized::<Vec<Digest>>::default().init_stack_for_isolated_run();
+        for element in &list[1..] {
+            let stack = [
+                init_stack.clone(),
+                vec![element_pointer, bfe!(element_size)],
+            ]
+            .concat();
+            let final_state = test_rust_equivalence_given_complete_state(
+                &snippet,
+                &stack,
+                &[],
+                &NonDeterminism::default().with_ram(memory.clone()),
+                &None,
+                None,
+            );
+
+            let mut final_stack = final_state.op_stack.stack;
+            element_size = pop_encodable::<u32>(&mut final_stack).try_into().unwrap();
+            element_pointer = final_stack.pop().unwrap();
+            let read_element =
+                *Vec::<Digest>::decode_from_memory(&memory, element_pointer).unwrap();
+            prop_assert_eq!(element, &read_element);
+            prop_assert_eq!(element.encode().len(), element_size);
+        }
+    }
+
+    #[proptest]
+    fn too_large_size_indicator_crashes_vm(
+        #[strategy(prop::collection::vec(arb(), 2..20))] list: Vec<Vec<BFieldElement>>,
+        #[strategy(0..#list.len() - 1)] index: usize,
+        #[strategy(u64::from(Vec::<BFieldElement>::MAX_OFFSET)..1 << 32)] size_indicator: u64,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let snippet = NextDynSized::<Vec<BFieldElement>>::default();
+        let mut initial_state = snippet.set_up_initial_state(list, index, list_pointer);
+        let (_, next_element_pointer) =
+            list_dyn_sized_elem_pointer(list_pointer, index + 1, &initial_state.memory);
+        initial_state
+            .memory
+            .insert(next_element_pointer - bfe!(1), bfe!(size_indicator));
+
+        test_assertion_failure(
+            &ShadowedAccessor::new(snippet),
+            initial_state.into(),
+            &[NextDynSized::<Vec<BFieldElement>>::SIZE_INDICATOR_TOO_LARGE_ERROR_ID],
+        );
+    }
+
+    #[proptest]
+    fn inconsistent_size_indicator_crashes_vm(
+        #[strategy(prop::collection::vec(arb(), 2..20))] list: Vec<Vec<XFieldElement>>,
+        #[strategy(0..#list.len() - 1)] index: usize,
+        #[strategy(1_u64..100)] size_offset: u64,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let snippet = NextDynSized::<Vec<XFieldElement>>::default();
+        let mut initial_state = snippet.set_up_initial_state(list, index, list_pointer);
+        let (next_element_size, next_element_pointer) =
+            list_dyn_sized_elem_pointer(list_pointer, index + 1, &initial_state.memory);
+        let size_indicator = bfe!(next_element_size as u64 + size_offset);
+        initial_state
+            .memory
+            .insert(next_element_pointer - bfe!(1), size_indicator);
+
+        test_assertion_failure(
+            &ShadowedAccessor::new(snippet),
+            initial_state.into(),
+            &[NextDynSized::<Vec<XFieldElement>>::INVALID_SIZE_INDICATOR_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedAccessor::new(NextDynSized::<Vec<Digest>>::default()).bench();
+    }
+}
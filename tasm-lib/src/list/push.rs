@@ -11,6 +11,8 @@ use crate::traits::basic_snippet::SignOffFingerprint;
 ///
 /// Only supports lists with [statically sized](BFieldCodec::static_length)
 /// elements. The element's static size must be in range `1..=15`.
+/// For lists with dynamically sized elements, see
+/// [`PushDynSized`](crate::list::push_dyn_sized::PushDynSized).
 ///
 /// ### Behavior
 ///
@@ -0,0 +1,346 @@
+use std::marker::PhantomData;
+
+use triton_vm::prelude::*;
+
+use crate::list::get_dyn_sized::GetDynSized;
+use crate::prelude::*;
+
+/// Push an element to a list with
+/// [dynamically sized](BFieldCodec::static_length) elements. The element is
+/// copied from memory and prepended with its size indicator.
+///
+/// The size indicators of all existing elements are walked to find the end of
+/// the list, making the cost linear in the list's length.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *list *element [element_size: u32]
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// - the argument `*list` points to a properly [`BFieldCodec`]-encoded list
+/// - the argument `*element` points to a properly [`BFieldCodec`]-encoded
+///   element that does not overlap with the extended list
+/// - all input arguments are properly [`BFieldCodec`] encoded
+///
+/// ### Postconditions
+///
+/// - `*list` points to a properly [`BFieldCodec`]-encoded list
+#[derive(Debug, Clone)]
+pub struct PushDynSized<T: TasmObject> {
+    _phantom_data: PhantomData<T>,
+}
+
+impl<T: TasmObject> Default for PushDynSized<T> {
+    fn default() -> Self {
+        Self {
+            _phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<T: TasmObject> PushDynSized<T> {
+    /// The element's size is larger than or equal to [`TasmObject::MAX_OFFSET`].
+    pub const ELEMENT_TOO_LARGE_ERROR_ID: i128 = 760;
+
+    /// The given size of the element does not match its encoding.
+    pub const INVALID_ELEMENT_SIZE_ERROR_ID: i128 = 761;
+
+    /// Any part of the extended list is outside the allocated memory page.
+    /// See the [memory convention][crate::memory] for more details.
+    pub const MEM_PAGE_ACCESS_VIOLATION_ERROR_ID: i128 = 762;
+}
+
+impl<T: TasmObject> BasicSnippet for PushDynSized<T> {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*list".to_string()),
+            (DataType::VoidPointer, "*element".to_string()),
+            (DataType::U32, "element_size".to_string()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        let element_type = T::label_friendly_name();
+        format!("tasmlib_list_push_dyn_sized_element___{element_type}")
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        GetDynSized::<T>::assert_element_type_is_supported();
+        let compute_size = T::compute_size_and_assert_valid_size_indicator(library);
+        let skip_elements = GetDynSized::<T>::skip_elements(library);
+        let mem_cpy = library.import(Box::new(MemCpy));
+
+        triton_asm!(
+            // BEFORE: _ *list *element element_size
+            // AFTER:  _
+            {self.entrypoint()}:
+                /* assert the element's size is valid */
+                push {T::MAX_OFFSET}
+                dup 1
+                lt
+                assert error_id {Self::ELEMENT_TOO_LARGE_ERROR_ID}
+
+                dup 1
+                {&compute_size}     // _ *list *element element_size computed_size
+                dup 1
+                eq
+                assert error_id {Self::INVALID_ELEMENT_SIZE_ERROR_ID}
+
+                /* find the end of the list */
+                dup 2
+                read_mem 1
+                addi 2              // _ *list *element element_size len *element[0]_si
+                call {skip_elements}
+                                    // _ *list *element element_size 0 *element[len]_si
+                pick 1
+                pop 1               // _ *list *element element_size *new_element_si
+
+                /* assert access is within one memory page */
+                dup 0
+                dup 2
+                add                 // _ *list *element element_size *new_element_si *new_element_last_word
+                dup 4
+                push -1
+                mul
+                add                 // _ *list *element element_size *new_element_si last_word_offset
+                split
+                pop 1
+                push 0
+                eq
+                assert error_id {Self::MEM_PAGE_ACCESS_VIOLATION_ERROR_ID}
+                                    // _ *list *element element_size *new_element_si
+
+                /* write size indicator */
+                dup 1
+                pick 1
+                write_mem 1         // _ *list *element element_size *new_element
+                place 1             // _ *list *element *new_element element_size
+                call {mem_cpy}      // _ *list
+
+                /* update list length */
+                read_mem 1
+                addi 1              // _ len *list
+                pick 1
+                addi 1
+                pick 1
+                write_mem 1
+                pop 1               // _
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::Arbitrary;
+
+    use super::*;
+    use crate::list::get_dyn_sized::tests::assert_valid_element;
+    use crate::list::get_dyn_sized::tests::random_dyn_sized_list;
+    use crate::rust_shadowing_helper_functions::list::list_dyn_sized_elem_pointer;
+    use crate::rust_shadowing_helper_functions::list::list_get_length;
+    use crate::rust_shadowing_helper_functions::list::list_push_dyn_sized;
+    use crate::test_prelude::*;
+    use crate::U32_TO_USIZE_ERR;
+
+    impl<T: TasmObject + for<'a> Arbitrary<'a>> PushDynSized<T> {
+        fn set_up_initial_state(
+            &self,
+            list: Vec<T>,
+            element: T,
+            list_pointer: BFieldElement,
+            element_pointer: BFieldElement,
+        ) -> FunctionInitialState {
+            let mut memory = HashMap::default();
+            encode_to_memory(&mut memory, list_pointer, &list);
+            encode_to_memory(&mut memory, element_pointer, &element);
+
+            let mut stack = self.init_stack_for_isolated_run();
+            stack.push(list_pointer);
+            stack.push(element_pointer);
+            stack.push(bfe!(element.encode().len()));
+
+            FunctionInitialState { stack, memory }
+        }
+    }
+
+    impl<T: TasmObject + for<'a> Arbitrary<'a>> Function for PushDynSized<T> {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let element_size = pop_encodable::<u32>(stack)
+                .try_into()
+                .expect(U32_TO_USIZE_ERR);
+            let element_pointer = stack.pop().unwrap();
+            let list_pointer = stack.pop().unwrap();
+            assert_valid_element::<T>(element_pointer, element_size, memory);
+
+            let list_length = list_get_length(list_pointer, memory);
+            let mut size_indicator_pointer = list_pointer + bfe!(1);
+            for _ in 0..list_length {
+                let size_indicator = memory[&size_indicator_pointer];
+                assert!(size_indicator.value() < u64::from(T::MAX_OFFSET));
+                size_indicator_pointer += size_indicator + bfe!(1);
+            }
+            let last_word_offset = size_indicator_pointer + bfe!(element_size) - list_pointer;
+            assert!(last_word_offset.value() <= u64::from(u32::MAX));
+
+            let element = (0..element_size)
+                .map(|i| memory[&(element_pointer + bfe!(i))])
+                .collect_vec();
+            list_push_dyn_sized(list_pointer, &element, memory);
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let list_length = match bench_case {
+                Some(BenchmarkCase::CommonCase) => 32,
+                Some(BenchmarkCase::WorstCase) => 64,
+                None => rng.random_range(0..20),
+            };
+            let mut list = random_dyn_sized_list::<T>(list_length + 1, &mut rng);
+            let element = list.pop().unwrap();
+            let list_pointer = rng.random();
+
+            // place the element on a different memory page to avoid overlap
+            let element_pointer = list_pointer + bfe!(1_u64 << 32);
+
+            self.set_up_initial_state(list, element, list_pointer, element_pointer)
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedFunction::new(PushDynSized::<Vec<BFieldElement>>::default()).test();
+        ShadowedFunction::new(PushDynSized::<Vec<XFieldElement>>::default()).test();
+        ShadowedFunction::new(PushDynSized::<Vec<Vec<Digest>>>::default()).test();
+    }
+
+    #[proptest(cases = 20)]
+    fn pushing_agrees_with_host_push(
+        #[strategy(prop::collection::vec(arb(), 0..20))] list: Vec<Vec<XFieldElement>>,
+        #[strategy(arb())] element: Vec<XFieldElement>,
+    ) {
+        let list_pointer = bfe!(1_u64 << 32);
+        let element_pointer = bfe!(2_u64 << 32);
+
+        let snippet = PushDynSized::<Vec<XFieldElement>>::default();
+        let initial_state = snippet.set_up_initial_state(
+            list.clone(),
+            element.clone(),
+            list_pointer,
+            element_pointer,
+        );
+        let final_state = test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            &None,
+            None,
+        );
+
+        let mut list = list;
+        list.push(element);
+        let final_list =
+            *Vec::<Vec<XFieldElement>>::decode_from_memory(&final_state.ram, list_pointer).unwrap();
+        prop_assert_eq!(list, final_list);
+    }
+
+    #[proptest]
+    fn invalid_element_size_crashes_vm(
+        #[strategy(prop::collection::vec(arb(), 0..20))] list: Vec<Vec<Digest>>,
+        #[strategy(arb())] element: Vec<Digest>,
+        #[strategy(1_u64..100)] size_offset: u64,
+    ) {
+        let snippet = PushDynSized::<Vec<Digest>>::default();
+        let mut initial_state =
+            snippet.set_up_initial_state(list, element, bfe!(1_u64 << 32), bfe!(0));
+        let element_size = initial_state.stack.pop().unwrap();
+        initial_state.stack.push(element_size + bfe!(size_offset));
+
+        test_assertion_failure(
+            &ShadowedFunction::new(snippet),
+            initial_state.into(),
+            &[PushDynSized::<Vec<Digest>>::INVALID_ELEMENT_SIZE_ERROR_ID],
+        );
+    }
+
+    #[proptest]
+    fn too_large_size_indicator_in_list_crashes_vm(
+        #[strategy(prop::collection::vec(arb(), 1..20))] list: Vec<Vec<BFieldElement>>,
+        #[strategy(0..#list.len())] messed_up_index: usize,
+        #[strategy(u64::from(Vec::<BFieldElement>::MAX_OFFSET)..1 << 32)] size_indicator: u64,
+        #[strategy(arb())] element: Vec<BFieldElement>,
+    ) {
+        let list_pointer = bfe!(1_u64 << 32);
+        let snippet = PushDynSized::<Vec<BFieldElement>>::default();
+        let mut initial_state = snippet.set_up_initial_state(list, element, list_pointer, bfe!(0));
+
+        let (_, element_pointer) =
+            list_dyn_sized_elem_pointer(list_pointer, messed_up_index, &initial_state.memory);
+        initial_state
+            .memory
+            .insert(element_pointer - bfe!(1), bfe!(size_indicator));
+
+        test_assertion_failure(
+            &ShadowedFunction::new(snippet),
+            initial_state.into(),
+            &[GetDynSized::<Vec<BFieldElement>>::SIZE_INDICATOR_TOO_LARGE_ERROR_ID],
+        );
+    }
+
+    #[proptest(cases = 50)]
+    fn too_large_lists_crash_vm(
+        #[strategy(16_u64..32)] list_length: u64,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let element_pointer = list_pointer - bfe!(1_u64 << 32);
+        let element = vec![bfe!(42); 10];
+        let snippet = PushDynSized::<Vec<BFieldElement>>::default();
+        let mut initial_state =
+            snippet.set_up_initial_state(vec![], element, list_pointer, element_pointer);
+
+        // spare host machine RAM: only write the size indicators of the
+        // existing, rather large elements
+        let size_indicator = u64::from(Vec::<BFieldElement>::MAX_OFFSET) - 1;
+        let memory = &mut initial_state.memory;
+        memory.insert(list_pointer, bfe!(list_length));
+        let mut size_indicator_pointer = list_pointer + bfe!(1);
+        for _ in 0..list_length {
+            memory.insert(size_indicator_pointer, bfe!(size_indicator));
+            size_indicator_pointer += bfe!(size_indicator + 1);
+        }
+
+        test_assertion_failure(
+            &ShadowedFunction::new(snippet),
+            initial_state.into(),
+            &[PushDynSized::<Vec<BFieldElement>>::MEM_PAGE_ACCESS_VIOLATION_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(PushDynSized::<Vec<Digest>>::default()).bench();
+    }
+}
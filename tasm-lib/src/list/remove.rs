@@ -0,0 +1,299 @@
+use triton_vm::prelude::*;
+
+use crate::list::get::Get;
+use crate::prelude::*;
+
+/// Remove the element at position `index` from a list, shifting all elements
+/// after it to the left. Mimics [`Vec::remove`].
+///
+/// Only supports lists with [statically sized](BFieldCodec::static_length)
+/// elements. The element's static size must be in range `1..=13`.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *list [index: u32]
+/// AFTER:  _ [element: ElementType]
+/// ```
+///
+/// ### Preconditions
+///
+/// - the argument `*list` points to a properly [`BFieldCodec`]-encoded list
+/// - all input arguments are properly [`BFieldCodec`] encoded
+///
+/// ### Postconditions
+///
+/// - `*list` points to a properly [`BFieldCodec`]-encoded list
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Remove {
+    element_type: DataType,
+}
+
+impl Remove {
+    pub const INDEX_OUT_OF_BOUNDS_ERROR_ID: i128 = 720;
+
+    /// # Panics
+    ///
+    /// Panics
+    /// - if the element has [dynamic length][BFieldCodec::static_length], or
+    /// - if the static length is 0, or
+    /// - if the static length is larger than 13.
+    pub fn new(element_type: DataType) -> Self {
+        // need to access internal registers below the element
+        assert!(element_type.stack_size() <= 13);
+        Get::assert_element_type_is_supported(&element_type);
+
+        Self { element_type }
+    }
+}
+
+impl BasicSnippet for Remove {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let list_type = DataType::List(Box::new(self.element_type.clone()));
+
+        vec![
+            (list_type, "*list".to_string()),
+            (DataType::U32, "index".to_string()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(self.element_type.clone(), "element".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        let element_type = self.element_type.label_friendly_name();
+        format!("tasmlib_list_remove___{element_type}")
+    }
+
+    fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+        let element_size = self.element_type.stack_size();
+        let mul_with_element_size = match element_size {
+            1 => triton_asm!(), // no-op
+            n => triton_asm!(push {n} mul),
+        };
+
+        let entrypoint = self.entrypoint();
+        let shift_left_loop = format!("{entrypoint}_shift_left");
+
+        triton_asm!(
+            // BEFORE: _ *list index
+            // AFTER:  _ [element]
+            {entrypoint}:
+                dup 1
+                read_mem 1
+                pop 1               // _ *list index len
+
+                /* assert index is in bounds */
+                dup 0
+                dup 2
+                lt                  // _ *list index len (index < len)
+                assert error_id {Self::INDEX_OUT_OF_BOUNDS_ERROR_ID}
+                                    // _ *list index len
+
+                /* update list length */
+                dup 0
+                addi -1
+                dup 3
+                write_mem 1
+                pop 1               // _ *list index len
+
+                /* read element */
+                pick 1
+                {&mul_with_element_size}
+                dup 2
+                add                 // _ *list len *stop
+                dup 0
+                addi {element_size}
+                                    // _ *list len *stop *element_last_word
+                {&self.element_type.read_value_from_memory_pop_pointer()}
+                                    // _ *list len *stop [element]
+                pick {element_size + 2}
+                pick {element_size + 2}
+                pick {element_size + 2}
+                                    // _ [element] *list len *stop
+
+                /* shift elements after index to the left */
+                pick 1
+                addi -1
+                {&mul_with_element_size}
+                pick 2
+                add                 // _ [element] *stop *new_last_word
+                call {shift_left_loop}
+                                    // _ [element] *new_last_word *new_last_word
+                pop 2
+                return
+
+            // INVARIANT: _ *word *end
+            {shift_left_loop}:
+                dup 1
+                dup 1
+                eq
+                skiz return
+
+                pick 1
+                addi 1              // _ *end (*word + 1)
+                dup 0
+                addi {element_size}
+                read_mem 1
+                pop 1               // _ *end (*word + 1) word
+                pick 1
+                write_mem 1         // _ *end (*word + 2)
+                addi -1
+                place 1             // _ (*word + 1) *end
+                recurse
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::strategy::Union;
+
+    use super::*;
+    use crate::rust_shadowing_helper_functions::list::insert_random_list;
+    use crate::rust_shadowing_helper_functions::list::list_insert;
+    use crate::rust_shadowing_helper_functions::list::list_remove;
+    use crate::rust_shadowing_helper_functions::list::load_list_with_copy_elements;
+    use crate::test_helpers::test_assertion_failure;
+    use crate::test_helpers::test_rust_equivalence_given_complete_state;
+    use crate::test_prelude::*;
+    use crate::U32_TO_USIZE_ERR;
+
+    impl Remove {
+        fn set_up_initial_state(
+            &self,
+            list_length: usize,
+            index: usize,
+            list_pointer: BFieldElement,
+        ) -> FunctionInitialState {
+            let mut memory = HashMap::default();
+            insert_random_list(&self.element_type, list_pointer, list_length, &mut memory);
+
+            let mut stack = self.init_stack_for_isolated_run();
+            stack.push(list_pointer);
+            stack.push(bfe!(index));
+
+            FunctionInitialState { stack, memory }
+        }
+    }
+
+    impl Function for Remove {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let index = pop_encodable::<u32>(stack)
+                .try_into()
+                .expect(U32_TO_USIZE_ERR);
+            let list_pointer = stack.pop().unwrap();
+
+            let element_size = self.element_type.stack_size();
+            let element = list_remove(list_pointer, index, memory, element_size);
+            stack.extend(element.into_iter().rev());
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let (list_length, index, list_pointer) = Get::random_len_idx_ptr(bench_case, &mut rng);
+
+            self.set_up_initial_state(list_length, index, list_pointer)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let list_pointer = bfe!(1_u64 << 32);
+
+            [(1, 0), (2, 0), (2, 1)]
+                .map(|(list_length, index)| {
+                    self.set_up_initial_state(list_length, index, list_pointer)
+                })
+                .to_vec()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        for ty in [
+            DataType::Bool,
+            DataType::Bfe,
+            DataType::U32,
+            DataType::U64,
+            DataType::Xfe,
+            DataType::Digest,
+        ] {
+            ShadowedFunction::new(Remove::new(ty)).test();
+        }
+    }
+
+    #[proptest]
+    fn removing_agrees_with_host_remove(
+        #[strategy(prop::collection::vec(arb(), 1..50))] list: Vec<u64>,
+        #[strategy(0..#list.len())] index: usize,
+    ) {
+        let list_pointer = bfe!(1_u64 << 32);
+        let mut memory = HashMap::default();
+        list_insert(list_pointer, list.clone(), &mut memory);
+
+        let snippet = Remove::new(DataType::U64);
+        let mut stack = snippet.init_stack_for_isolated_run();
+        let mut expected_stack = stack.clone();
+        stack.push(list_pointer);
+        stack.push(bfe!(index));
+
+        let mut list = list;
+        let element = list.remove(index);
+        expected_stack.extend(element.encode().into_iter().rev());
+
+        let final_state = test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &stack,
+            &[],
+            &NonDeterminism::default().with_ram(memory),
+            &None,
+            Some(&expected_stack),
+        );
+
+        let list: Vec<[_; 2]> = list
+            .iter()
+            .map(|x| x.encode().try_into().unwrap())
+            .collect();
+        let final_list = load_list_with_copy_elements(list_pointer, &final_state.ram);
+        prop_assert_eq!(list, final_list);
+    }
+
+    #[proptest]
+    fn out_of_bounds_index_crashes_vm(
+        #[strategy(Union::new(
+            [DataType::U32, DataType::U64, DataType::Xfe, DataType::Digest].map(Just)
+        ))]
+        element_type: DataType,
+        #[strategy(0_usize..100)] list_length: usize,
+        #[strategy(#list_length..1 << 32)] index: usize,
+        #[strategy(arb())] list_pointer: BFieldElement,
+    ) {
+        let snippet = Remove::new(element_type);
+        let initial_state = snippet.set_up_initial_state(list_length, index, list_pointer);
+
+        test_assertion_failure(
+            &ShadowedFunction::new(snippet),
+            initial_state.into(),
+            &[Remove::INDEX_OUT_OF_BOUNDS_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(Remove::new(DataType::Digest)).bench();
+    }
+}
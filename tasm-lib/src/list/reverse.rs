@@ -0,0 +1,242 @@
+use triton_vm::prelude::*;
+
+use crate::list::get::Get;
+use crate::prelude::*;
+
+/// Reverse the order of a list's elements in place. Mimics
+/// [`slice::reverse`].
+///
+/// Only supports lists with [statically sized](BFieldCodec::static_length)
+/// elements. The element's static size must be in range `1..=7`.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *list
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// - the argument `*list` points to a properly [`BFieldCodec`]-encoded list
+///
+/// ### Postconditions
+///
+/// - `*list` points to a properly [`BFieldCodec`]-encoded list
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Reverse {
+    element_type: DataType,
+}
+
+impl Reverse {
+    /// # Panics
+    ///
+    /// Panics
+    /// - if the element has [dynamic length][BFieldCodec::static_length], or
+    /// - if the static length is 0, or
+    /// - if the static length is larger than 7.
+    pub fn new(element_type: DataType) -> Self {
+        // two elements and three internal registers must be accessible
+        assert!(element_type.stack_size() <= 7);
+        Get::assert_element_type_is_supported(&element_type);
+
+        Self { element_type }
+    }
+}
+
+impl BasicSnippet for Reverse {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let list_type = DataType::List(Box::new(self.element_type.clone()));
+        vec![(list_type, "*list".to_string())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        let element_type = self.element_type.label_friendly_name();
+        format!("tasmlib_list_reverse___{element_type}")
+    }
+
+    fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+        let element_size = self.element_type.stack_size();
+        let neg_element_size = -(element_size as i32);
+        let mul_with_element_size = match element_size {
+            1 => triton_asm!(), // no-op
+            n => triton_asm!(push {n} mul),
+        };
+        let read_element = self.element_type.read_value_from_memory_pop_pointer();
+        let write_element = self.element_type.write_value_to_memory_pop_pointer();
+
+        let entrypoint = self.entrypoint();
+        let swap_loop = format!("{entrypoint}_swap");
+
+        triton_asm!(
+            // BEFORE: _ *list
+            // AFTER:  _
+            {entrypoint}:
+                read_mem 1
+                addi 2              // _ len *list[0]
+
+                /* number of swaps: len / 2 */
+                push 2
+                dup 2
+                div_mod
+                pop 1               // _ len *list[0] half
+                place 2             // _ half len *list[0]
+
+                /* pointer to last element */
+                pick 1
+                addi -1
+                {&mul_with_element_size}
+                dup 1
+                add                 // _ half *list[0] *list[len - 1]
+                place 1             // _ half *back *front
+
+                call {swap_loop}    // _ 0 *back *front
+                pop 3
+                return
+
+            // INVARIANT: _ remaining *back *front
+            {swap_loop}:
+                dup 2
+                push 0
+                eq
+                skiz return
+
+                dup 0
+                addi {element_size - 1}
+                {&read_element}     // _ remaining *back *front [front_element]
+                dup {element_size + 1}
+                addi {element_size - 1}
+                {&read_element}     // _ remaining *back *front [front_element] [back_element]
+                dup {2 * element_size}
+                {&write_element}    // _ remaining *back *front [front_element]
+                dup {element_size + 1}
+                {&write_element}    // _ remaining *back *front
+
+                addi {element_size}
+                pick 1
+                addi {neg_element_size}
+                place 1
+                pick 2
+                addi -1
+                place 2             // _ (remaining - 1) *back' *front'
+                recurse
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_shadowing_helper_functions::list::insert_random_list;
+    use crate::rust_shadowing_helper_functions::list::list_insert;
+    use crate::rust_shadowing_helper_functions::list::list_reverse;
+    use crate::rust_shadowing_helper_functions::list::load_list_with_copy_elements;
+    use crate::test_helpers::test_rust_equivalence_given_complete_state;
+    use crate::test_prelude::*;
+
+    impl Reverse {
+        fn set_up_initial_state(
+            &self,
+            list_length: usize,
+            list_pointer: BFieldElement,
+        ) -> FunctionInitialState {
+            let mut memory = HashMap::default();
+            insert_random_list(&self.element_type, list_pointer, list_length, &mut memory);
+
+            let mut stack = self.init_stack_for_isolated_run();
+            stack.push(list_pointer);
+
+            FunctionInitialState { stack, memory }
+        }
+    }
+
+    impl Function for Reverse {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let list_pointer = stack.pop().unwrap();
+            list_reverse(list_pointer, memory, self.element_type.stack_size());
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let list_length = match bench_case {
+                Some(BenchmarkCase::CommonCase) => 32,
+                Some(BenchmarkCase::WorstCase) => 64,
+                None => rng.random_range(0..100),
+            };
+
+            self.set_up_initial_state(list_length, rng.random())
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let list_pointer = bfe!(1_u64 << 32);
+
+            (0..=3)
+                .map(|list_length| self.set_up_initial_state(list_length, list_pointer))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        for ty in [
+            DataType::Bool,
+            DataType::Bfe,
+            DataType::U32,
+            DataType::U64,
+            DataType::U128,
+            DataType::Xfe,
+        ] {
+            ShadowedFunction::new(Reverse::new(ty)).test();
+        }
+    }
+
+    #[proptest]
+    fn reversing_agrees_with_host_reverse(
+        #[strategy(prop::collection::vec(arb(), 0..50))] list: Vec<XFieldElement>,
+    ) {
+        let list_pointer = bfe!(1_u64 << 32);
+        let mut memory = HashMap::default();
+        list_insert(list_pointer, list.clone(), &mut memory);
+
+        let snippet = Reverse::new(DataType::Xfe);
+        let mut stack = snippet.init_stack_for_isolated_run();
+        let expected_stack = stack.clone();
+        stack.push(list_pointer);
+
+        let final_state = test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &stack,
+            &[],
+            &NonDeterminism::default().with_ram(memory),
+            &None,
+            Some(&expected_stack),
+        );
+
+        let list = list.into_iter().rev().map(|x| x.coefficients).collect_vec();
+        let final_list = load_list_with_copy_elements(list_pointer, &final_state.ram);
+        prop_assert_eq!(list, final_list);
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(Reverse::new(DataType::Xfe)).bench();
+    }
+}
@@ -8,6 +8,8 @@ use crate::prelude::*;
 ///
 /// Only supports lists with [statically sized](BFieldCodec::static_length)
 /// elements.
+/// For lists with dynamically sized elements, see
+/// [`SetDynSized`](crate::list::set_dyn_sized::SetDynSized).
 ///
 /// ### Behavior
 ///
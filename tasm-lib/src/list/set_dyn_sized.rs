@@ -0,0 +1,308 @@
+use std::marker::PhantomData;
+
+use triton_vm::prelude::*;
+
+use crate::list::get_dyn_sized::GetDynSized;
+use crate::prelude::*;
+
+/// Overwrite an element of a list with
+/// [dynamically sized](BFieldCodec::static_length) elements. The new element
+/// is copied from memory and must have the same size as the element it
+/// replaces. Performs bounds check.
+///
+/// Like [`GetDynSized`], the cost is linear in `index`.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *list [index: u32] *new_element [new_element_size: u32]
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// - the argument `*list` points to a properly [`BFieldCodec`]-encoded list
+/// - the argument `*new_element` points to a properly [`BFieldCodec`]-encoded
+///   element that does not overlap with the list
+/// - all input arguments are properly [`BFieldCodec`] encoded
+///
+/// ### Postconditions
+///
+/// - `*list` points to a properly [`BFieldCodec`]-encoded list
+#[derive(Debug, Clone)]
+pub struct SetDynSized<T: TasmObject> {
+    _phantom_data: PhantomData<T>,
+}
+
+impl<T: TasmObject> Default for SetDynSized<T> {
+    fn default() -> Self {
+        Self {
+            _phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<T: TasmObject> SetDynSized<T> {
+    /// The given size of the new element does not match its encoding.
+    pub const INVALID_ELEMENT_SIZE_ERROR_ID: i128 = 750;
+
+    /// The new element's size differs from the size of the element it replaces.
+    pub const ELEMENT_SIZE_MISMATCH_ERROR_ID: i128 = 751;
+}
+
+impl<T: TasmObject + 'static> BasicSnippet for SetDynSized<T> {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*list".to_string()),
+            (DataType::U32, "index".to_string()),
+            (DataType::VoidPointer, "*new_element".to_string()),
+            (DataType::U32, "new_element_size".to_string()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        let element_type = T::label_friendly_name();
+        format!("tasmlib_list_set_dyn_sized_element___{element_type}")
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let compute_size = T::compute_size_and_assert_valid_size_indicator(library);
+        let get_element = library.import(Box::new(GetDynSized::<T>::default()));
+        let mem_cpy = library.import(Box::new(MemCpy));
+
+        triton_asm!(
+            // BEFORE: _ *list index *new_element new_element_size
+            // AFTER:  _
+            {self.entrypoint()}:
+                /* assert the new element's size is valid */
+                dup 1
+                {&compute_size}     // _ *list index *new_element new_element_size computed_size
+                dup 1
+                eq
+                assert error_id {Self::INVALID_ELEMENT_SIZE_ERROR_ID}
+
+                pick 3
+                pick 3
+                call {get_element}  // _ *new_element new_element_size *element element_size
+
+                /* assert the sizes match */
+                dup 2
+                eq
+                assert error_id {Self::ELEMENT_SIZE_MISMATCH_ERROR_ID}
+                                    // _ *new_element new_element_size *element
+                place 1             // _ *new_element *element new_element_size
+                call {mem_cpy}      // _
+                return
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::Arbitrary;
+    use arbitrary::Unstructured;
+
+    use super::*;
+    use crate::list::get::Get;
+    use crate::list::get_dyn_sized::tests::assert_valid_element;
+    use crate::list::get_dyn_sized::tests::random_dyn_sized_list;
+    use crate::rust_shadowing_helper_functions::list::list_set_dyn_sized;
+    use crate::test_prelude::*;
+    use crate::U32_TO_USIZE_ERR;
+
+    impl<T: TasmObject + for<'a> Arbitrary<'a> + 'static> SetDynSized<T> {
+        fn set_up_initial_state(
+            &self,
+            list: Vec<T>,
+            index: usize,
+            new_element: T,
+            list_pointer: BFieldElement,
+            new_element_pointer: BFieldElement,
+        ) -> FunctionInitialState {
+            let mut memory = HashMap::default();
+            encode_to_memory(&mut memory, list_pointer, &list);
+            encode_to_memory(&mut memory, new_element_pointer, &new_element);
+
+            let mut stack = self.init_stack_for_isolated_run();
+            stack.push(list_pointer);
+            stack.push(bfe!(index));
+            stack.push(new_element_pointer);
+            stack.push(bfe!(new_element.encode().len()));
+
+            FunctionInitialState { stack, memory }
+        }
+
+        /// An arbitrary element with the same size as the given one. If no such
+        /// element is found quickly, a copy of the given element is returned.
+        fn random_element_of_same_size(element: &T, rng: &mut impl Rng) -> T {
+            let mut randomness = vec![0u8; 1 << 16];
+            rng.fill(randomness.as_mut_slice());
+            let mut unstructured = Unstructured::new(&randomness);
+
+            let size = element.encode().len();
+            (0..1000)
+                .filter_map(|_| T::arbitrary(&mut unstructured).ok())
+                .find(|candidate| candidate.encode().len() == size)
+                .unwrap_or_else(|| *T::decode(&element.encode()).unwrap())
+        }
+    }
+
+    impl<T: TasmObject + for<'a> Arbitrary<'a> + 'static> Function for SetDynSized<T> {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let new_element_size = pop_encodable::<u32>(stack)
+                .try_into()
+                .expect(U32_TO_USIZE_ERR);
+            let new_element_pointer = stack.pop().unwrap();
+            assert_valid_element::<T>(new_element_pointer, new_element_size, memory);
+
+            let index = stack[stack.len() - 1].value().try_into().unwrap();
+            let list_pointer = stack[stack.len() - 2];
+
+            // validate the list and the element to be replaced
+            GetDynSized::<T>::default().rust_shadow(stack, memory);
+            let _element_size = stack.pop();
+            let _element_pointer = stack.pop();
+
+            let new_element = (0..new_element_size)
+                .map(|i| memory[&(new_element_pointer + bfe!(i))])
+                .collect_vec();
+            list_set_dyn_sized(list_pointer, index, &new_element, memory);
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let (list_length, index, list_pointer) = Get::random_len_idx_ptr(bench_case, &mut rng);
+            let list = random_dyn_sized_list::<T>(list_length, &mut rng);
+            let new_element = Self::random_element_of_same_size(&list[index], &mut rng);
+
+            // place the new element on a different memory page to avoid overlap
+            let new_element_pointer = list_pointer + bfe!(1_u64 << 32);
+
+            self.set_up_initial_state(list, index, new_element, list_pointer, new_element_pointer)
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedFunction::new(SetDynSized::<Vec<BFieldElement>>::default()).test();
+        ShadowedFunction::new(SetDynSized::<Vec<XFieldElement>>::default()).test();
+        ShadowedFunction::new(SetDynSized::<Vec<Vec<Digest>>>::default()).test();
+    }
+
+    #[proptest(cases = 20)]
+    fn setting_agrees_with_host_indexing(
+        #[strategy(prop::collection::vec(arb(), 1..20))] list: Vec<Vec<XFieldElement>>,
+        #[strategy(0..#list.len())] index: usize,
+        #[strategy(prop::collection::vec(arb(), #list[#index].len()))] new_element: Vec<
+            XFieldElement,
+        >,
+    ) {
+        let list_pointer = bfe!(1_u64 << 32);
+        let new_element_pointer = bfe!(2_u64 << 32);
+
+        let snippet = SetDynSized::<Vec<XFieldElement>>::default();
+        let initial_state = snippet.set_up_initial_state(
+            list.clone(),
+            index,
+            new_element.clone(),
+            list_pointer,
+            new_element_pointer,
+        );
+
+        let final_state = test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            &None,
+            None,
+        );
+
+        let mut list = list;
+        list[index] = new_element;
+        let final_list =
+            *Vec::<Vec<XFieldElement>>::decode_from_memory(&final_state.ram, list_pointer).unwrap();
+        prop_assert_eq!(list, final_list);
+    }
+
+    #[proptest]
+    fn out_of_bounds_access_crashes_vm(
+        #[strategy(0_usize..20)] list_length: usize,
+        #[strategy(#list_length..1 << 32)] index: usize,
+    ) {
+        let snippet = SetDynSized::<Vec<BFieldElement>>::default();
+        let list = vec![vec![]; list_length];
+        let initial_state =
+            snippet.set_up_initial_state(list, index, vec![], bfe!(1_u64 << 32), bfe!(0));
+
+        test_assertion_failure(
+            &ShadowedFunction::new(snippet),
+            initial_state.into(),
+            &[GetDynSized::<Vec<BFieldElement>>::INDEX_OUT_OF_BOUNDS_ERROR_ID],
+        );
+    }
+
+    #[proptest]
+    fn size_mismatch_crashes_vm(
+        #[strategy(prop::collection::vec(arb(), 1..20))] list: Vec<Vec<BFieldElement>>,
+        #[strategy(0..#list.len())] index: usize,
+        #[filter(#new_element.len() != #list[#index].len())]
+        #[strategy(arb())]
+        new_element: Vec<BFieldElement>,
+    ) {
+        let snippet = SetDynSized::<Vec<BFieldElement>>::default();
+        let initial_state =
+            snippet.set_up_initial_state(list, index, new_element, bfe!(1_u64 << 32), bfe!(0));
+
+        test_assertion_failure(
+            &ShadowedFunction::new(snippet),
+            initial_state.into(),
+            &[SetDynSized::<Vec<BFieldElement>>::ELEMENT_SIZE_MISMATCH_ERROR_ID],
+        );
+    }
+
+    #[proptest]
+    fn invalid_new_element_size_crashes_vm(
+        #[strategy(prop::collection::vec(arb(), 1..20))] list: Vec<Vec<Digest>>,
+        #[strategy(0..#list.len())] index: usize,
+        #[strategy(arb())] new_element: Vec<Digest>,
+        #[strategy(1_u64..100)] size_offset: u64,
+    ) {
+        let snippet = SetDynSized::<Vec<Digest>>::default();
+        let mut initial_state =
+            snippet.set_up_initial_state(list, index, new_element, bfe!(1_u64 << 32), bfe!(0));
+        let new_element_size = initial_state.stack.pop().unwrap();
+        initial_state
+            .stack
+            .push(new_element_size + bfe!(size_offset));
+
+        test_assertion_failure(
+            &ShadowedFunction::new(snippet),
+            initial_state.into(),
+            &[SetDynSized::<Vec<Digest>>::INVALID_ELEMENT_SIZE_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(SetDynSized::<Vec<Digest>>::default()).bench();
+    }
+}
@@ -0,0 +1,439 @@
+use triton_vm::prelude::*;
+
+use crate::hashing::lt_digest::LtDigest;
+use crate::prelude::*;
+
+/// Sort a list in place, in ascending order.
+///
+/// Uses insertion sort, making this operation *O*(*n*²). If the sorting itself
+/// does not need to happen inside Triton VM, consider
+/// [`SortNondeterministic`](super::sort_nondeterministic::SortNondeterministic)
+/// instead.
+///
+/// Only supports lists with elements of type [`DataType::U32`],
+/// [`DataType::U64`], [`DataType::U128`], [`DataType::Bfe`], or
+/// [`DataType::Digest`]. [`BFieldElement`]s are ordered by their canonical
+/// representative, [`Digest`]s in the same way as [`LtDigest`] orders them.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *list
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// - the argument `*list` points to a properly [`BFieldCodec`]-encoded list
+///
+/// ### Postconditions
+///
+/// - the list is sorted in ascending order
+/// - the list's length is unchanged
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Sort {
+    element_type: DataType,
+}
+
+impl Sort {
+    /// # Panics
+    ///
+    /// Panics if the element type is not supported. See [`Sort`] for the list of
+    /// supported element types.
+    pub fn new(element_type: DataType) -> Self {
+        Self::assert_element_type_is_supported(&element_type);
+
+        Self { element_type }
+    }
+
+    pub(crate) fn assert_element_type_is_supported(element_type: &DataType) {
+        assert!(
+            matches!(
+                element_type,
+                DataType::U32 | DataType::U64 | DataType::U128 | DataType::Bfe | DataType::Digest
+            ),
+            "element type must have a total order, got: {element_type:?}"
+        );
+    }
+
+    /// Generate code that compares the two list elements whose last words are
+    /// pointed to.
+    ///
+    /// ```text
+    /// BEFORE: _ *lhs_last_word *rhs_last_word
+    /// AFTER:  _ (lhs > rhs)
+    /// ```
+    pub(crate) fn greater_than_code(
+        element_type: &DataType,
+        library: &mut Library,
+    ) -> Vec<LabelledInstruction> {
+        if *element_type == DataType::Digest {
+            let lt_digest = library.import(Box::new(LtDigest));
+            let last_word_offset = -(Digest::LEN as isize - 1);
+            return triton_asm!(
+                addi {last_word_offset}
+                pick 1
+                addi {last_word_offset}
+                pick 1
+                call {lt_digest}
+            );
+        }
+
+        let element_size = element_type.stack_size();
+        let load_both = triton_asm!(
+            pick 1
+            {&element_type.read_value_from_memory_pop_pointer()}
+            pick {element_size}
+            {&element_type.read_value_from_memory_pop_pointer()}
+            // _ [lhs] [rhs]
+        );
+
+        let compare = match element_type {
+            DataType::U32 => triton_asm!(lt),
+            DataType::U64 => {
+                let lt_u64 = library.import(Box::new(crate::arithmetic::u64::lt::Lt));
+                triton_asm!(call { lt_u64 })
+            }
+            DataType::U128 => {
+                let lt_u128 = library.import(Box::new(crate::arithmetic::u128::lt::Lt));
+                triton_asm!(call { lt_u128 })
+            }
+            DataType::Bfe => {
+                let lt_u64 = library.import(Box::new(crate::arithmetic::u64::lt::Lt));
+                triton_asm!(
+                    // _ lhs rhs
+                    pick 1
+                    split
+                    pick 2
+                    split
+                    // _ [lhs: u64] [rhs: u64]
+                    call {lt_u64}
+                )
+            }
+            _ => unreachable!(),
+        };
+
+        [load_both, compare].concat()
+    }
+
+    /// Generate code that swaps two adjacent list elements whose last words are
+    /// pointed to.
+    ///
+    /// ```text
+    /// BEFORE: _ *a_last_word *b_last_word
+    /// AFTER:  _ *a_last_word *b_last_word
+    /// ```
+    fn swap_code(&self) -> Vec<LabelledInstruction> {
+        let element_size = self.element_type.stack_size();
+        let first_word_from_last_word = match element_size {
+            1 => triton_asm!(),
+            n => triton_asm!(addi {-(n as isize - 1)}),
+        };
+
+        triton_asm!(
+            dup 1
+            {&self.element_type.read_value_from_memory_pop_pointer()}
+            dup {element_size}
+            {&self.element_type.read_value_from_memory_pop_pointer()}
+            // _ *a_last_word *b_last_word [a] [b]
+
+            dup {2 * element_size + 1}
+            {&first_word_from_last_word}
+            {&self.element_type.write_value_to_memory_pop_pointer()}
+            // _ *a_last_word *b_last_word [a]
+
+            dup {element_size}
+            {&first_word_from_last_word}
+            {&self.element_type.write_value_to_memory_pop_pointer()}
+            // _ *a_last_word *b_last_word
+        )
+    }
+}
+
+impl BasicSnippet for Sort {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let list_type = DataType::List(Box::new(self.element_type.clone()));
+        vec![(list_type, "self".to_owned())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        let element_type = self.element_type.label_friendly_name();
+        format!("tasmlib_list_sort___{element_type}")
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let insert_loop = format!("{entrypoint}_insert_loop");
+        let sift_down_loop = format!("{entrypoint}_sift_down_loop");
+
+        let element_size = self.element_type.stack_size();
+        let mul_with_element_size = match element_size {
+            1 => triton_asm!(), // no-op
+            n => triton_asm!(push {n} mul),
+        };
+        let greater_than = Self::greater_than_code(&self.element_type, library);
+
+        triton_asm!(
+            // BEFORE: _ *list
+            // AFTER:  _
+            {entrypoint}:
+                dup 0
+                read_mem 1
+                pop 1               // _ *list len
+                {&mul_with_element_size}
+                dup 1
+                add                 // _ *list *last_elem_lw
+                dup 1
+                addi {element_size} // _ *list *last_elem_lw *first_elem_lw
+                pick 2              // _ *last_elem_lw *first_elem_lw *list
+
+                /* The list pointer doubles as the last word of the (empty)
+                 * sorted prefix.
+                 */
+                call {insert_loop}
+                pop 3
+                return
+
+            // INVARIANT: _ *last_elem_lw *first_elem_lw *sorted_prefix_lw
+            {insert_loop}:
+                dup 2
+                dup 1
+                eq
+                skiz return
+
+                addi {element_size} // _ *last_elem_lw *first_elem_lw *elem_lw
+                dup 0
+                call {sift_down_loop}
+                pop 1
+                recurse
+
+            // INVARIANT: _ *last_elem_lw *first_elem_lw *elem_lw *current_lw
+            {sift_down_loop}:
+                /* current element is the first element? */
+                dup 2
+                dup 1
+                eq
+                skiz return
+
+                /* predecessor is not greater than current element? */
+                dup 0
+                addi {-(element_size as isize)}
+                dup 1
+                {&greater_than}     // _ *last_elem_lw *first_elem_lw *elem_lw *current_lw (pred > current)
+                push 0
+                eq
+                skiz return
+
+                dup 0
+                addi {-(element_size as isize)}
+                pick 1              // _ *last_elem_lw *first_elem_lw *elem_lw *pred_lw *current_lw
+                {&self.swap_code()}
+                pop 1               // _ *last_elem_lw *first_elem_lw *elem_lw *pred_lw
+                recurse
+        )
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::cmp::Ordering;
+
+    use super::*;
+    use crate::empty_stack;
+    use crate::rust_shadowing_helper_functions::list::load_list_unstructured;
+    use crate::test_helpers::test_rust_equivalence_given_complete_state;
+    use crate::test_prelude::*;
+
+    /// Compare two elements as [`Sort`] does: by comparing the words' canonical
+    /// representatives, starting with the most significant, i.e., last, word.
+    pub(crate) fn compare_elements(a: &[BFieldElement], b: &[BFieldElement]) -> Ordering {
+        let a = a.iter().rev().map(|word| word.value());
+        let b = b.iter().rev().map(|word| word.value());
+        a.cmp(b)
+    }
+
+    pub(crate) fn sort_elements(elements: &mut [Vec<BFieldElement>]) {
+        elements.sort_by(|a, b| compare_elements(a, b));
+    }
+
+    impl Sort {
+        pub(crate) fn set_up_initial_state(
+            &self,
+            list_pointer: BFieldElement,
+            elements: Vec<Vec<BFieldElement>>,
+        ) -> FunctionInitialState {
+            let list = [bfe_vec![elements.len()], elements.concat()].concat();
+            let memory = (0..).map(|i| list_pointer + bfe!(i)).zip(list).collect();
+
+            let stack = [empty_stack(), vec![list_pointer]].concat();
+            FunctionInitialState { stack, memory }
+        }
+    }
+
+    impl Function for Sort {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let list_pointer = stack.pop().unwrap();
+            let element_size = self.element_type.stack_size();
+            let mut elements = load_list_unstructured(element_size, list_pointer, memory);
+            sort_elements(&mut elements);
+
+            let words = (1..).map(|i| list_pointer + bfe!(i)).zip(elements.concat());
+            memory.extend(words);
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let list_length = match bench_case {
+                Some(BenchmarkCase::CommonCase) => 20,
+                Some(BenchmarkCase::WorstCase) => 100,
+                None => rng.random_range(0..50),
+            };
+            let elements = (0..list_length)
+                .map(|_| self.element_type.seeded_random_element(&mut rng))
+                .collect();
+
+            self.set_up_initial_state(rng.random(), elements)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let element_size = self.element_type.stack_size();
+            let element = |i: u64| bfe_vec![i; element_size];
+            let list_pointer = bfe!(42);
+
+            let empty = self.set_up_initial_state(list_pointer, vec![]);
+            let single = self.set_up_initial_state(list_pointer, vec![element(3)]);
+            let sorted = self.set_up_initial_state(list_pointer, (0..10).map(element).collect());
+            let reversed =
+                self.set_up_initial_state(list_pointer, (0..10).rev().map(element).collect());
+            let repeated = self
+                .set_up_initial_state(list_pointer, [5, 1, 5, 0, 1, 5, 0].map(element).to_vec());
+
+            let mut most_significant_word_decides = vec![bfe!(1); element_size];
+            most_significant_word_decides[0] = bfe!(2);
+            let most_significant_word_decides = self.set_up_initial_state(
+                list_pointer,
+                vec![most_significant_word_decides, element(1)],
+            );
+
+            vec![
+                empty,
+                single,
+                sorted,
+                reversed,
+                repeated,
+                most_significant_word_decides,
+            ]
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        for element_type in [
+            DataType::U32,
+            DataType::U64,
+            DataType::U128,
+            DataType::Bfe,
+            DataType::Digest,
+        ] {
+            ShadowedFunction::new(Sort::new(element_type)).test();
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn element_type_without_total_order_is_unsupported() {
+        Sort::new(DataType::Xfe);
+    }
+
+    /// Sort the list in Triton VM, asserting equivalence with the Rust shadow.
+    fn sort_in_vm<T: BFieldCodec>(element_type: DataType, list: &[T]) -> Vec<T> {
+        let snippet = Sort::new(element_type);
+        let list_pointer = bfe!(42);
+        let elements = list.iter().map(|element| element.encode()).collect();
+        let initial_state = snippet.set_up_initial_state(list_pointer, elements);
+
+        let final_state = test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            &None,
+            None,
+        );
+
+        let element_size = T::static_length().unwrap();
+        load_list_unstructured(element_size, list_pointer, &final_state.ram)
+            .into_iter()
+            .map(|words| *T::decode(&words).unwrap())
+            .collect()
+    }
+
+    #[proptest(cases = 20)]
+    fn sorting_u32s_agrees_with_host(
+        #[strategy(prop::collection::vec(0_u32..10, 0..30))] list: Vec<u32>,
+    ) {
+        let sorted = list.iter().copied().sorted().collect_vec();
+        prop_assert_eq!(sorted, sort_in_vm(DataType::U32, &list));
+    }
+
+    #[proptest(cases = 20)]
+    fn sorting_u64s_agrees_with_host(
+        #[strategy(prop::collection::vec(arb(), 0..30))] list: Vec<u64>,
+    ) {
+        let sorted = list.iter().copied().sorted().collect_vec();
+        prop_assert_eq!(sorted, sort_in_vm(DataType::U64, &list));
+    }
+
+    #[proptest(cases = 20)]
+    fn sorting_u128s_agrees_with_host(
+        #[strategy(prop::collection::vec(arb(), 0..30))] list: Vec<u128>,
+    ) {
+        let sorted = list.iter().copied().sorted().collect_vec();
+        prop_assert_eq!(sorted, sort_in_vm(DataType::U128, &list));
+    }
+
+    #[proptest(cases = 20)]
+    fn sorting_bfes_agrees_with_host(
+        #[strategy(prop::collection::vec(arb(), 0..30))] list: Vec<BFieldElement>,
+    ) {
+        let sorted = list
+            .iter()
+            .copied()
+            .sorted_by_key(|bfe| bfe.value())
+            .collect_vec();
+        prop_assert_eq!(sorted, sort_in_vm(DataType::Bfe, &list));
+    }
+
+    #[proptest(cases = 20)]
+    fn sorting_digests_agrees_with_host(
+        #[strategy(prop::collection::vec(arb(), 0..30))] list: Vec<Digest>,
+    ) {
+        let sorted = list.iter().copied().sorted().collect_vec();
+        prop_assert_eq!(sorted, sort_in_vm(DataType::Digest, &list));
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        for element_type in [DataType::U64, DataType::Digest] {
+            ShadowedFunction::new(Sort::new(element_type)).bench();
+        }
+    }
+}
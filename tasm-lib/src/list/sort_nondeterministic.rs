@@ -0,0 +1,384 @@
+use triton_vm::prelude::*;
+
+use crate::list::multiset_equality_digests::MultisetEqualityDigests;
+use crate::list::multiset_equality_u64s::MultisetEqualityU64s;
+use crate::list::new::New;
+use crate::list::sort::Sort;
+use crate::memory::memcpy::MemCpy;
+use crate::prelude::*;
+
+/// Sort a list in place, in ascending order, by divining the sorted list.
+///
+/// Instead of sorting inside Triton VM, the sorted list is read from secret
+/// input. It is then checked to be in ascending order and to be a permutation
+/// of the original list, before it overwrites the original list. This is
+/// *O*(*n*), whereas [`Sort`] is *O*(*n*²).
+///
+/// Only supports lists with elements of type [`DataType::U64`] or
+/// [`DataType::Digest`], for which a multiset-equality check exists.
+/// Elements are ordered like [`Sort`] orders them.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *list
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// - the argument `*list` points to a properly [`BFieldCodec`]-encoded list
+/// - the individual tokens of the secret input contain the sorted list's
+///   elements, where each element's words appear in reverse order
+///
+/// ### Postconditions
+///
+/// - the list is sorted in ascending order
+/// - the list's length is unchanged
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SortNondeterministic {
+    element_type: DataType,
+}
+
+impl SortNondeterministic {
+    pub const UNSORTED_ERROR_ID: i128 = 680;
+    pub const NOT_A_PERMUTATION_ERROR_ID: i128 = 681;
+
+    /// # Panics
+    ///
+    /// Panics if the element type is neither [`DataType::U64`] nor
+    /// [`DataType::Digest`].
+    pub fn new(element_type: DataType) -> Self {
+        assert!(
+            matches!(element_type, DataType::U64 | DataType::Digest),
+            "element type must be u64 or digest, got: {element_type:?}"
+        );
+
+        Self { element_type }
+    }
+
+    fn multiset_equality(&self, library: &mut Library) -> String {
+        match self.element_type {
+            DataType::U64 => library.import(Box::new(MultisetEqualityU64s)),
+            DataType::Digest => library.import(Box::new(MultisetEqualityDigests)),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl BasicSnippet for SortNondeterministic {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        let list_type = DataType::List(Box::new(self.element_type.clone()));
+        vec![(list_type, "self".to_owned())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        let element_type = self.element_type.label_friendly_name();
+        format!("tasmlib_list_sort_nondeterministic___{element_type}")
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let divine_loop = format!("{entrypoint}_divine_loop");
+        let monotonicity_loop = format!("{entrypoint}_monotonicity_loop");
+
+        let new_list = library.import(Box::new(New));
+        let multiset_equality = self.multiset_equality(library);
+        let memcpy = library.import(Box::new(MemCpy));
+        let greater_than = Sort::greater_than_code(&self.element_type, library);
+
+        let element_size = self.element_type.stack_size();
+
+        triton_asm!(
+            // BEFORE: _ *list
+            // AFTER:  _
+            {entrypoint}:
+                call {new_list}         // _ *list *sorted
+                dup 1
+                read_mem 1
+                pop 1                   // _ *list *sorted len
+                dup 0
+                dup 2
+                write_mem 1             // _ *list *sorted len (*sorted + 1)
+                call {divine_loop}
+                pop 2                   // _ *list *sorted
+
+                dup 0
+                read_mem 1
+                addi {1 + element_size} // _ *list *sorted len *sorted[0]_lw
+                call {monotonicity_loop}
+                pop 2                   // _ *list *sorted
+
+                dup 1
+                dup 1
+                call {multiset_equality}
+                assert error_id {Self::NOT_A_PERMUTATION_ERROR_ID}
+                                        // _ *list *sorted
+
+                dup 0
+                read_mem 1
+                pop 1
+                push {element_size}
+                mul                     // _ *list *sorted num_words
+                pick 1
+                addi 1
+                pick 2
+                addi 1
+                pick 2                  // _ *sorted[0] *list[0] num_words
+                call {memcpy}
+                return
+
+            // INVARIANT: _ remaining *sorted[i]
+            {divine_loop}:
+                dup 1
+                push 0
+                eq
+                skiz return
+
+                divine {element_size}
+                pick {element_size}
+                {&self.element_type.write_value_to_memory_leave_pointer()}
+                                        // _ remaining *sorted[i+1]
+                pick 1
+                addi -1
+                place 1
+                recurse
+
+            // INVARIANT: _ remaining *sorted[i]_lw
+            {monotonicity_loop}:
+                push 2
+                dup 2
+                lt
+                skiz return             // _ remaining *sorted[i]_lw
+
+                dup 0
+                addi {element_size}     // _ remaining *sorted[i]_lw *sorted[i+1]_lw
+                dup 1
+                dup 1
+                {&greater_than}         // _ remaining *sorted[i]_lw *sorted[i+1]_lw (sorted[i] > sorted[i+1])
+                push 0
+                eq
+                assert error_id {Self::UNSORTED_ERROR_ID}
+
+                pick 1
+                pop 1
+                pick 1
+                addi -1
+                place 1                 // _ (remaining - 1) *sorted[i+1]_lw
+                recurse
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::strategy::Union;
+
+    use super::*;
+    use crate::empty_stack;
+    use crate::list::sort::tests::sort_elements;
+    use crate::rust_shadowing_helper_functions::dyn_malloc::dynamic_allocator;
+    use crate::rust_shadowing_helper_functions::list::load_list_unstructured;
+    use crate::test_helpers::test_assertion_failure;
+    use crate::test_prelude::*;
+
+    impl SortNondeterministic {
+        fn set_up_initial_state(
+            &self,
+            list_pointer: BFieldElement,
+            elements: Vec<Vec<BFieldElement>>,
+            divined_elements: Vec<Vec<BFieldElement>>,
+        ) -> AlgorithmInitialState {
+            let list = [bfe_vec![elements.len()], elements.concat()].concat();
+            let ram: HashMap<_, _> = (0..).map(|i| list_pointer + bfe!(i)).zip(list).collect();
+
+            let individual_tokens = divined_elements
+                .into_iter()
+                .flat_map(|element| element.into_iter().rev())
+                .collect_vec();
+            let nondeterminism = NonDeterminism::new(individual_tokens).with_ram(ram);
+
+            let stack = [empty_stack(), vec![list_pointer]].concat();
+            AlgorithmInitialState {
+                stack,
+                nondeterminism,
+            }
+        }
+
+        fn honest_initial_state(
+            &self,
+            list_pointer: BFieldElement,
+            elements: Vec<Vec<BFieldElement>>,
+        ) -> AlgorithmInitialState {
+            let mut sorted_elements = elements.clone();
+            sort_elements(&mut sorted_elements);
+            self.set_up_initial_state(list_pointer, elements, sorted_elements)
+        }
+    }
+
+    impl Algorithm for SortNondeterministic {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+            nondeterminism: &NonDeterminism,
+        ) {
+            let list_pointer = stack.pop().unwrap();
+            let element_size = self.element_type.stack_size();
+            let elements = load_list_unstructured(element_size, list_pointer, memory);
+
+            let sorted_elements = nondeterminism
+                .individual_tokens
+                .chunks(element_size)
+                .take(elements.len())
+                .map(|chunk| chunk.iter().rev().copied().collect_vec())
+                .collect_vec();
+            let mut expected_sorted_elements = sorted_elements.clone();
+            sort_elements(&mut expected_sorted_elements);
+            assert_eq!(expected_sorted_elements, sorted_elements);
+
+            let sorted_list_pointer = dynamic_allocator(memory);
+            let sorted_list = [bfe_vec![elements.len()], sorted_elements.concat()].concat();
+            memory.extend(
+                (0..)
+                    .map(|i| sorted_list_pointer + bfe!(i))
+                    .zip(sorted_list),
+            );
+
+            stack.push(list_pointer);
+            stack.push(sorted_list_pointer);
+            match self.element_type {
+                DataType::U64 => MultisetEqualityU64s.rust_shadow(stack, memory),
+                DataType::Digest => MultisetEqualityDigests.rust_shadow(stack, memory),
+                _ => unreachable!(),
+            }
+            assert_eq!(bfe!(1), stack.pop().unwrap());
+
+            memory.extend(
+                (1..)
+                    .map(|i| list_pointer + bfe!(i))
+                    .zip(sorted_elements.concat()),
+            );
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> AlgorithmInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let list_length = match bench_case {
+                Some(BenchmarkCase::CommonCase) => 20,
+                Some(BenchmarkCase::WorstCase) => 100,
+                None => rng.random_range(0..50),
+            };
+            let elements = (0..list_length)
+                .map(|_| self.element_type.seeded_random_element(&mut rng))
+                .collect();
+
+            self.honest_initial_state(bfe!(rng.random_range(0..1_u64 << 30)), elements)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<AlgorithmInitialState> {
+            let element_size = self.element_type.stack_size();
+            let element = |i: u64| bfe_vec![i; element_size];
+            let list_pointer = bfe!(42);
+
+            let empty = self.honest_initial_state(list_pointer, vec![]);
+            let single = self.honest_initial_state(list_pointer, vec![element(3)]);
+            let reversed =
+                self.honest_initial_state(list_pointer, (0..10).rev().map(element).collect());
+            let repeated = self
+                .honest_initial_state(list_pointer, [5, 1, 5, 0, 1, 5, 0].map(element).to_vec());
+
+            vec![empty, single, reversed, repeated]
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        for element_type in [DataType::U64, DataType::Digest] {
+            ShadowedAlgorithm::new(SortNondeterministic::new(element_type)).test();
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn element_type_without_multiset_equality_is_unsupported() {
+        SortNondeterministic::new(DataType::U32);
+    }
+
+    fn random_distinct_elements(
+        element_type: &DataType,
+        seed: u64,
+        len: usize,
+    ) -> Vec<Vec<BFieldElement>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let elements = (0..len)
+            .map(|_| element_type.seeded_random_element(&mut rng))
+            .unique()
+            .collect_vec();
+        assert_eq!(len, elements.len());
+
+        elements
+    }
+
+    #[proptest(cases = 20)]
+    fn unsorted_divined_list_crashes_vm(
+        #[strategy(Union::new([DataType::U64, DataType::Digest].map(Just)))] element_type: DataType,
+        #[strategy(2_usize..30)] list_length: usize,
+        #[strategy(0..#list_length - 1)] swap_index: usize,
+        seed: u64,
+    ) {
+        let elements = random_distinct_elements(&element_type, seed, list_length);
+        let mut divined_elements = elements.clone();
+        sort_elements(&mut divined_elements);
+        divined_elements.swap(swap_index, swap_index + 1);
+
+        let snippet = SortNondeterministic::new(element_type);
+        let initial_state = snippet.set_up_initial_state(bfe!(42), elements, divined_elements);
+        test_assertion_failure(
+            &ShadowedAlgorithm::new(snippet),
+            initial_state.into(),
+            &[SortNondeterministic::UNSORTED_ERROR_ID],
+        );
+    }
+
+    #[proptest(cases = 20)]
+    fn sorted_divined_list_that_is_no_permutation_crashes_vm(
+        #[strategy(Union::new([DataType::U64, DataType::Digest].map(Just)))] element_type: DataType,
+        #[strategy(2_usize..30)] list_length: usize,
+        #[strategy(0..#list_length - 1)] duplicate_index: usize,
+        seed: u64,
+    ) {
+        let elements = random_distinct_elements(&element_type, seed, list_length);
+        let mut divined_elements = elements.clone();
+        sort_elements(&mut divined_elements);
+        divined_elements[duplicate_index + 1] = divined_elements[duplicate_index].clone();
+
+        let snippet = SortNondeterministic::new(element_type);
+        let initial_state = snippet.set_up_initial_state(bfe!(42), elements, divined_elements);
+        test_assertion_failure(
+            &ShadowedAlgorithm::new(snippet),
+            initial_state.into(),
+            &[SortNondeterministic::NOT_A_PERMUTATION_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        for element_type in [DataType::U64, DataType::Digest] {
+            ShadowedAlgorithm::new(SortNondeterministic::new(element_type)).bench();
+        }
+    }
+}
@@ -13,13 +13,19 @@
 //!   [STARK verifier][stark_verifier] (when standard memory layouts are used).
 //! - Pages 1 through 2^31-1 are dynamically allocated by the
 //!   [dynamic allocator][dynamic_allocator] snippet.
-//! - Pages 2^31 through 2^32-5 are not in use by this crate.
+//!   The [arena allocator][arena_allocator] obtains its pages from the dynamic allocator, too.
+//! - The state of the arena allocator lives at address [`ARENA_MALLOC_ADDRESS`][arena_malloc_addr],
+//!   the first address of page 2^32-5. It is a single word, containing a pointer to the first
+//!   free word of the current arena.
+//! - Pages 2^31 through 2^32-6 are not in use by this crate.
 //!
 //! [prime]: BFieldElement::P
 //! [dyn_malloc_addr]: dyn_malloc::DYN_MALLOC_ADDRESS
 //! [static_malloc_addr]: crate::library::STATIC_MEMORY_FIRST_ADDRESS
 //! [stark_verifier]: crate::verifier::stark_verify::StarkVerify
 //! [dynamic_allocator]: dyn_malloc::DynMalloc
+//! [arena_allocator]: arena_malloc::ArenaMalloc
+//! [arena_malloc_addr]: arena_malloc::ARENA_MALLOC_ADDRESS
 
 use std::collections::HashMap;
 
@@ -27,8 +33,12 @@ use num::One;
 use triton_vm::memory_layout::MemoryRegion;
 use triton_vm::prelude::*;
 
+pub mod arena_malloc;
 pub mod dyn_malloc;
+pub mod memcmp;
 pub mod memcpy;
+pub mod memmove;
+pub mod memset;
 
 /// Non-deterministically initialized memory lives in the range $[0: 2^{32})$
 ///
@@ -162,7 +172,7 @@ pub fn write_words_to_memory_pop_pointer(n: usize) -> Vec<LabelledInstruction> {
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use std::collections::HashMap;
 
     use num::Zero;
@@ -172,6 +182,24 @@ mod tests {
     use triton_vm::prelude::BFieldElement;
 
     use super::*;
+    use crate::library::STATIC_MEMORY_FIRST_ADDRESS;
+    use crate::library::STATIC_MEMORY_LAST_ADDRESS;
+
+    /// The first addresses of ranges of length `num_words` that start at the
+    /// beginning or end at the end of the page for non-deterministically
+    /// initialized memory and of the page for static allocations, in this order.
+    pub(crate) fn range_starts_at_page_boundaries(num_words: usize) -> [BFieldElement; 4] {
+        let start_of_range_ending_at = |last_address| last_address + bfe!(1) - bfe!(num_words);
+
+        [
+            FIRST_NON_DETERMINISTICALLY_INITIALIZED_MEMORY_ADDRESS,
+            start_of_range_ending_at(
+                LAST_ADDRESS_AVAILABLE_FOR_NON_DETERMINISTICALLY_ALLOCATED_MEMORY,
+            ),
+            STATIC_MEMORY_LAST_ADDRESS,
+            start_of_range_ending_at(STATIC_MEMORY_FIRST_ADDRESS),
+        ]
+    }
 
     #[test]
     fn last_populated_nd_memory_address_looks_sane() {
@@ -0,0 +1,361 @@
+use triton_vm::prelude::*;
+
+use crate::memory::dyn_malloc::DynMalloc;
+use crate::prelude::*;
+
+/// The location of the arena allocator state in memory.
+///
+/// See the [memory convention][super] for details.
+pub const ARENA_MALLOC_ADDRESS: BFieldElement = BFieldElement::new((u32::MAX as u64 - 4) << 32);
+
+/// Return a pointer to a chunk of memory of the requested size.
+///
+/// Unlike [`DynMalloc`], which hands out an entire page per call, this
+/// allocator carves consecutive chunks out of one page, the current arena. Only
+/// if the requested chunk does not fit into the remainder of the arena, a fresh
+/// page is requested from [`DynMalloc`] and becomes the new arena. The
+/// remainder of the previous arena is never used.
+///
+/// The state of the arena allocator is a single word, stored at
+/// [`ARENA_MALLOC_ADDRESS`]. It is the address of the first free word of the
+/// current arena, or 0 if no arena has been allocated yet.
+///
+/// Since a chunk never crosses a page boundary, the last word of an arena is
+/// never handed out.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ [size: u32]
+/// AFTER:  _ *chunk
+/// ```
+///
+/// ### Preconditions
+///
+/// - the arena allocator state is either 0 or was set by this snippet
+///
+/// ### Postconditions
+///
+/// - the chunk `[*chunk, *chunk + size)` lies within one dynamically allocated
+///   page and does not overlap with any previously allocated chunk
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ArenaMalloc;
+
+impl ArenaMalloc {
+    /// The requested size is not a `u32`.
+    pub const SIZE_TOO_LARGE_ERROR_ID: i128 = 770;
+}
+
+impl BasicSnippet for ArenaMalloc {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::U32, "size".to_string())]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::VoidPointer, "*chunk".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_memory_arena_malloc".to_string()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let dyn_malloc = library.import(Box::new(DynMalloc));
+        let new_arena = format!("{entrypoint}_new_arena");
+
+        triton_asm! {
+        // BEFORE: _ size
+        // AFTER:  _ *chunk
+        {entrypoint}:
+            /* assert the size is a u32 */
+            dup 0
+            split
+            pop 1
+            push 0
+            eq
+            assert error_id {Self::SIZE_TOO_LARGE_ERROR_ID}
+
+            push {ARENA_MALLOC_ADDRESS}
+            read_mem 1
+            pop 1                           // _ size *next
+
+            /* check whether the chunk fits into the current arena */
+            dup 0
+            split
+            pick 1
+            pop 1                           // _ size *next offset
+            dup 2
+            add
+            split
+            pop 1                           // _ size *next chunk_exceeds_arena
+            dup 1
+            push 0
+            eq
+            add                             // _ size *next (chunk_exceeds_arena || arena_is_uninitialized)
+            skiz call {new_arena}           // _ size *chunk
+
+            /* update arena allocator state */
+            dup 0
+            pick 2
+            add                             // _ *chunk *next
+            push {ARENA_MALLOC_ADDRESS}
+            write_mem 1
+            pop 1                           // _ *chunk
+            return
+
+        // BEFORE: _ *next
+        // AFTER:  _ *fresh_page
+        {new_arena}:
+            pop 1
+            call {dyn_malloc}
+            return
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::dyn_malloc::DYN_MALLOC_ADDRESS;
+    use crate::memory::dyn_malloc::DYN_MALLOC_FIRST_ADDRESS;
+    use crate::memory::dyn_malloc::DYN_MALLOC_FIRST_PAGE;
+    use crate::memory::dyn_malloc::DYN_MALLOC_PAGE_SIZE;
+    use crate::memory::dyn_malloc::NUM_ALLOCATABLE_PAGES;
+    use crate::test_prelude::*;
+
+    impl ArenaMalloc {
+        fn set_up_initial_state(
+            &self,
+            size: u64,
+            arena_state: Option<BFieldElement>,
+            dyn_malloc_state: Option<BFieldElement>,
+        ) -> FunctionInitialState {
+            let mut memory = HashMap::default();
+            if let Some(arena_state) = arena_state {
+                memory.insert(ARENA_MALLOC_ADDRESS, arena_state);
+            }
+            if let Some(dyn_malloc_state) = dyn_malloc_state {
+                memory.insert(DYN_MALLOC_ADDRESS, dyn_malloc_state);
+            }
+
+            let mut stack = self.init_stack_for_isolated_run();
+            stack.push(bfe!(size));
+
+            FunctionInitialState { stack, memory }
+        }
+    }
+
+    impl Function for ArenaMalloc {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let size = stack.pop().unwrap().value();
+            assert!(size <= u32::MAX.into(), "size must be a u32");
+
+            let mut next = memory
+                .get(&ARENA_MALLOC_ADDRESS)
+                .copied()
+                .unwrap_or_default();
+            let offset = next.value() % DYN_MALLOC_PAGE_SIZE;
+            if next == bfe!(0) || offset + size >= DYN_MALLOC_PAGE_SIZE {
+                DynMalloc.rust_shadow(stack, memory);
+                next = stack.pop().unwrap();
+            }
+
+            memory.insert(ARENA_MALLOC_ADDRESS, next + bfe!(size));
+            stack.push(next);
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let page = rng.random_range(1..NUM_ALLOCATABLE_PAGES - 1);
+            let dyn_malloc_state = Some(bfe!(page + 1));
+            let (size, offset) = match bench_case {
+                Some(BenchmarkCase::CommonCase) => (10, 1 << 10),
+                Some(BenchmarkCase::WorstCase) => (1 << 10, DYN_MALLOC_PAGE_SIZE - 10),
+                None => {
+                    let size = rng.random_range(0..1 << 20);
+                    let offset = match rng.random_range(0..3) {
+                        0 => rng.random_range(0..1 << 20),
+                        1 => DYN_MALLOC_PAGE_SIZE - rng.random_range(1..=size + 1),
+                        _ => rng.random_range(0..DYN_MALLOC_PAGE_SIZE),
+                    };
+                    (size, offset)
+                }
+            };
+            let arena_state = Some(bfe!(page * DYN_MALLOC_PAGE_SIZE + offset));
+
+            self.set_up_initial_state(size, arena_state, dyn_malloc_state)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let page_start = DYN_MALLOC_FIRST_ADDRESS;
+            let page_end = page_start + bfe!(DYN_MALLOC_PAGE_SIZE);
+            let dyn_malloc_state = Some(bfe!(2));
+            let max_size = u64::from(u32::MAX);
+
+            vec![
+                self.set_up_initial_state(0, None, None),
+                self.set_up_initial_state(42, None, None),
+                self.set_up_initial_state(max_size, None, None),
+                self.set_up_initial_state(0, Some(page_start), dyn_malloc_state),
+                self.set_up_initial_state(max_size, Some(page_start), dyn_malloc_state),
+                self.set_up_initial_state(1, Some(page_end - bfe!(2)), dyn_malloc_state),
+                self.set_up_initial_state(1, Some(page_end - bfe!(1)), dyn_malloc_state),
+                self.set_up_initial_state(0, Some(page_end - bfe!(1)), dyn_malloc_state),
+                self.set_up_initial_state(2, Some(page_end - bfe!(2)), dyn_malloc_state),
+            ]
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedFunction::new(ArenaMalloc).test();
+    }
+
+    #[test]
+    fn first_allocation_initializes_arena() {
+        let initial_state = ArenaMalloc.set_up_initial_state(42, None, None);
+        let final_state = test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(ArenaMalloc),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            &None,
+            None,
+        );
+
+        assert_eq!(
+            Some(&DYN_MALLOC_FIRST_ADDRESS),
+            final_state.op_stack.stack.last()
+        );
+        assert_eq!(
+            DYN_MALLOC_FIRST_ADDRESS + bfe!(42),
+            final_state.ram[&ARENA_MALLOC_ADDRESS]
+        );
+    }
+
+    #[proptest]
+    fn non_u32_size_crashes_vm(#[strategy(1_u64 << 32..)] size: u64) {
+        let initial_state = ArenaMalloc.set_up_initial_state(size, None, None);
+        test_assertion_failure(
+            &ShadowedFunction::new(ArenaMalloc),
+            initial_state.into(),
+            &[ArenaMalloc::SIZE_TOO_LARGE_ERROR_ID],
+        );
+    }
+
+    #[derive(Debug, Clone, Eq, PartialEq)]
+    struct MultipleArenaMallocCalls {
+        sizes: Vec<u32>,
+    }
+
+    impl BasicSnippet for MultipleArenaMallocCalls {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            vec![]
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            vec![(DataType::VoidPointer, "*chunk".to_string()); self.sizes.len()]
+        }
+
+        fn entrypoint(&self) -> String {
+            "tasmlib_memory_arena_malloc_multiple_calls".to_string()
+        }
+
+        fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+            let arena_malloc = library.import(Box::new(ArenaMalloc));
+            let calls = self
+                .sizes
+                .iter()
+                .flat_map(|size| triton_asm!(push {size} call {arena_malloc}))
+                .collect_vec();
+
+            triton_asm!( {self.entrypoint()}: {&calls} return )
+        }
+    }
+
+    impl Function for MultipleArenaMallocCalls {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            for &size in &self.sizes {
+                stack.push(bfe!(size));
+                ArenaMalloc.rust_shadow(stack, memory);
+            }
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            _: [u8; 32],
+            _: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            FunctionInitialState {
+                stack: self.init_stack_for_isolated_run(),
+                memory: HashMap::default(),
+            }
+        }
+    }
+
+    #[proptest(cases = 20)]
+    fn chunks_are_disjoint_and_share_pages(
+        #[strategy(prop::collection::vec(0_u32..1 << 30, 0..30))] sizes: Vec<u32>,
+    ) {
+        let snippet = MultipleArenaMallocCalls {
+            sizes: sizes.clone(),
+        };
+        let stack = snippet.init_stack_for_isolated_run();
+        let final_state = test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(snippet.clone()),
+            &stack,
+            &[],
+            &NonDeterminism::default(),
+            &None,
+            None,
+        );
+
+        let chunk_pointers = final_state.op_stack.stack[stack.len()..].to_vec();
+        let chunks = chunk_pointers
+            .iter()
+            .zip(&sizes)
+            .map(|(pointer, &size)| (pointer.value(), pointer.value() + u64::from(size)))
+            .collect_vec();
+        for &(start, end) in &chunks {
+            prop_assert!(DynMalloc::memory_region().contains_address(bfe!(start)));
+            prop_assert_eq!(start / DYN_MALLOC_PAGE_SIZE, end / DYN_MALLOC_PAGE_SIZE);
+        }
+        for (i, &(start, end)) in chunks.iter().enumerate() {
+            for &(other_start, other_end) in &chunks[i + 1..] {
+                prop_assert!(end <= other_start || other_end <= start);
+            }
+        }
+
+        let total_size = sizes.iter().map(|&size| u64::from(size)).sum::<u64>();
+        let num_used_pages = final_state
+            .ram
+            .get(&DYN_MALLOC_ADDRESS)
+            .map_or(0, |next_page| next_page.value() - DYN_MALLOC_FIRST_PAGE);
+        let max_num_pages = total_size.div_ceil(DYN_MALLOC_PAGE_SIZE / 2) + 1;
+        prop_assert!(num_used_pages <= max_num_pages);
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(ArenaMalloc).bench();
+    }
+}
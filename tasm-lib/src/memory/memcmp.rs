@@ -0,0 +1,321 @@
+use triton_vm::prelude::*;
+
+use crate::prelude::*;
+use crate::structure::tasm_object::DEFAULT_MAX_DYN_FIELD_SIZE;
+
+/// Determine whether two ranges of memory hold the same words.
+///
+/// Like [`MemCpy`], reads the words in chunks of 5 as long as possible.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *lhs *rhs [num_words: u32]
+/// AFTER:  _ [ranges_are_equal: bool]
+/// ```
+///
+/// ### Preconditions
+///
+/// None.
+///
+/// ### Postconditions
+///
+/// None.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct MemCmp;
+
+impl MemCmp {
+    pub const EXCEEDS_MAX_COMPARE_SIZE_ERROR_ID: i128 = 780;
+}
+
+impl BasicSnippet for MemCmp {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*lhs".to_string()),
+            (DataType::VoidPointer, "*rhs".to_string()),
+            (DataType::U32, "num_words".to_string()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![(DataType::Bool, "ranges_are_equal".to_string())]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_memory_memcmp".to_string()
+    }
+
+    fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let compare_5_words_loop_label = format!("{entrypoint}_loop_compare_5_words");
+        let compare_single_words_loop_label = format!("{entrypoint}_loop_compare_single_words");
+
+        triton_asm!(
+        // BEFORE: _ *lhs *rhs num_words
+        // AFTER:  _ ranges_are_equal
+        {entrypoint}:
+            /* Cap size of memcmp operation */
+            push {DEFAULT_MAX_DYN_FIELD_SIZE}
+            dup 1
+            lt
+            assert error_id {Self::EXCEEDS_MAX_COMPARE_SIZE_ERROR_ID}
+            // _ *lhs *rhs num_words
+
+            pick 2
+            addi 4
+            place 2
+            pick 1
+            addi 4
+            place 1
+            push 1
+            // _ (*lhs + 4) (*rhs + 4) num_words 1
+
+            call {compare_5_words_loop_label}
+            // _ (*lhs + 4) (*rhs + 4) remaining_words equal
+
+            pick 3
+            addi -4
+            place 3
+            pick 2
+            addi -4
+            place 2
+            call {compare_single_words_loop_label}
+            // _ *lhs' *rhs' 0 ranges_are_equal
+
+            place 3
+            pop 3
+            return
+
+        // INVARIANT: _ (*lhs + 4) (*rhs + 4) remaining_words equal
+        {compare_5_words_loop_label}:
+            // termination condition
+            push 5
+            dup 2
+            lt
+            // _ (*lhs + 4) (*rhs + 4) remaining_words equal (5 > remaining_words)
+
+            skiz return
+            // _ (*lhs + 4) (*rhs + 4) remaining_words equal
+
+            pick 3
+            read_mem 5
+            addi 10
+            place 8     // _ (*lhs + 9) (*rhs + 4) remaining_words equal [lhs_chunk]
+
+            pick 7
+            read_mem 5
+            addi 10
+            place 12    // _ (*lhs + 9) (*rhs + 9) remaining_words equal [lhs_chunk] [rhs_chunk]
+
+            pick 5
+            eq
+            pick 5
+            pick 2
+            eq
+            mul
+            pick 4
+            pick 2
+            eq
+            mul
+            pick 3
+            pick 2
+            eq
+            mul
+            pick 2
+            pick 2
+            eq
+            mul         // _ (*lhs + 9) (*rhs + 9) remaining_words equal chunks_are_equal
+            mul         // _ (*lhs + 9) (*rhs + 9) remaining_words equal'
+
+            pick 1
+            addi -5
+            place 1     // _ (*lhs + 9) (*rhs + 9) (remaining_words - 5) equal'
+            recurse
+
+        // BEFORE: _ *lhs       *rhs       n equal
+        // AFTER:  _ (*lhs + n) (*rhs + n) 0 equal'
+        {compare_single_words_loop_label}:
+            dup 1
+            push 0
+            eq
+            skiz return
+
+            pick 3
+            read_mem 1
+            addi 2
+            place 4     // _ (*lhs + 1) *rhs n equal lhs_word
+
+            pick 3
+            read_mem 1
+            addi 2
+            place 4     // _ (*lhs + 1) (*rhs + 1) n equal lhs_word rhs_word
+
+            eq
+            mul
+            pick 1
+            addi -1
+            place 1
+            recurse
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::Zero;
+
+    use super::*;
+    use crate::memory::tests::range_starts_at_page_boundaries;
+    use crate::test_prelude::*;
+
+    impl MemCmp {
+        fn set_up_initial_state(
+            &self,
+            lhs_pointer: BFieldElement,
+            rhs_pointer: BFieldElement,
+            lhs: &[BFieldElement],
+            rhs: &[BFieldElement],
+        ) -> FunctionInitialState {
+            assert_eq!(lhs.len(), rhs.len());
+
+            let mut memory = HashMap::default();
+            for (i, &word) in rhs.iter().enumerate() {
+                memory.insert(rhs_pointer + bfe!(i), word);
+            }
+            for (i, &word) in lhs.iter().enumerate() {
+                memory.insert(lhs_pointer + bfe!(i), word);
+            }
+
+            let mut stack = self.init_stack_for_isolated_run();
+            stack.extend(bfe_vec![lhs_pointer, rhs_pointer, lhs.len()]);
+
+            FunctionInitialState { stack, memory }
+        }
+    }
+
+    impl Function for MemCmp {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let len = pop_encodable::<u32>(stack);
+            let rhs_pointer = stack.pop().unwrap();
+            let lhs_pointer = stack.pop().unwrap();
+            assert!(len < DEFAULT_MAX_DYN_FIELD_SIZE);
+
+            let read = |address| memory.get(&address).copied().unwrap_or_default();
+            let ranges_are_equal = (0..len)
+                .map(|i| bfe!(i))
+                .all(|offset| read(lhs_pointer + offset) == read(rhs_pointer + offset));
+
+            push_encodable(stack, &ranges_are_equal);
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let len = match bench_case {
+                Some(BenchmarkCase::CommonCase) => 17,
+                Some(BenchmarkCase::WorstCase) => 1000,
+                None => rng.random_range(0..=200),
+            };
+            let lhs_pointer = rng.random();
+            let rhs_pointer = lhs_pointer + bfe!(rng.random_range(len..1 << 40));
+
+            let lhs = (0..len).map(|_| rng.random()).collect_vec();
+            let mut rhs = lhs.clone();
+            if bench_case.is_none() && len > 0 && rng.random() {
+                let index = rng.random_range(0..len);
+                rhs[index] += bfe!(rng.random_range(1..=u32::MAX));
+            }
+
+            self.set_up_initial_state(lhs_pointer, rhs_pointer, &lhs, &rhs)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let mut rng = StdRng::seed_from_u64(0x3e3c3b);
+            let mut states = vec![];
+            for len in [0, 1, 4, 5, 6, 11] {
+                let lhs = (0..len).map(|_| rng.random()).collect_vec();
+                let mut rhs = lhs.clone();
+                let starts = range_starts_at_page_boundaries(len);
+                for (&lhs_pointer, &rhs_pointer) in starts.iter().cartesian_product(&starts) {
+                    if lhs_pointer == rhs_pointer {
+                        continue;
+                    }
+                    states.push(self.set_up_initial_state(lhs_pointer, rhs_pointer, &lhs, &rhs));
+                }
+
+                if let Some(last_word) = rhs.last_mut() {
+                    last_word.increment();
+                    states.push(self.set_up_initial_state(starts[0], starts[1], &lhs, &rhs));
+                    states.push(self.set_up_initial_state(starts[2], starts[3], &lhs, &rhs));
+                }
+            }
+
+            let uninitialized_ranges = bfe_vec![0, 1_u64 << 32, 42];
+            states.push(FunctionInitialState {
+                stack: [self.init_stack_for_isolated_run(), uninitialized_ranges].concat(),
+                memory: HashMap::default(),
+            });
+
+            states
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedFunction::new(MemCmp).test();
+    }
+
+    #[proptest]
+    fn ranges_differing_in_one_word_are_unequal(
+        #[strategy(prop::collection::vec(arb(), 1..50))] lhs: Vec<BFieldElement>,
+        #[strategy(0..#lhs.len())] index: usize,
+        #[filter(!#difference.is_zero())]
+        #[strategy(arb())]
+        difference: BFieldElement,
+    ) {
+        let mut rhs = lhs.clone();
+        rhs[index] += difference;
+        let initial_state = MemCmp.set_up_initial_state(bfe!(0), bfe!(1_u64 << 32), &lhs, &rhs);
+
+        let expected_stack = [MemCmp.init_stack_for_isolated_run(), bfe_vec![0]].concat();
+        test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(MemCmp),
+            &initial_state.stack,
+            &[],
+            &NonDeterminism::default().with_ram(initial_state.memory),
+            &None,
+            Some(&expected_stack),
+        );
+    }
+
+    #[proptest]
+    fn exceeding_max_size_crashes_vm(#[strategy(DEFAULT_MAX_DYN_FIELD_SIZE..)] len: u32) {
+        let mut stack = MemCmp.init_stack_for_isolated_run();
+        stack.extend(bfe_vec![0, 0, len]);
+        let initial_state = InitVmState::with_stack(stack);
+
+        test_assertion_failure(
+            &ShadowedFunction::new(MemCmp),
+            initial_state,
+            &[MemCmp::EXCEEDS_MAX_COMPARE_SIZE_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(MemCmp).bench();
+    }
+}
@@ -0,0 +1,318 @@
+use triton_vm::prelude::*;
+
+use crate::memory::memcpy::MemCpy;
+use crate::prelude::*;
+use crate::structure::tasm_object::DEFAULT_MAX_DYN_FIELD_SIZE;
+
+/// Copy a range of memory to another, possibly overlapping range.
+///
+/// If the destination lies behind the source and the two ranges overlap,
+/// the words are copied back to front. Otherwise, this snippet defers to
+/// [`MemCpy`], which copies front to back. In both cases, the words are copied
+/// in chunks of 5 as long as possible.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *source *dest [num_words: u32]
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// None.
+///
+/// ### Postconditions
+///
+/// - the range `[*dest, *dest + num_words)` holds the words the range
+///   `[*source, *source + num_words)` held before the call
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct MemMove;
+
+impl MemMove {
+    pub const EXCEEDS_MAX_MOVE_SIZE_ERROR_ID: i128 = 800;
+}
+
+impl BasicSnippet for MemMove {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*source".to_string()),
+            (DataType::VoidPointer, "*dest".to_string()),
+            (DataType::U32, "num_words".to_string()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_memory_memmove".to_string()
+    }
+
+    fn code(&self, library: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let mem_cpy = library.import(Box::new(MemCpy));
+        let move_backwards_label = format!("{entrypoint}_move_backwards");
+        let move_5_words_loop_label = format!("{entrypoint}_loop_move_5_words_backwards");
+        let move_single_words_loop_label = format!("{entrypoint}_loop_move_single_words_backwards");
+
+        triton_asm!(
+        // BEFORE: _ *source *dest num_words
+        // AFTER:  _
+        {entrypoint}:
+            /* Cap size of memmove operation */
+            push {DEFAULT_MAX_DYN_FIELD_SIZE}
+            dup 1
+            lt
+            assert error_id {Self::EXCEEDS_MAX_MOVE_SIZE_ERROR_ID}
+            // _ *source *dest num_words
+
+            /* copy back to front iff the destination overlaps the source's tail */
+            dup 1
+            dup 3
+            push -1
+            mul
+            add
+            split       // _ *source *dest num_words (distance_hi: u32) (distance_lo: u32)
+            dup 2
+            swap 1
+            lt          // _ *source *dest num_words distance_hi (distance_lo < num_words)
+            swap 1
+            push 0
+            eq
+            mul         // _ *source *dest num_words must_move_backwards
+
+            push 1
+            swap 1      // _ *source *dest num_words 1 must_move_backwards
+            skiz call {move_backwards_label}
+            skiz call {mem_cpy}
+            // _
+
+            return
+
+        // BEFORE: _ *source *dest num_words 1
+        // AFTER:  _ 0
+        {move_backwards_label}:
+            pop 1
+            pick 2
+            dup 1
+            add
+            addi -1
+            place 2     // _ *source_last_word *dest num_words
+
+            pick 1
+            dup 1
+            add
+            addi -5
+            place 1     // _ *source_last_word (*dest_last_word - 4) num_words
+
+            call {move_5_words_loop_label}
+            // _ *source_last_word' (*dest_last_word' - 4) remaining_words
+
+            pick 1
+            addi 4
+            place 1
+            call {move_single_words_loop_label}
+            // _ (*source - 1) (*dest - 1) 0
+
+            pop 3
+
+            /* ensure `else` branch is not taken */
+            push 0
+            return
+
+        // INVARIANT: _ *source_last_word (*dest_last_word - 4) remaining_words
+        {move_5_words_loop_label}:
+            // termination condition
+            push 5
+            dup 1
+            lt
+            // _ *source_last_word (*dest_last_word - 4) remaining_words (5 > remaining_words)
+
+            skiz return
+            // _ *source_last_word (*dest_last_word - 4) remaining_words
+
+            // read
+            pick 2      // _ (*dest_last_word - 4) remaining_words *source_last_word
+            read_mem 5  // _ (*dest_last_word - 4) remaining_words [val4 val3 val2 val1 val0] (*source_last_word - 5)
+            place 7     // _ (*source_last_word - 5) (*dest_last_word - 4) remaining_words [val4 val3 val2 val1 val0]
+
+            // write
+            pick 6      // _ (*source_last_word - 5) remaining_words [val4 val3 val2 val1 val0] (*dest_last_word - 4)
+            write_mem 5 // _ (*source_last_word - 5) remaining_words (*dest_last_word + 1)
+            addi -10    // _ (*source_last_word - 5) remaining_words (*dest_last_word - 9)
+            place 1     // _ (*source_last_word - 5) (*dest_last_word - 9) remaining_words
+
+            addi -5     // _ (*source_last_word - 5) (*dest_last_word - 9) (remaining_words - 5)
+            recurse
+
+        // BEFORE: _ *source_last_word       *dest_last_word       n
+        // AFTER:  _ (*source_last_word - n) (*dest_last_word - n) 0
+        {move_single_words_loop_label}:
+            dup 0
+            push 0
+            eq
+            skiz return
+
+            pick 2
+            read_mem 1
+            place 3
+
+            pick 2
+            write_mem 1
+            addi -2
+            place 1
+
+            addi -1
+            recurse
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::tests::range_starts_at_page_boundaries;
+    use crate::test_prelude::*;
+
+    impl MemMove {
+        fn set_up_initial_state(
+            &self,
+            source: BFieldElement,
+            dest: BFieldElement,
+            num_words: usize,
+            rng: &mut impl Rng,
+        ) -> FunctionInitialState {
+            let mut stack = self.init_stack_for_isolated_run();
+            stack.extend(bfe_vec![source, dest, num_words]);
+
+            let memory = (0..num_words)
+                .map(|i| (source + bfe!(i), rng.random()))
+                .collect();
+
+            FunctionInitialState { stack, memory }
+        }
+    }
+
+    impl Function for MemMove {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let len = pop_encodable::<u32>(stack);
+            let dest = stack.pop().unwrap();
+            let source = stack.pop().unwrap();
+            assert!(len < DEFAULT_MAX_DYN_FIELD_SIZE);
+
+            let words = (0..len)
+                .map(|i| memory.get(&(source + bfe!(i))).copied().unwrap_or_default())
+                .collect_vec();
+            for (i, word) in words.into_iter().enumerate() {
+                memory.insert(dest + bfe!(i), word);
+            }
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let len = match bench_case {
+                Some(BenchmarkCase::CommonCase) => 17,
+                Some(BenchmarkCase::WorstCase) => 1000,
+                None => rng.random_range(0..=200),
+            };
+            let source = rng.random();
+            let max_distance = i64::try_from(len).unwrap() + 5;
+            let dest = match bench_case {
+                Some(_) => source + bfe!(3),
+                None => source + bfe!(rng.random_range(-max_distance..=max_distance)),
+            };
+
+            self.set_up_initial_state(source, dest, len, &mut rng)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let mut rng = StdRng::seed_from_u64(0x3e303e);
+            let mut states = vec![];
+            for len in [0, 1, 4, 5, 6, 11] {
+                let starts = range_starts_at_page_boundaries(len);
+                for (&source, &dest) in starts.iter().cartesian_product(&starts) {
+                    states.push(self.set_up_initial_state(source, dest, len, &mut rng));
+                }
+
+                // overlapping ranges at the page boundaries
+                for start in starts {
+                    for distance in [1, 4, 5, 6] {
+                        let (source, dest) = (start, start + bfe!(distance));
+                        states.push(self.set_up_initial_state(source, dest, len, &mut rng));
+                        states.push(self.set_up_initial_state(dest, source, len, &mut rng));
+                    }
+                }
+            }
+
+            states
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedFunction::new(MemMove).test();
+    }
+
+    #[proptest]
+    fn moving_to_overlapping_range_preserves_words(
+        #[strategy(prop::collection::vec(arb(), 0..50))] words: Vec<BFieldElement>,
+        #[strategy(-(#words.len() as i64)..=#words.len() as i64)] distance: i64,
+        #[strategy(arb())] source: BFieldElement,
+    ) {
+        let dest = source + bfe!(distance);
+        let mut memory = HashMap::default();
+        for (i, &word) in words.iter().enumerate() {
+            memory.insert(source + bfe!(i), word);
+        }
+
+        let mut stack = MemMove.init_stack_for_isolated_run();
+        stack.extend(bfe_vec![source, dest, words.len()]);
+        let final_state = test_rust_equivalence_given_complete_state(
+            &ShadowedFunction::new(MemMove),
+            &stack,
+            &[],
+            &NonDeterminism::default().with_ram(memory),
+            &None,
+            None,
+        );
+
+        let moved_words = (0..words.len())
+            .map(|i| final_state.ram[&(dest + bfe!(i))])
+            .collect_vec();
+        prop_assert_eq!(words, moved_words);
+    }
+
+    #[proptest]
+    fn exceeding_max_size_crashes_vm(#[strategy(DEFAULT_MAX_DYN_FIELD_SIZE..)] len: u32) {
+        let mut stack = MemMove.init_stack_for_isolated_run();
+        stack.extend(bfe_vec![0, 0, len]);
+        let initial_state = InitVmState::with_stack(stack);
+
+        test_assertion_failure(
+            &ShadowedFunction::new(MemMove),
+            initial_state,
+            &[MemMove::EXCEEDS_MAX_MOVE_SIZE_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(MemMove).bench();
+    }
+}
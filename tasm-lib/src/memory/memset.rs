@@ -0,0 +1,215 @@
+use triton_vm::prelude::*;
+
+use crate::prelude::*;
+use crate::structure::tasm_object::DEFAULT_MAX_DYN_FIELD_SIZE;
+
+/// Fill a range of memory with copies of one word.
+///
+/// Like [`MemCpy`], writes the words in chunks of 5 as long as possible.
+///
+/// ### Behavior
+///
+/// ```text
+/// BEFORE: _ *dest value [num_words: u32]
+/// AFTER:  _
+/// ```
+///
+/// ### Preconditions
+///
+/// None.
+///
+/// ### Postconditions
+///
+/// - every word in the range `[*dest, *dest + num_words)` is `value`
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct MemSet;
+
+impl MemSet {
+    pub const EXCEEDS_MAX_SET_SIZE_ERROR_ID: i128 = 790;
+}
+
+impl BasicSnippet for MemSet {
+    fn inputs(&self) -> Vec<(DataType, String)> {
+        vec![
+            (DataType::VoidPointer, "*dest".to_string()),
+            (DataType::Bfe, "value".to_string()),
+            (DataType::U32, "num_words".to_string()),
+        ]
+    }
+
+    fn outputs(&self) -> Vec<(DataType, String)> {
+        vec![]
+    }
+
+    fn entrypoint(&self) -> String {
+        "tasmlib_memory_memset".to_string()
+    }
+
+    fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+        let entrypoint = self.entrypoint();
+        let set_5_words_loop_label = format!("{entrypoint}_loop_set_5_words");
+        let set_single_words_loop_label = format!("{entrypoint}_loop_set_single_words");
+
+        triton_asm!(
+        // BEFORE: _ *dest value num_words
+        // AFTER:  _
+        {entrypoint}:
+            /* Cap size of memset operation */
+            push {DEFAULT_MAX_DYN_FIELD_SIZE}
+            dup 1
+            lt
+            assert error_id {Self::EXCEEDS_MAX_SET_SIZE_ERROR_ID}
+            // _ *dest value num_words
+
+            call {set_5_words_loop_label}
+            // _ *dest' value remaining_words
+
+            call {set_single_words_loop_label}
+            // _ *dest'' value 0
+
+            pop 3
+            return
+
+        // INVARIANT: _ *dest value remaining_words
+        {set_5_words_loop_label}:
+            // termination condition
+            push 5
+            dup 1
+            lt
+            // _ *dest value remaining_words (5 > remaining_words)
+
+            skiz return
+            // _ *dest value remaining_words
+
+            dup 1
+            dup 2
+            dup 3
+            dup 4
+            dup 5       // _ *dest value remaining_words value value value value value
+            pick 7
+            write_mem 5 // _ value remaining_words (*dest + 5)
+            place 2     // _ (*dest + 5) value remaining_words
+
+            addi -5     // _ (*dest + 5) value (remaining_words - 5)
+            recurse
+
+        // BEFORE: _ *dest       value n
+        // AFTER:  _ (*dest + n) value 0
+        {set_single_words_loop_label}:
+            dup 0
+            push 0
+            eq
+            skiz return
+
+            dup 1
+            pick 3
+            write_mem 1
+            place 2
+
+            addi -1
+            recurse
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::tests::range_starts_at_page_boundaries;
+    use crate::test_prelude::*;
+
+    impl MemSet {
+        fn set_up_initial_state(
+            &self,
+            dest: BFieldElement,
+            value: BFieldElement,
+            num_words: usize,
+            memory: HashMap<BFieldElement, BFieldElement>,
+        ) -> FunctionInitialState {
+            let mut stack = self.init_stack_for_isolated_run();
+            stack.extend(bfe_vec![dest, value, num_words]);
+
+            FunctionInitialState { stack, memory }
+        }
+    }
+
+    impl Function for MemSet {
+        fn rust_shadow(
+            &self,
+            stack: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            let len = pop_encodable::<u32>(stack);
+            let value = stack.pop().unwrap();
+            let dest = stack.pop().unwrap();
+            assert!(len < DEFAULT_MAX_DYN_FIELD_SIZE);
+
+            for i in 0..len {
+                memory.insert(dest + bfe!(i), value);
+            }
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            seed: [u8; 32],
+            bench_case: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            let mut rng = StdRng::from_seed(seed);
+            let len = match bench_case {
+                Some(BenchmarkCase::CommonCase) => 17,
+                Some(BenchmarkCase::WorstCase) => 1000,
+                None => rng.random_range(0..=200),
+            };
+            let dest = rng.random();
+
+            // surround the range with populated memory to catch out-of-bounds writes
+            let memory = (0..len + 2)
+                .map(|i| (dest + bfe!(i) - bfe!(1), rng.random()))
+                .collect();
+
+            self.set_up_initial_state(dest, rng.random(), len, memory)
+        }
+
+        fn corner_case_initial_states(&self) -> Vec<FunctionInitialState> {
+            let mut rng = StdRng::seed_from_u64(0x3e35e7);
+            let mut states = vec![];
+            for len in [0, 1, 4, 5, 6, 11] {
+                for dest in range_starts_at_page_boundaries(len) {
+                    let memory = (0..len).map(|i| (dest + bfe!(i), rng.random())).collect();
+                    states.push(self.set_up_initial_state(dest, rng.random(), len, memory));
+                }
+            }
+
+            states
+        }
+    }
+
+    #[test]
+    fn rust_shadow() {
+        ShadowedFunction::new(MemSet).test();
+    }
+
+    #[proptest]
+    fn exceeding_max_size_crashes_vm(#[strategy(DEFAULT_MAX_DYN_FIELD_SIZE..)] len: u32) {
+        let mut stack = MemSet.init_stack_for_isolated_run();
+        stack.extend(bfe_vec![0, 0, len]);
+        let initial_state = InitVmState::with_stack(stack);
+
+        test_assertion_failure(
+            &ShadowedFunction::new(MemSet),
+            initial_state,
+            &[MemSet::EXCEEDS_MAX_SET_SIZE_ERROR_ID],
+        );
+    }
+}
+
+#[cfg(test)]
+mod benches {
+    use super::*;
+    use crate::test_prelude::*;
+
+    #[test]
+    fn benchmark() {
+        ShadowedFunction::new(MemSet).bench();
+    }
+}
@@ -0,0 +1,415 @@
+//! A peephole optimizer for linked Triton assembly.
+//!
+//! Composing hand-written snippets leaves behind instruction sequences that
+//! have no effect, like `push 0 add` or `swap 1 swap 1`, as well as calls to
+//! functions that consist of a single instruction. The [`optimize`] pass
+//! removes such sequences and inlines such calls.
+//!
+//! The pass is optional. Set the environment variable `TASMLIB_PEEPHOLE` to
+//! additionally check in every snippet test that the optimized code behaves like
+//! the unoptimized code, and to record the [savings](OptimizationSavings) of
+//! every snippet benchmark in directory `benchmarks/peephole/`.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use num_traits::Zero;
+use serde::Deserialize;
+use serde::Serialize;
+use triton_vm::isa::instruction::AnInstruction;
+use triton_vm::isa::op_stack::NumberOfWords;
+use triton_vm::prelude::*;
+
+use crate::snippet_bencher::BenchmarkCase;
+use crate::snippet_bencher::BenchmarkResult;
+
+/// Remove instruction sequences without effect and inline calls to functions
+/// consisting of a single instruction, until no more optimizations apply.
+///
+/// Removed sequences are
+/// - `push 0 add` and `addi 0`,
+/// - `swap n swap n`,
+/// - `pick n place n` and `place n pick n`,
+/// - `dup n pop 1` and `push a pop 1`, and
+/// - `nop`.
+///
+/// Any sequence containing a label is left untouched, since it might be the
+/// target of a `call`. Similarly, no sequence is removed if it immediately
+/// follows instruction `skiz`, since that would change which instruction is
+/// skipped.
+///
+/// Functions are not removed, even if all calls to them got inlined. Use
+/// [`Library::reachable_imports`](crate::library::Library::reachable_imports)
+/// to drop them.
+pub fn optimize(code: &[LabelledInstruction]) -> Vec<LabelledInstruction> {
+    let mut code = code.to_vec();
+    loop {
+        let optimized = optimize_once(&code);
+        if optimized == code {
+            return code;
+        }
+        code = optimized;
+    }
+}
+
+fn optimize_once(code: &[LabelledInstruction]) -> Vec<LabelledInstruction> {
+    let single_instruction_functions = single_instruction_functions(code);
+
+    let mut optimized: Vec<LabelledInstruction> = Vec::with_capacity(code.len());
+    let mut i = 0;
+    while i < code.len() {
+        let follows_skiz = optimized
+            .iter()
+            .rev()
+            .find(|item| matches!(item, LabelledInstruction::Instruction(_)))
+            .is_some_and(|item| item == &LabelledInstruction::Instruction(AnInstruction::Skiz));
+
+        let LabelledInstruction::Instruction(instruction) = &code[i] else {
+            optimized.push(code[i].clone());
+            i += 1;
+            continue;
+        };
+
+        if let AnInstruction::Call(label) = instruction {
+            match single_instruction_functions.get(label.as_str()) {
+                Some(body) if !body.is_empty() || !follows_skiz => {
+                    optimized.extend_from_slice(body);
+                    i += 1;
+                    continue;
+                }
+                _ => (),
+            }
+        }
+
+        if follows_skiz {
+            optimized.push(code[i].clone());
+            i += 1;
+            continue;
+        }
+
+        if is_without_effect(instruction) {
+            i += 1;
+            continue;
+        }
+
+        if let Some(LabelledInstruction::Instruction(next)) = code.get(i + 1) {
+            if pair_is_without_effect(instruction, next) {
+                i += 2;
+                continue;
+            }
+        }
+
+        optimized.push(code[i].clone());
+        i += 1;
+    }
+
+    optimized
+}
+
+fn is_without_effect(instruction: &AnInstruction<String>) -> bool {
+    match instruction {
+        AnInstruction::Nop => true,
+        AnInstruction::AddI(summand) => summand.is_zero(),
+        _ => false,
+    }
+}
+
+fn pair_is_without_effect(first: &AnInstruction<String>, second: &AnInstruction<String>) -> bool {
+    match (first, second) {
+        (AnInstruction::Push(summand), AnInstruction::Add) => summand.is_zero(),
+        (AnInstruction::Swap(a), AnInstruction::Swap(b)) => a == b,
+        (AnInstruction::Pick(a), AnInstruction::Place(b)) => a == b,
+        (AnInstruction::Place(a), AnInstruction::Pick(b)) => a == b,
+        (AnInstruction::Dup(_) | AnInstruction::Push(_), AnInstruction::Pop(NumberOfWords::N1)) => {
+            true
+        }
+        _ => false,
+    }
+}
+
+/// All functions consisting of at most one instruction followed by `return`,
+/// mapped to the code that can replace a call to them.
+fn single_instruction_functions(
+    code: &[LabelledInstruction],
+) -> HashMap<&str, Vec<LabelledInstruction>> {
+    let mut functions = HashMap::new();
+    for (i, item) in code.iter().enumerate() {
+        let LabelledInstruction::Label(label) = item else {
+            continue;
+        };
+
+        let mut body = vec![];
+        for item in &code[i + 1..] {
+            match item {
+                LabelledInstruction::Instruction(AnInstruction::Return) => {
+                    functions.insert(label.as_str(), body);
+                    break;
+                }
+                LabelledInstruction::Instruction(instruction)
+                    if body.is_empty() && can_be_inlined(instruction) =>
+                {
+                    body.push(item.clone())
+                }
+                LabelledInstruction::AssertionContext(_) if !body.is_empty() => {
+                    body.push(item.clone())
+                }
+                LabelledInstruction::TypeHint(_) | LabelledInstruction::Breakpoint => (),
+                _ => break,
+            }
+        }
+    }
+
+    functions
+}
+
+/// Whether the instruction behaves the same outside of its function. Instructions
+/// that interact with the jump stack do not, and neither does `skiz`, which would
+/// skip a different instruction.
+fn can_be_inlined(instruction: &AnInstruction<String>) -> bool {
+    !matches!(
+        instruction,
+        AnInstruction::Return
+            | AnInstruction::Recurse
+            | AnInstruction::RecurseOrReturn
+            | AnInstruction::Skiz
+    )
+}
+
+/// The effect of the [peephole optimization](optimize) on the execution of
+/// some program.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizationSavings {
+    pub name: String,
+    pub case: BenchmarkCase,
+    pub unoptimized: BenchmarkResult,
+    pub optimized: BenchmarkResult,
+}
+
+impl Display for OptimizationSavings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (before, after) = (&self.unoptimized, &self.optimized);
+        let metrics = [
+            (
+                "clock cycles",
+                before.clock_cycle_count,
+                after.clock_cycle_count,
+            ),
+            (
+                "hash table height",
+                before.hash_table_height,
+                after.hash_table_height,
+            ),
+            (
+                "u32 table height",
+                before.u32_table_height,
+                after.u32_table_height,
+            ),
+            (
+                "op stack table height",
+                before.op_stack_table_height,
+                after.op_stack_table_height,
+            ),
+            (
+                "ram table height",
+                before.ram_table_height,
+                after.ram_table_height,
+            ),
+        ];
+
+        writeln!(
+            f,
+            "peephole optimization of {} ({:?}):",
+            self.name, self.case
+        )?;
+        for (metric, before, after) in metrics {
+            let saved = before as isize - after as isize;
+            writeln!(
+                f,
+                "  {metric:<22} {before:>10} → {after:>10} (saved {saved})"
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_prelude::*;
+
+    fn assert_equivalent_execution(code: &[LabelledInstruction], stack: &[BFieldElement]) {
+        let run = |code: &[LabelledInstruction]| {
+            let mut vm_state = VMState::new(
+                Program::new(code),
+                PublicInput::default(),
+                NonDeterminism::default(),
+            );
+            vm_state.op_stack.stack = stack.to_vec();
+            let result = vm_state.run();
+            (result, vm_state.op_stack.stack, vm_state.ram)
+        };
+
+        let optimized = optimize(code);
+        assert_eq!(run(code), run(&optimized));
+    }
+
+    fn pseudorandom_stack(seed: u64) -> Vec<BFieldElement> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..32).map(|_| rng.random()).collect()
+    }
+
+    #[test]
+    fn sequences_without_effect_are_removed() {
+        let code = triton_asm!(
+            push 0 add
+            addi 0
+            swap 3 swap 3
+            pick 5 place 5
+            place 7 pick 7
+            dup 0 pop 1
+            dup 12 pop 1
+            push 42 pop 1
+            nop
+            halt
+        );
+        assert_eq!(triton_asm!(halt), optimize(&code));
+        assert_equivalent_execution(&code, &pseudorandom_stack(0));
+    }
+
+    #[test]
+    fn sequences_with_effect_are_retained() {
+        let code = triton_asm!(
+            push 1 add
+            addi 1
+            swap 3 swap 4
+            pick 5 place 6
+            dup 0 pop 2
+            push 42 pop 2
+            halt
+        );
+        assert_eq!(code, optimize(&code));
+    }
+
+    #[test]
+    fn optimization_is_applied_until_fixed_point() {
+        let code = triton_asm!(swap 1 push 0 dup 3 pop 1 add swap 1 halt);
+        assert_eq!(triton_asm!(halt), optimize(&code));
+    }
+
+    #[test]
+    fn sequences_interrupted_by_labels_are_retained() {
+        let code = triton_asm!(
+            call foo
+            push 0
+            foo:
+                add
+                halt
+        );
+        assert_eq!(code, optimize(&code));
+    }
+
+    #[test]
+    fn sequences_following_skiz_are_retained() {
+        let code = triton_asm!(skiz push 0 add skiz nop halt);
+        assert_eq!(code, optimize(&code));
+        for condition in [0, 1] {
+            let mut stack = pseudorandom_stack(1);
+            stack.push(bfe!(condition));
+            stack.push(bfe!(condition));
+            assert_equivalent_execution(&code, &stack);
+        }
+    }
+
+    #[test]
+    fn calls_to_single_instruction_functions_are_inlined() {
+        let code = triton_asm!(
+            call double
+            call nothing
+            call three
+            halt
+
+            double:
+                hint value = stack[0]
+                dup 0
+                return
+            nothing:
+                return
+            three:
+                push 3
+                add
+                return
+        );
+        let expected = triton_asm!(
+            dup 0
+            call three
+            halt
+
+            double:
+                hint value = stack[0]
+                dup 0
+                return
+            nothing:
+                return
+            three:
+                push 3
+                add
+                return
+        );
+        assert_eq!(expected, optimize(&code));
+        assert_equivalent_execution(&code, &pseudorandom_stack(2));
+    }
+
+    #[test]
+    fn inlined_assertion_retains_its_context() {
+        let code = triton_asm!(
+            call check
+            halt
+            check:
+                assert error_id 42
+                return
+        );
+        let optimized = optimize(&code);
+        assert_eq!(triton_asm!(assert error_id 42), optimized[..2]);
+
+        let mut stack = pseudorandom_stack(3);
+        stack.push(bfe!(0));
+        assert_equivalent_execution(&code, &stack);
+    }
+
+    #[test]
+    fn calls_to_functions_interacting_with_jump_stack_are_not_inlined() {
+        let code = triton_asm!(
+            call loop_
+            call skip
+            halt
+            loop_: recurse_or_return return
+            skip: skiz return
+        );
+        assert_eq!(code, optimize(&code));
+    }
+
+    #[test]
+    fn call_to_empty_function_following_skiz_is_retained() {
+        let code = triton_asm!(
+            skiz call nothing
+            push 1
+            halt
+            nothing: return
+        );
+        assert_eq!(code, optimize(&code));
+
+        let code = triton_asm!(
+            skiz call one
+            halt
+            one: push 1 return
+        );
+        let expected = triton_asm!(
+            skiz push 1
+            halt
+            one: push 1 return
+        );
+        assert_eq!(expected, optimize(&code));
+    }
+}
@@ -11,6 +11,7 @@ pub use crate::data_type::DataType;
 pub use crate::library::Library;
 pub use crate::memory::dyn_malloc::DynMalloc;
 pub use crate::memory::memcpy::MemCpy;
+pub use crate::structure::tasm_object::TasmEnum;
 pub use crate::structure::tasm_object::TasmObject;
 pub use crate::structure::tasm_object::TasmStruct;
 pub use crate::traits::basic_snippet::BasicSnippet;
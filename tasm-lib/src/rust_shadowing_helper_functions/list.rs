@@ -181,6 +181,25 @@ pub fn list_pointer_to_elem_pointer(
         return (element_size, elem_ptr);
     }
 
+    list_dyn_sized_elem_pointer(list_pointer, index, memory)
+}
+
+/// A pointer to the `i`th element in a list with dynamically sized elements,
+/// as well as the size of that element. Walks the size indicators of all
+/// preceding elements.
+///
+/// # Panics
+///
+/// Panics if the `index` is out of bounds, or if the pointed-to-list is
+/// incorrectly encoded.
+pub fn list_dyn_sized_elem_pointer(
+    list_pointer: BFieldElement,
+    index: usize,
+    memory: &HashMap<BFieldElement, BFieldElement>,
+) -> (usize, BFieldElement) {
+    let list_len = list_get_length(list_pointer, memory);
+    assert!(index < list_len, "out of bounds: {index} >= {list_len}");
+
     let mut elem_pointer = list_pointer + bfe!(LIST_METADATA_SIZE);
     for _ in 0..index {
         elem_pointer += memory[&elem_pointer] + BFieldElement::ONE;
@@ -189,6 +208,53 @@ pub fn list_pointer_to_elem_pointer(
     (elem_size, elem_pointer + BFieldElement::ONE)
 }
 
+/// Overwrite an element of a list with dynamically sized elements. The new
+/// element must have the same size as the element it replaces.
+///
+/// # Panics
+///
+/// Panics if
+/// - the `index` is out of bounds, or
+/// - the new element's size differs from the old element's size, or
+/// - the pointed-to-list is incorrectly encoded into `memory`.
+pub fn list_set_dyn_sized(
+    list_pointer: BFieldElement,
+    index: usize,
+    element: &[BFieldElement],
+    memory: &mut HashMap<BFieldElement, BFieldElement>,
+) {
+    let (elem_size, elem_pointer) = list_dyn_sized_elem_pointer(list_pointer, index, memory);
+    assert_eq!(elem_size, element.len(), "element sizes must match");
+
+    for (i, &word) in element.iter().enumerate() {
+        memory.insert(elem_pointer + bfe!(i), word);
+    }
+}
+
+/// Append an element to a list with dynamically sized elements. Walks the
+/// size indicators of all existing elements.
+///
+/// # Panics
+///
+/// Panics if the pointed-to-list is incorrectly encoded into `memory`.
+pub fn list_push_dyn_sized(
+    list_pointer: BFieldElement,
+    element: &[BFieldElement],
+    memory: &mut HashMap<BFieldElement, BFieldElement>,
+) {
+    let list_len = list_get_length(list_pointer, memory);
+    let mut size_indicator_pointer = list_pointer + bfe!(LIST_METADATA_SIZE);
+    for _ in 0..list_len {
+        size_indicator_pointer += memory[&size_indicator_pointer] + BFieldElement::ONE;
+    }
+
+    memory.insert(size_indicator_pointer, bfe!(element.len()));
+    for (i, &word) in element.iter().enumerate() {
+        memory.insert(size_indicator_pointer + bfe!(i + 1), word);
+    }
+    list_set_length(list_pointer, list_len + 1, memory);
+}
+
 /// Read an element from a list.
 ///
 /// Only supports lists with statically sized elements.
@@ -252,6 +318,131 @@ pub fn list_set(
     }
 }
 
+/// Append the elements of list `other` to list `self`.
+///
+/// Only supports lists with statically sized elements.
+///
+/// # Panics
+///
+/// Panics if the extended list `self` would reside outside its
+/// [memory page][crate::memory], or if either list is incorrectly encoded.
+pub fn list_extend(
+    self_pointer: BFieldElement,
+    other_pointer: BFieldElement,
+    memory: &mut HashMap<BFieldElement, BFieldElement>,
+    element_size: usize,
+) {
+    let other = load_list_unstructured(element_size, other_pointer, memory);
+    for element in other {
+        list_push(self_pointer, element, memory);
+    }
+}
+
+/// Insert an element into a list at position `index`, shifting all elements
+/// after it to the right.
+///
+/// Only supports lists with statically sized elements.
+///
+/// # Panics
+///
+/// Panics if
+/// - the `index` is larger than the list's length, or
+/// - the list would reside outside its [memory page][crate::memory], or
+/// - the pointed-to-list is incorrectly encoded into `memory`.
+pub fn list_insert_at(
+    list_pointer: BFieldElement,
+    index: usize,
+    value: Vec<BFieldElement>,
+    memory: &mut HashMap<BFieldElement, BFieldElement>,
+) {
+    let element_size = value.len();
+    let mut list = load_list_unstructured(element_size, list_pointer, memory);
+    assert!(
+        index <= list.len(),
+        "out of bounds: {index} > {}",
+        list.len()
+    );
+
+    list.insert(index, value);
+    list_new(list_pointer, memory);
+    for element in list {
+        list_push(list_pointer, element, memory);
+    }
+}
+
+/// Remove the element at position `index` from a list, shifting all elements
+/// after it to the left.
+///
+/// Only supports lists with statically sized elements.
+///
+/// # Panics
+///
+/// Panics if the `index` is out of bounds, or if the pointed-to-list is
+/// incorrectly encoded into `memory`.
+pub fn list_remove(
+    list_pointer: BFieldElement,
+    index: usize,
+    memory: &mut HashMap<BFieldElement, BFieldElement>,
+    element_size: usize,
+) -> Vec<BFieldElement> {
+    let mut list = load_list_unstructured(element_size, list_pointer, memory);
+    assert!(
+        index < list.len(),
+        "out of bounds: {index} >= {}",
+        list.len()
+    );
+
+    let element = list.remove(index);
+    list_new(list_pointer, memory);
+    for element in list {
+        list_push(list_pointer, element, memory);
+    }
+
+    element
+}
+
+/// Reverse the order of a list's elements in place.
+///
+/// Only supports lists with statically sized elements.
+///
+/// # Panics
+///
+/// Panics if the pointed-to-list is incorrectly encoded into `memory`.
+pub fn list_reverse(
+    list_pointer: BFieldElement,
+    memory: &mut HashMap<BFieldElement, BFieldElement>,
+    element_size: usize,
+) {
+    let mut list = load_list_unstructured(element_size, list_pointer, memory);
+    list.reverse();
+    list_new(list_pointer, memory);
+    for element in list {
+        list_push(list_pointer, element, memory);
+    }
+}
+
+/// Remove consecutive repeated elements from a list, like [`Vec::dedup`].
+/// If the list is sorted, this removes all duplicates.
+///
+/// Only supports lists with statically sized elements. Elements that are
+/// removed from the list's end are not erased from memory.
+///
+/// # Panics
+///
+/// Panics if the pointed-to-list is incorrectly encoded into `memory`.
+pub fn list_dedup(
+    list_pointer: BFieldElement,
+    memory: &mut HashMap<BFieldElement, BFieldElement>,
+    element_size: usize,
+) {
+    let mut list = load_list_unstructured(element_size, list_pointer, memory);
+    list.dedup();
+    list_new(list_pointer, memory);
+    for element in list {
+        list_push(list_pointer, element, memory);
+    }
+}
+
 pub fn list_get_length(
     list_pointer: BFieldElement,
     memory: &HashMap<BFieldElement, BFieldElement>,
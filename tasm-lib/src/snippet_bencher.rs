@@ -9,6 +9,8 @@ use serde_json::to_writer_pretty;
 use triton_vm::aet::AlgebraicExecutionTrace;
 use triton_vm::prelude::TableId;
 
+use crate::peephole::OptimizationSavings;
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BenchmarkResult {
     pub clock_cycle_count: usize,
@@ -25,7 +27,28 @@ pub struct NamedBenchmarkResult {
     pub case: BenchmarkCase,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+/// How much cheaper a benchmark result is than some baseline. Negative values
+/// mean that the result is more expensive than the baseline.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BenchmarkSavings {
+    pub baseline: String,
+    pub clock_cycle_count: isize,
+    pub hash_table_height: isize,
+    pub u32_table_height: isize,
+    pub op_stack_table_height: isize,
+    pub ram_table_height: isize,
+}
+
+/// A [`NamedBenchmarkResult`] together with its [savings](BenchmarkSavings)
+/// over some baseline.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ComparedBenchmarkResult {
+    #[serde(flatten)]
+    pub benchmark: NamedBenchmarkResult,
+    pub savings: BenchmarkSavings,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum BenchmarkCase {
     CommonCase,
     WorstCase,
@@ -43,11 +66,27 @@ impl BenchmarkResult {
     }
 }
 
-pub fn write_benchmarks(benchmarks: Vec<NamedBenchmarkResult>) {
-    let mut path = PathBuf::new();
-    path.push("benchmarks");
-    create_dir_all(&path).expect("benchmarks directory should exist");
+impl BenchmarkSavings {
+    pub fn new(baseline: &NamedBenchmarkResult, result: &BenchmarkResult) -> Self {
+        let saving = |baseline: usize, result: usize| {
+            let baseline = isize::try_from(baseline).unwrap();
+            let result = isize::try_from(result).unwrap();
+            baseline - result
+        };
+        let base = &baseline.benchmark_result;
+
+        Self {
+            baseline: baseline.name.clone(),
+            clock_cycle_count: saving(base.clock_cycle_count, result.clock_cycle_count),
+            hash_table_height: saving(base.hash_table_height, result.hash_table_height),
+            u32_table_height: saving(base.u32_table_height, result.u32_table_height),
+            op_stack_table_height: saving(base.op_stack_table_height, result.op_stack_table_height),
+            ram_table_height: saving(base.ram_table_height, result.ram_table_height),
+        }
+    }
+}
 
+pub fn write_benchmarks(benchmarks: Vec<NamedBenchmarkResult>) {
     let function_name = &benchmarks[0].name;
     for fnname in benchmarks.iter().map(|x| &x.name) {
         assert_eq!(
@@ -56,7 +95,63 @@ pub fn write_benchmarks(benchmarks: Vec<NamedBenchmarkResult>) {
         );
     }
 
-    path.push(Path::new(&function_name).with_extension("json"));
+    write_benchmark_file("benchmarks", function_name, &benchmarks);
+}
+
+/// Record the [savings](OptimizationSavings) of the peephole optimization in
+/// directory `benchmarks/peephole/`. Does nothing if there are no savings, for
+/// example, because the environment variable `TASMLIB_PEEPHOLE` is not set.
+pub fn write_optimization_savings(savings: Vec<OptimizationSavings>) {
+    let Some(function_name) = savings.first().map(|s| s.name.clone()) else {
+        return;
+    };
+    for fnname in savings.iter().map(|x| &x.name) {
+        assert_eq!(
+            &function_name, fnname,
+            "all fn names must agree for benchmark writing to disk"
+        );
+    }
+
+    write_benchmark_file("benchmarks/peephole", &function_name, &savings);
+}
+
+/// Like [`write_benchmarks`], but additionally records the savings over the
+/// baseline benchmark of the same [case](BenchmarkCase).
+///
+/// # Panics
+///
+/// Panics if some benchmark has no baseline of the same case.
+pub fn write_benchmarks_with_savings(
+    benchmarks: Vec<NamedBenchmarkResult>,
+    baselines: &[NamedBenchmarkResult],
+) {
+    let function_name = benchmarks[0].name.clone();
+    let compared_benchmarks = benchmarks
+        .into_iter()
+        .map(|benchmark| {
+            assert_eq!(
+                function_name, benchmark.name,
+                "all fn names must agree for benchmark writing to disk"
+            );
+            let baseline = baselines
+                .iter()
+                .find(|baseline| baseline.case == benchmark.case)
+                .expect("every benchmark case should have a baseline");
+            let savings = BenchmarkSavings::new(baseline, &benchmark.benchmark_result);
+
+            ComparedBenchmarkResult { benchmark, savings }
+        })
+        .collect::<Vec<_>>();
+
+    write_benchmark_file("benchmarks", &function_name, &compared_benchmarks);
+}
+
+fn write_benchmark_file<T: Serialize>(directory: &str, name: &str, benchmarks: &T) {
+    let mut path = PathBuf::new();
+    path.push(directory);
+    create_dir_all(&path).expect("benchmarks directory should exist");
+
+    path.push(Path::new(name).with_extension("json"));
     let output = File::create(&path).expect("open file for writing");
-    to_writer_pretty(output, &benchmarks).expect("write json to file");
+    to_writer_pretty(output, benchmarks).expect("write json to file");
 }
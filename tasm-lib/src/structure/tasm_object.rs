@@ -139,6 +139,59 @@ pub trait TasmStruct: TasmObject {
     fn destructure() -> Vec<LabelledInstruction>;
 }
 
+/// This trait defines methods for dealing with custom-defined enum types from
+/// within the VM, assuming they live in memory as they are encoded with
+/// [`BFieldCodec`].
+///
+/// Variants are referred to by their name. Since [`BFieldCodec`] only supports
+/// variants with unnamed fields, the nth field of any variant is implicitly
+/// `field_n`.
+///
+/// ### Dyn-Compatibility
+///
+/// This trait is _not_ [dyn-compatible] (previously known as “object safe”).
+///
+/// [dyn-compatible]: https://doc.rust-lang.org/reference/items/traits.html#dyn-compatibility
+pub trait TasmEnum: TasmObject {
+    /// Tasm code that returns the discriminant of the object, assuming:
+    ///  - that a pointer to the said object lives on top of the stack;
+    ///  - said object has a type that implements the [`TasmObject`] trait;
+    ///  - said object lives in memory encoded as [`BFieldCodec`] specifies.
+    ///
+    /// The discriminant is not checked for validity. See
+    /// [`TasmObject::compute_size_and_assert_valid_size_indicator`] for that.
+    ///
+    /// ```text
+    /// BEFORE: _ *object
+    /// AFTER:  _ discriminant
+    /// ```
+    fn get_discriminant() -> Vec<LabelledInstruction>;
+
+    /// Tasm code that returns a pointer to a field of the specified variant,
+    /// under the same assumptions as [`get_discriminant`](Self::get_discriminant).
+    ///
+    /// Crashes the VM if the object's discriminant does not match the specified
+    /// variant.
+    ///
+    /// ```text
+    /// BEFORE: _ *object
+    /// AFTER:  _ *field
+    /// ```
+    fn get_variant_field(variant_name: &str, field_name: &str) -> Vec<LabelledInstruction>;
+
+    /// Like [`get_variant_field`](Self::get_variant_field), but also returns
+    /// the size of the field in number of [`BFieldElement`]s.
+    ///
+    /// ```text
+    /// BEFORE: _ *object
+    /// AFTER:  _ *field field_size
+    /// ```
+    fn get_variant_field_with_size(
+        variant_name: &str,
+        field_name: &str,
+    ) -> Vec<LabelledInstruction>;
+}
+
 pub fn decode_from_memory_with_size<T: BFieldCodec>(
     memory: &HashMap<BFieldElement, BFieldElement>,
     address: BFieldElement,
@@ -267,6 +320,7 @@ mod tests {
     use super::*;
     use crate::empty_stack;
     use crate::execute_with_terminal_state;
+    use crate::linker::LinkedCode;
     use crate::list::length::Length;
     use crate::memory::FIRST_NON_DETERMINISTICALLY_INITIALIZED_MEMORY_ADDRESS;
     use crate::test_prelude::*;
@@ -890,22 +944,30 @@ mod tests {
 
             // link by hand
             let entrypoint = "entrypoint";
-            let library_code = library.all_imports();
-            let instructions = triton_asm!(
-                call {entrypoint}
-                halt
-
-                {entrypoint}:
-                    {&code}
-                    return
-
-                {&library_code}
+            let mut linked_code = LinkedCode::default();
+            linked_code.push_code(&triton_asm!(call { entrypoint } halt));
+            linked_code.push_snippet(
+                entrypoint,
+                &triton_asm!(
+                    {entrypoint}:
+                        {&code}
+                        return
+                ),
             );
+            linked_code.append(library.linked_imports());
 
-            let program = Program::new(&instructions);
+            let program = Program::new(&linked_code.code);
+            let source_map = linked_code.source_map();
             let nondeterminism = NonDeterminism::new(vec![]).with_ram(memory);
-            let final_state =
-                execute_with_terminal_state(program, &[], &stack, &nondeterminism, None).unwrap();
+            let final_state = execute_with_terminal_state(
+                program,
+                &source_map,
+                &[],
+                &stack,
+                &nondeterminism,
+                None,
+            )
+            .unwrap();
             final_state.op_stack.stack
         }
     }
@@ -1246,6 +1308,247 @@ mod tests {
         }
     }
 
+    mod enums {
+        use super::*;
+
+        #[derive(Debug, Clone, PartialEq, Eq, BFieldCodec, TasmObject, Arbitrary)]
+        enum Enum {
+            Unit,
+            Static(u64, Digest),
+            Dynamic(Vec<u32>, XFieldElement, Vec<Vec<Digest>>),
+            Nested(InnerStruct, Option<Vec<u64>>),
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq, BFieldCodec, TasmObject, Arbitrary)]
+        enum OptionLike<T>
+        where
+            T: TasmObject,
+        {
+            Nothing,
+            Something(T),
+        }
+
+        /// Run the code on a pointer to the object in memory. Returns the final
+        /// stack.
+        fn run_on_object_in_ram(
+            ram: HashMap<BFieldElement, BFieldElement>,
+            object_ptr: BFieldElement,
+            code_generator: impl FnOnce(&mut Library) -> Vec<LabelledInstruction>,
+        ) -> std::result::Result<Vec<BFieldElement>, InstructionError> {
+            let mut library = Library::new();
+            let code = code_generator(&mut library);
+            let mut linked_code = LinkedCode::default();
+            linked_code.push_code(&triton_asm!(
+                push {object_ptr}
+                {&code}
+                halt
+            ));
+            linked_code.append(library.linked_imports());
+            let program = Program::new(&linked_code.code);
+            let source_map = linked_code.source_map();
+
+            let non_determinism = NonDeterminism::default().with_ram(ram);
+            let final_state = execute_with_terminal_state(
+                program,
+                &source_map,
+                &[],
+                &empty_stack(),
+                &non_determinism,
+                None,
+            )?;
+            Ok(final_state.op_stack.stack)
+        }
+
+        fn assertion_error_id(err: InstructionError) -> i128 {
+            let InstructionError::AssertionFailed(err) = err else {
+                panic!("expected assertion failure, got: {err}");
+            };
+            err.id.unwrap()
+        }
+
+        #[proptest]
+        fn load_and_decode_from_memory(
+            #[strategy(arb())] object: Enum,
+            #[strategy(arb())] address: BFieldElement,
+        ) {
+            let mut ram = HashMap::new();
+            encode_to_memory(&mut ram, address, &object);
+            let object_again = *Enum::decode_from_memory(&ram, address).unwrap();
+            prop_assert_eq!(object, object_again);
+        }
+
+        #[proptest]
+        fn compute_size(
+            #[strategy(arb())] object: Enum,
+            #[strategy(arb())] address: BFieldElement,
+        ) {
+            let mut ram = HashMap::new();
+            encode_to_memory(&mut ram, address, &object);
+            let mut stack = run_on_object_in_ram(
+                ram,
+                address,
+                Enum::compute_size_and_assert_valid_size_indicator,
+            )
+            .unwrap();
+            prop_assert_eq!(bfe!(object.encode().len() as u64), stack.pop().unwrap());
+        }
+
+        #[proptest]
+        fn compute_size_of_generic_enum(
+            #[strategy(arb())] object: OptionLike<Vec<Digest>>,
+            #[strategy(arb())] address: BFieldElement,
+        ) {
+            let mut ram = HashMap::new();
+            encode_to_memory(&mut ram, address, &object);
+            let mut stack = run_on_object_in_ram(
+                ram,
+                address,
+                OptionLike::<Vec<Digest>>::compute_size_and_assert_valid_size_indicator,
+            )
+            .unwrap();
+            prop_assert_eq!(bfe!(object.encode().len() as u64), stack.pop().unwrap());
+        }
+
+        #[proptest]
+        fn compute_size_crashes_vm_for_invalid_discriminant(
+            #[strategy(arb())] object: Enum,
+            #[strategy(arb())] address: BFieldElement,
+            #[strategy(4..=u64::from(u32::MAX))] discriminant: u64,
+        ) {
+            let mut ram = HashMap::new();
+            encode_to_memory(&mut ram, address, &object);
+            ram.insert(address, bfe!(discriminant));
+            let err = run_on_object_in_ram(
+                ram,
+                address,
+                Enum::compute_size_and_assert_valid_size_indicator,
+            )
+            .unwrap_err();
+            prop_assert_eq!(186, assertion_error_id(err));
+        }
+
+        #[proptest]
+        fn compute_size_crashes_vm_for_invalid_size_indicator_in_variant(
+            #[strategy(arb())] list: Vec<u32>,
+            #[strategy(arb())] xfe: XFieldElement,
+            #[strategy(arb())] address: BFieldElement,
+        ) {
+            let digests: Vec<Vec<Digest>> = vec![];
+            let object = Enum::Dynamic(list.clone(), xfe, digests.clone());
+            let mut ram = HashMap::new();
+            encode_to_memory(&mut ram, address, &object);
+
+            // discriminant, field_2_si, field_2, field_1, field_0_si, field_0
+            let field_0_si_offset = 1 + 1 + digests.encode().len() + xfe.encode().len();
+            let field_0_si_ptr = address + bfe!(field_0_si_offset as u64);
+            prop_assert_eq!(bfe!(list.encode().len() as u64), ram[&field_0_si_ptr]);
+            ram.insert(field_0_si_ptr, bfe!(list.encode().len() as u64 + 1));
+
+            let err = run_on_object_in_ram(
+                ram,
+                address,
+                Enum::compute_size_and_assert_valid_size_indicator,
+            )
+            .unwrap_err();
+            prop_assert_eq!(181, assertion_error_id(err));
+        }
+
+        #[proptest]
+        fn get_discriminant(
+            #[strategy(arb())] object: Enum,
+            #[strategy(arb())] address: BFieldElement,
+        ) {
+            let mut ram = HashMap::new();
+            encode_to_memory(&mut ram, address, &object);
+            let mut stack =
+                run_on_object_in_ram(ram, address, |_| Enum::get_discriminant()).unwrap();
+            let discriminant = bfe!(object.bfield_codec_discriminant() as u64);
+            prop_assert_eq!(discriminant, stack.pop().unwrap());
+        }
+
+        #[proptest]
+        fn get_variant_fields(
+            #[strategy(arb())] field_0: Vec<u32>,
+            #[strategy(arb())] field_1: XFieldElement,
+            #[strategy(arb())] field_2: Vec<Vec<Digest>>,
+            #[strategy(arb())] address: BFieldElement,
+        ) {
+            let object = Enum::Dynamic(field_0.clone(), field_1, field_2.clone());
+            let mut ram = HashMap::new();
+            encode_to_memory(&mut ram, address, &object);
+
+            let get_field_and_size = |field_name| {
+                let code = |_: &mut Library| {
+                    triton_asm!(
+                        // _ *object
+                        dup 0
+                        {&Enum::get_variant_field("Dynamic", field_name)}
+                        pick 1
+                        {&Enum::get_variant_field_with_size("Dynamic", field_name)}
+                        // _ *field *field field_size
+                    )
+                };
+                let mut stack = run_on_object_in_ram(ram.clone(), address, code).unwrap();
+                let size = stack.pop().unwrap();
+                let field_ptr = stack.pop().unwrap();
+                assert_eq!(field_ptr, stack.pop().unwrap());
+                (field_ptr, size.value() as usize)
+            };
+
+            let (field_0_ptr, field_0_size) = get_field_and_size("field_0");
+            prop_assert_eq!(field_0.encode().len(), field_0_size);
+            prop_assert_eq!(
+                field_0,
+                *Vec::<u32>::decode_from_memory(&ram, field_0_ptr).unwrap()
+            );
+
+            let (field_1_ptr, field_1_size) = get_field_and_size("field_1");
+            prop_assert_eq!(field_1.encode().len(), field_1_size);
+            prop_assert_eq!(
+                field_1,
+                *XFieldElement::decode_from_memory(&ram, field_1_ptr).unwrap()
+            );
+
+            let (field_2_ptr, field_2_size) = get_field_and_size("field_2");
+            prop_assert_eq!(field_2.encode().len(), field_2_size);
+            prop_assert_eq!(
+                field_2,
+                *Vec::<Vec<Digest>>::decode_from_memory(&ram, field_2_ptr).unwrap()
+            );
+        }
+
+        #[test]
+        fn getting_field_of_wrong_variant_crashes_vm() {
+            let address = bfe!(42);
+            let mut ram = HashMap::new();
+            encode_to_memory(&mut ram, address, &Enum::Static(13, Digest::default()));
+
+            let code = |_: &mut Library| Enum::get_variant_field("Dynamic", "field_0");
+            let err = run_on_object_in_ram(ram, address, code).unwrap_err();
+            assert_eq!(187, assertion_error_id(err));
+        }
+
+        #[test]
+        #[should_panic(expected = "unknown variant")]
+        fn getting_field_of_unknown_variant_panics() {
+            Enum::get_variant_field("Unknown", "field_0");
+        }
+
+        #[test]
+        #[should_panic(expected = "has no fields")]
+        fn getting_field_of_unit_variant_panics() {
+            Enum::get_variant_field("Unit", "field_0");
+        }
+
+        #[test]
+        fn instantiations_of_generic_enum_have_distinct_label_friendly_names() {
+            assert_ne!(
+                OptionLike::<Vec<u64>>::label_friendly_name(),
+                OptionLike::<Vec<u32>>::label_friendly_name()
+            );
+        }
+    }
+
     #[test]
     fn test_option() {
         let mut rng = rand::rng();
@@ -4,10 +4,17 @@ use std::fmt::Display;
 use itertools::Itertools;
 use triton_vm::isa::op_stack::NUM_OP_STACK_REGISTERS;
 use triton_vm::prelude::*;
+use triton_vm::vm::CoProcessorCall;
 
+use crate::dyn_malloc::DynMalloc;
 use crate::dyn_malloc::DYN_MALLOC_ADDRESS;
 use crate::execute_test;
 use crate::execute_with_terminal_state;
+use crate::library::Library;
+use crate::linker::SourceMap;
+use crate::memory::nd_memory_region;
+use crate::peephole;
+use crate::prelude::BasicSnippet;
 use crate::prelude::Tip5;
 use crate::traits::basic_snippet::SignedOffSnippet;
 use crate::traits::rust_shadow::RustShadow;
@@ -196,9 +203,183 @@ pub fn test_rust_equivalence_given_complete_state<T: RustShadow>(
     verify_memory_equivalence("Rust-shadow", &rust.ram, "TVM", &tasm.ram);
     verify_stack_growth(shadowed_snippet, &init_stack, &tasm.op_stack.stack);
 
+    // If this environment variable is set, the snippet's writes to memory are checked against the
+    // memory convention. This is opt-in because many tests place the snippet's inputs into memory
+    // regions the snippet is then – legitimately – asked to modify.
+    if std::env::var("TASMLIB_CHECK_MEMORY_CONVENTION").is_ok() {
+        verify_memory_convention(shadowed_snippet, stack, stdin, nondeterminism, sponge);
+    }
+
+    // If this environment variable is set, the snippet's peephole-optimized code is run, too, and
+    // must behave exactly like the unoptimized code.
+    if std::env::var("TASMLIB_PEEPHOLE").is_ok() {
+        verify_peephole_equivalence(
+            shadowed_snippet,
+            stack,
+            stdin,
+            nondeterminism,
+            sponge,
+            &tasm,
+        );
+    }
+
     tasm
 }
 
+/// Assert that the snippet's [peephole-optimized](crate::peephole::optimize) code
+/// behaves exactly like its unoptimized code, which resulted in the given final
+/// state.
+pub fn verify_peephole_equivalence<T: RustShadow>(
+    shadowed_snippet: &T,
+    stack: &[BFieldElement],
+    stdin: &[BFieldElement],
+    nondeterminism: &NonDeterminism,
+    sponge: &Option<Tip5>,
+    unoptimized: &VMState,
+) {
+    let code = peephole::optimize(&shadowed_snippet.inner().link_for_isolated_run());
+    let optimized = execute_test(
+        &code,
+        &SourceMap::new(&code, &[]),
+        &mut stack.to_vec(),
+        shadowed_snippet.inner().stack_diff(),
+        stdin.to_vec(),
+        nondeterminism.clone(),
+        sponge.clone(),
+    );
+
+    assert_eq!(
+        unoptimized.public_output, optimized.public_output,
+        "Unoptimized and optimized std out must agree"
+    );
+    verify_stack_equivalence(
+        "unoptimized final stack",
+        &unoptimized.op_stack.stack,
+        "optimized final stack",
+        &optimized.op_stack.stack,
+    );
+    verify_memory_equivalence("Unoptimized", &unoptimized.ram, "Optimized", &optimized.ram);
+    assert_eq!(
+        unoptimized.sponge, optimized.sponge,
+        "Unoptimized and optimized sponge states must agree"
+    );
+}
+
+/// A single read from or write to Triton VM's RAM, as observed during execution.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct MemoryAccess {
+    /// The cycle count at which the access happened.
+    pub clk: u32,
+    pub address: BFieldElement,
+    pub value: BFieldElement,
+    pub is_write: bool,
+
+    /// The label of the innermost function that was executing when the access happened, or
+    /// `None` if no function call was in progress.
+    pub function: Option<String>,
+}
+
+/// Run the snippet in isolation, one instruction at a time, and record every access to RAM.
+///
+/// ### Panics
+///
+/// Panics if Triton VM execution fails.
+pub fn record_memory_accesses<T: RustShadow>(
+    shadowed_snippet: &T,
+    stack: &[BFieldElement],
+    stdin: &[BFieldElement],
+    nondeterminism: &NonDeterminism,
+    sponge: &Option<Tip5>,
+) -> Vec<MemoryAccess> {
+    let program = Program::new(&shadowed_snippet.inner().link_for_isolated_run());
+    let public_input = PublicInput::new(stdin.to_vec());
+    let mut vm_state = VMState::new(program.clone(), public_input, nondeterminism.clone());
+    vm_state.op_stack.stack = stack.to_vec();
+    vm_state.sponge = sponge.clone();
+
+    let mut accesses = vec![];
+    while !vm_state.halting {
+        let function = vm_state
+            .jump_stack
+            .last()
+            .map(|&(_, destination)| program.label_for_address(destination.value()));
+        let co_processor_calls = match vm_state.step() {
+            Ok(calls) => calls,
+            Err(err) => panic!("{err}\n\nFinal state was: {vm_state}"),
+        };
+
+        for call in co_processor_calls {
+            let CoProcessorCall::Ram(ram_call) = call else {
+                continue;
+            };
+            accesses.push(MemoryAccess {
+                clk: ram_call.clk,
+                address: ram_call.ram_pointer,
+                value: ram_call.ram_value,
+                is_write: ram_call.is_write,
+                function: function.clone(),
+            });
+        }
+    }
+
+    accesses
+}
+
+/// Assert that the snippet only writes to memory it may write to according to the
+/// [memory convention](crate::memory). In particular, the snippet must not write
+/// - to non-deterministically initialized memory,
+/// - to the state of the [dynamic allocator](DynMalloc), unless it is the dynamic allocator, or
+/// - to static memory that was not [allocated](Library::kmalloc) by the snippet or its
+///   dependencies.
+pub fn verify_memory_convention<T: RustShadow>(
+    shadowed_snippet: &T,
+    stack: &[BFieldElement],
+    stdin: &[BFieldElement],
+    nondeterminism: &NonDeterminism,
+    sponge: &Option<Tip5>,
+) {
+    let mut library = Library::empty();
+    shadowed_snippet.inner().annotated_code(&mut library);
+    let owned_static_memory = library.kmalloc_allocated_memory_region();
+    let dyn_malloc = DynMalloc.entrypoint();
+
+    let violated_region = |access: &MemoryAccess| {
+        let address = access.address;
+        if nd_memory_region().contains_address(address) {
+            return Some("non-deterministically initialized memory");
+        }
+        if address == DYN_MALLOC_ADDRESS && access.function.as_ref() != Some(&dyn_malloc) {
+            return Some("state of the dynamic allocator");
+        }
+        if Library::kmalloc_memory_region().contains_address(address)
+            && !owned_static_memory.contains_address(address)
+        {
+            return Some("static memory not allocated by the snippet");
+        }
+        None
+    };
+
+    let violations = record_memory_accesses(shadowed_snippet, stack, stdin, nondeterminism, sponge)
+        .into_iter()
+        .filter(|access| access.is_write)
+        .filter_map(|access| {
+            let region = violated_region(&access)?;
+            let function = access.function.unwrap_or_else(|| "-".to_string());
+            Some(format!(
+                "clk {}: write of {} to address {} ({region}) in function {function}",
+                access.clk, access.value, access.address,
+            ))
+        })
+        .collect_vec();
+
+    assert!(
+        violations.is_empty(),
+        "Snippet `{}` must adhere to the memory convention. Violations:\n{}",
+        shadowed_snippet.inner().entrypoint(),
+        violations.join("\n"),
+    );
+}
+
 pub fn link_and_run_tasm_for_test<T: RustShadow>(
     snippet_struct: &T,
     stack: &mut Vec<BFieldElement>,
@@ -206,10 +387,13 @@ pub fn link_and_run_tasm_for_test<T: RustShadow>(
     nondeterminism: NonDeterminism,
     maybe_sponge: Option<Tip5>,
 ) -> VMState {
-    let code = snippet_struct.inner().link_for_isolated_run();
+    let (code, source_map) = snippet_struct
+        .inner()
+        .link_for_isolated_run_with_source_map();
 
     execute_test(
         &code,
+        &source_map,
         stack,
         snippet_struct.inner().stack_diff(),
         std_in,
@@ -291,9 +475,10 @@ fn instruction_error_from_failing_code<S: RustShadow>(
         "Failed to fail: Rust-shadowing must panic in negative test case"
     );
 
-    let code = snippet.inner().link_for_isolated_run();
+    let (code, source_map) = snippet.inner().link_for_isolated_run_with_source_map();
     let tvm_result = execute_with_terminal_state(
         Program::new(&code),
+        &source_map,
         &init_state.public_input,
         &init_state.stack,
         &init_state.nondeterminism,
@@ -342,3 +527,158 @@ pub fn maybe_write_tvm_output_to_disk(
     let proof = serde_json::to_string(proof).unwrap();
     write!(proof_file, "{proof}").unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arithmetic::u64::div_mod::DivMod;
+    use crate::library::STATIC_MEMORY_FIRST_ADDRESS;
+    use crate::memory::arena_malloc::ArenaMalloc;
+    use crate::prelude::DataType;
+    use crate::test_prelude::*;
+
+    /// Writes a fixed word to a fixed address, irrespective of the memory convention.
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    struct WriteToAddress {
+        address: BFieldElement,
+    }
+
+    impl WriteToAddress {
+        const VALUE: u64 = 42;
+    }
+
+    impl BasicSnippet for WriteToAddress {
+        fn inputs(&self) -> Vec<(DataType, String)> {
+            vec![]
+        }
+
+        fn outputs(&self) -> Vec<(DataType, String)> {
+            vec![]
+        }
+
+        fn entrypoint(&self) -> String {
+            "tasmlib_test_helpers_write_to_address".to_string()
+        }
+
+        fn code(&self, _: &mut Library) -> Vec<LabelledInstruction> {
+            triton_asm!(
+                {self.entrypoint()}:
+                    push {Self::VALUE}
+                    push {self.address}
+                    write_mem 1
+                    pop 1
+                    return
+            )
+        }
+    }
+
+    impl Function for WriteToAddress {
+        fn rust_shadow(
+            &self,
+            _: &mut Vec<BFieldElement>,
+            memory: &mut HashMap<BFieldElement, BFieldElement>,
+        ) {
+            memory.insert(self.address, bfe!(Self::VALUE));
+        }
+
+        fn pseudorandom_initial_state(
+            &self,
+            _: [u8; 32],
+            _: Option<BenchmarkCase>,
+        ) -> FunctionInitialState {
+            FunctionInitialState {
+                stack: self.init_stack_for_isolated_run(),
+                memory: HashMap::default(),
+            }
+        }
+    }
+
+    fn verify_memory_convention_of_function(function: impl Function + 'static, seed: [u8; 32]) {
+        let FunctionInitialState { stack, memory } =
+            function.pseudorandom_initial_state(seed, None);
+        let nondeterminism = NonDeterminism::default().with_ram(memory);
+        let shadowed_function = ShadowedFunction::new(function);
+        verify_memory_convention(&shadowed_function, &stack, &[], &nondeterminism, &None);
+    }
+
+    #[proptest(cases = 10)]
+    fn peephole_optimized_code_behaves_like_unoptimized_code(#[strategy(arb())] seed: [u8; 32]) {
+        let FunctionInitialState { stack, memory } = DivMod.pseudorandom_initial_state(seed, None);
+        let nondeterminism = NonDeterminism::default().with_ram(memory);
+        let shadowed_function = ShadowedFunction::new(DivMod);
+        let unoptimized = tasm_final_state(
+            &shadowed_function,
+            &stack,
+            &[],
+            nondeterminism.clone(),
+            &None,
+        );
+        verify_peephole_equivalence(
+            &shadowed_function,
+            &stack,
+            &[],
+            &nondeterminism,
+            &None,
+            &unoptimized,
+        );
+    }
+
+    #[test]
+    fn memory_accesses_are_attributed_to_innermost_function() {
+        let address = bfe!(1_u64 << 32);
+        let snippet = WriteToAddress { address };
+        let accesses = record_memory_accesses(
+            &ShadowedFunction::new(snippet),
+            &snippet.init_stack_for_isolated_run(),
+            &[],
+            &NonDeterminism::default(),
+            &None,
+        );
+
+        let [access] = accesses.try_into().unwrap();
+        assert!(access.is_write);
+        assert_eq!(address, access.address);
+        assert_eq!(bfe!(WriteToAddress::VALUE), access.value);
+        assert_eq!(Some(snippet.entrypoint()), access.function);
+    }
+
+    #[proptest(cases = 10)]
+    fn allocators_adhere_to_memory_convention(#[strategy(arb())] seed: [u8; 32]) {
+        verify_memory_convention_of_function(DynMalloc, seed);
+        verify_memory_convention_of_function(ArenaMalloc, seed);
+    }
+
+    #[proptest(cases = 10)]
+    fn writing_to_statically_allocated_memory_adheres_to_memory_convention(
+        #[strategy(arb())] seed: [u8; 32],
+    ) {
+        verify_memory_convention_of_function(DivMod, seed);
+    }
+
+    #[test]
+    fn writing_to_dynamically_allocated_page_adheres_to_memory_convention() {
+        let address = bfe!(1_u64 << 32);
+        verify_memory_convention_of_function(WriteToAddress { address }, [0; 32]);
+    }
+
+    #[test]
+    #[should_panic(expected = "non-deterministically initialized memory")]
+    fn writing_to_nd_memory_violates_memory_convention() {
+        let address = bfe!(42);
+        verify_memory_convention_of_function(WriteToAddress { address }, [0; 32]);
+    }
+
+    #[test]
+    #[should_panic(expected = "state of the dynamic allocator")]
+    fn writing_to_dyn_malloc_state_violates_memory_convention() {
+        let address = DYN_MALLOC_ADDRESS;
+        verify_memory_convention_of_function(WriteToAddress { address }, [0; 32]);
+    }
+
+    #[test]
+    #[should_panic(expected = "static memory not allocated by the snippet")]
+    fn writing_to_unallocated_static_memory_violates_memory_convention() {
+        let address = STATIC_MEMORY_FIRST_ADDRESS;
+        verify_memory_convention_of_function(WriteToAddress { address }, [0; 32]);
+    }
+}
@@ -6,8 +6,10 @@ use triton_vm::prelude::*;
 use super::basic_snippet::BasicSnippet;
 use super::rust_shadow::RustShadow;
 use crate::linker::execute_bench;
+use crate::linker::maybe_execute_peephole_bench;
 use crate::prelude::Tip5;
 use crate::snippet_bencher::write_benchmarks;
+use crate::snippet_bencher::write_optimization_savings;
 use crate::snippet_bencher::BenchmarkCase;
 use crate::snippet_bencher::NamedBenchmarkResult;
 use crate::test_helpers::test_rust_equivalence_given_complete_state;
@@ -127,6 +129,7 @@ where
                 .unwrap(),
         );
         let mut benchmarks = Vec::with_capacity(2);
+        let mut optimization_savings = vec![];
 
         for bench_case in [BenchmarkCase::CommonCase, BenchmarkCase::WorstCase] {
             let AccessorInitialState { stack, memory } = self
@@ -134,6 +137,15 @@ where
                 .pseudorandom_initial_state(rng.random(), Some(bench_case));
             let program = self.accessor.link_for_isolated_run();
             let nd = NonDeterminism::default().with_ram(memory);
+            optimization_savings.extend(maybe_execute_peephole_bench(
+                self.accessor.entrypoint(),
+                bench_case,
+                &program,
+                &stack,
+                vec![],
+                nd.clone(),
+                None,
+            ));
             let benchmark = execute_bench(&program, &stack, vec![], nd, None);
             let benchmark = NamedBenchmarkResult {
                 name: self.accessor.entrypoint(),
@@ -144,5 +156,6 @@ where
         }
 
         write_benchmarks(benchmarks);
+        write_optimization_savings(optimization_savings);
     }
 }
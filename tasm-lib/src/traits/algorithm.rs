@@ -6,8 +6,10 @@ use triton_vm::prelude::*;
 use super::basic_snippet::BasicSnippet;
 use super::rust_shadow::RustShadow;
 use crate::linker::execute_bench;
+use crate::linker::maybe_execute_peephole_bench;
 use crate::prelude::Tip5;
 use crate::snippet_bencher::write_benchmarks;
+use crate::snippet_bencher::write_optimization_savings;
 use crate::snippet_bencher::BenchmarkCase;
 use crate::snippet_bencher::NamedBenchmarkResult;
 use crate::test_helpers::test_rust_equivalence_given_complete_state;
@@ -157,6 +159,7 @@ where
                 .unwrap(),
         );
         let mut benchmarks = Vec::with_capacity(2);
+        let mut optimization_savings = vec![];
 
         for bench_case in [BenchmarkCase::CommonCase, BenchmarkCase::WorstCase] {
             let AlgorithmInitialState {
@@ -166,6 +169,15 @@ where
                 .algorithm
                 .pseudorandom_initial_state(rng.random(), Some(bench_case));
             let program = self.algorithm.link_for_isolated_run();
+            optimization_savings.extend(maybe_execute_peephole_bench(
+                self.algorithm.entrypoint(),
+                bench_case,
+                &program,
+                &stack,
+                vec![],
+                nondeterminism.clone(),
+                None,
+            ));
             let benchmark = execute_bench(&program, &stack, vec![], nondeterminism, None);
             let benchmark = NamedBenchmarkResult {
                 name: self.algorithm.entrypoint(),
@@ -176,5 +188,6 @@ where
         }
 
         write_benchmarks(benchmarks);
+        write_optimization_savings(optimization_savings);
     }
 }
@@ -10,6 +10,8 @@ use triton_vm::isa::instruction::AnInstruction;
 use triton_vm::isa::op_stack::NUM_OP_STACK_REGISTERS;
 use triton_vm::prelude::*;
 
+use crate::linker::LinkedCode;
+use crate::linker::SourceMap;
 use crate::prelude::*;
 use crate::push_encodable;
 
@@ -75,42 +77,42 @@ pub trait BasicSnippet {
     fn link_for_isolated_run_populated_static_memory(
         &self,
         words_statically_allocated: u32,
-    ) -> Vec<LabelledInstruction> {
+    ) -> (Vec<LabelledInstruction>, SourceMap) {
         let mut library = Library::with_preallocated_memory(words_statically_allocated);
         let entrypoint = self.entrypoint();
         let function_body = self.annotated_code(&mut library);
-        let library_code = library.all_imports();
 
         // The TASM code is always run through a function call, so the 1st instruction is a call to
         // the function in question.
-        let code = triton_asm!(
-            call {entrypoint}
-            halt
+        let mut linked_code = LinkedCode::default();
+        linked_code.push_code(&triton_asm!(call { entrypoint } halt));
+        linked_code.push_snippet(&entrypoint, &function_body);
+        linked_code.append(library.linked_imports());
 
-            {&function_body}
-            {&library_code}
-        );
-
-        code
+        let source_map = linked_code.source_map();
+        (linked_code.code, source_map)
     }
 
     fn link_for_isolated_run(&self) -> Vec<LabelledInstruction> {
+        self.link_for_isolated_run_with_source_map().0
+    }
+
+    /// Like [`link_for_isolated_run`](Self::link_for_isolated_run), but additionally
+    /// returns the [source map](SourceMap) the linker recorded.
+    fn link_for_isolated_run_with_source_map(&self) -> (Vec<LabelledInstruction>, SourceMap) {
         let mut library = Library::empty();
         let entrypoint = self.entrypoint();
         let function_body = self.annotated_code(&mut library);
-        let library_code = library.all_imports();
 
         // The TASM code is always run through a function call, so the 1st instruction is a call to
         // the function in question.
-        let code = triton_asm!(
-            call {entrypoint}
-            halt
-
-            {&function_body}
-            {&library_code}
-        );
+        let mut linked_code = LinkedCode::default();
+        linked_code.push_code(&triton_asm!(call { entrypoint } halt));
+        linked_code.push_snippet(&entrypoint, &function_body);
+        linked_code.append(library.linked_imports());
 
-        code
+        let source_map = linked_code.source_map();
+        (linked_code.code, source_map)
     }
 
     /// Initial stack on program start, when the snippet runs in isolation.
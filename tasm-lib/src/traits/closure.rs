@@ -4,9 +4,11 @@ use triton_vm::prelude::*;
 use super::basic_snippet::BasicSnippet;
 use super::rust_shadow::RustShadow;
 use crate::linker::execute_bench;
+use crate::linker::maybe_execute_peephole_bench;
 use crate::prelude::Tip5;
 use crate::push_encodable;
 use crate::snippet_bencher::write_benchmarks;
+use crate::snippet_bencher::write_optimization_savings;
 use crate::snippet_bencher::BenchmarkCase;
 use crate::snippet_bencher::NamedBenchmarkResult;
 use crate::test_helpers::test_rust_equivalence_given_complete_state;
@@ -117,6 +119,7 @@ impl<C: Closure> RustShadow for ShadowedClosure<C> {
                 .unwrap(),
         );
         let mut benchmarks = Vec::with_capacity(2);
+        let mut optimization_savings = vec![];
 
         for bench_case in [BenchmarkCase::CommonCase, BenchmarkCase::WorstCase] {
             let args = self
@@ -124,6 +127,15 @@ impl<C: Closure> RustShadow for ShadowedClosure<C> {
                 .pseudorandom_args(rng.random(), Some(bench_case));
             let stack = self.closure.set_up_test_stack(args);
             let program = self.closure.link_for_isolated_run();
+            optimization_savings.extend(maybe_execute_peephole_bench(
+                self.closure.entrypoint(),
+                bench_case,
+                &program,
+                &stack,
+                vec![],
+                NonDeterminism::new(vec![]),
+                None,
+            ));
             let benchmark =
                 execute_bench(&program, &stack, vec![], NonDeterminism::new(vec![]), None);
             let benchmark = NamedBenchmarkResult {
@@ -135,5 +147,6 @@ impl<C: Closure> RustShadow for ShadowedClosure<C> {
         }
 
         write_benchmarks(benchmarks);
+        write_optimization_savings(optimization_savings);
     }
 }
@@ -3,6 +3,9 @@ use anyhow::Result;
 use triton_vm::prelude::*;
 
 use crate::library::Library;
+use crate::linker::LinkedCode;
+use crate::linker::SourceMap;
+use crate::peephole;
 use crate::snippet_bencher::BenchmarkResult;
 
 pub trait CompiledProgram {
@@ -12,25 +15,65 @@ pub trait CompiledProgram {
     ) -> Result<Vec<BFieldElement>>;
 
     fn program() -> Program {
+        Self::program_with_source_map().0
+    }
+
+    /// The [program](Self::program), together with the [source map](SourceMap)
+    /// the linker recorded.
+    fn program_with_source_map() -> (Program, SourceMap) {
         let (program_instructions, library) = Self::code();
-        let library_instructions = library.all_imports();
-        Program::new(&[program_instructions, library_instructions].concat())
+        let library_code = if Self::eliminate_dead_code() {
+            library.linked_reachable_imports(&program_instructions)
+        } else {
+            library.linked_imports()
+        };
+
+        let mut linked_code = LinkedCode::default();
+        linked_code.push_code(&program_instructions);
+        linked_code.append(library_code);
+        if let Some(threshold) = Self::inlining_threshold() {
+            linked_code = linked_code.inline_small_functions(threshold);
+        }
+
+        (Program::new(&linked_code.code), linked_code.source_map())
+    }
+
+    /// The [program](Self::program) after [peephole optimization](peephole::optimize).
+    fn optimized_program() -> Program {
+        let code = Self::program().labelled_instructions();
+        Program::new(&peephole::optimize(&code))
     }
 
+    /// Run the [program](Self::program). On failure, the error carries the
+    /// symbolic call stack of the crashed program.
     fn run(
         public_input: &PublicInput,
         nondeterminism: &NonDeterminism,
     ) -> Result<Vec<BFieldElement>> {
-        VM::run(
-            Self::program(),
-            public_input.clone(),
-            nondeterminism.clone(),
-        )
-        .map_err(|err| anyhow!(err))
+        let (program, source_map) = Self::program_with_source_map();
+        VM::run(program, public_input.clone(), nondeterminism.clone()).map_err(|err| {
+            let call_stack = source_map.format_call_stack(&err.vm_state);
+            anyhow!(err).context(format!(
+                "Triton VM execution failed. Call stack:\n{call_stack}"
+            ))
+        })
     }
 
     fn code() -> (Vec<LabelledInstruction>, Library);
 
+    /// If set, only [reachable](Library::reachable_imports) snippets are linked.
+    /// This changes the program digest.
+    fn eliminate_dead_code() -> bool {
+        false
+    }
+
+    /// If set, [small functions](crate::linker::inline_small_functions) of fewer than
+    /// this many instructions are inlined at their call sites. This changes the
+    /// program digest.
+    fn inlining_threshold() -> Option<usize> {
+        None
+    }
+
     fn crash_conditions() -> Vec<String> {
         vec![]
     }
@@ -61,10 +104,7 @@ pub fn bench_and_profile_program<P: CompiledProgram>(
 
     use crate::snippet_bencher::NamedBenchmarkResult;
 
-    let (program_instructions, library) = P::code();
-    let library_instructions = library.all_imports();
-    let all_instructions = [program_instructions, library_instructions].concat();
-    let program = Program::new(&all_instructions);
+    let program = P::program();
 
     // run in trace mode to get table heights
     let (aet, _output) = VM::trace_execution(
@@ -98,7 +138,11 @@ pub fn bench_and_profile_program<P: CompiledProgram>(
 
 #[cfg(test)]
 mod tests {
+    use triton_vm::isa::instruction::AnInstruction;
+
     use super::*;
+    use crate::arithmetic::u64::incr::Incr;
+    use crate::prelude::BasicSnippet;
 
     pub(super) struct FiboTest;
 
@@ -152,6 +196,122 @@ mod tests {
         let nondeterminism = NonDeterminism::new(vec![]);
         test_rust_shadow::<FiboTest>(&public_input, &nondeterminism);
     }
+
+    #[test]
+    fn dead_code_elimination_is_opt_in() {
+        fn code_importing_unused_snippet() -> (Vec<LabelledInstruction>, Library) {
+            let mut library = Library::new();
+            let _ = library.import(Box::new(Incr));
+            (triton_asm!(halt), library)
+        }
+
+        struct AllImports;
+        struct ReachableImports;
+
+        impl CompiledProgram for AllImports {
+            fn rust_shadow(_: &PublicInput, _: &NonDeterminism) -> Result<Vec<BFieldElement>> {
+                Ok(vec![])
+            }
+
+            fn code() -> (Vec<LabelledInstruction>, Library) {
+                code_importing_unused_snippet()
+            }
+        }
+
+        impl CompiledProgram for ReachableImports {
+            fn rust_shadow(_: &PublicInput, _: &NonDeterminism) -> Result<Vec<BFieldElement>> {
+                Ok(vec![])
+            }
+
+            fn code() -> (Vec<LabelledInstruction>, Library) {
+                code_importing_unused_snippet()
+            }
+
+            fn eliminate_dead_code() -> bool {
+                true
+            }
+        }
+
+        let (code, library) = code_importing_unused_snippet();
+        let all_imports = Program::new(&[code.clone(), library.all_imports()].concat());
+        assert_eq!(all_imports, AllImports::program());
+        assert_eq!(Program::new(&code), ReachableImports::program());
+    }
+
+    #[test]
+    fn inlining_preserves_behavior_and_removes_calls() {
+        struct IncrTwice;
+
+        impl CompiledProgram for IncrTwice {
+            fn rust_shadow(
+                public_input: &PublicInput,
+                _: &NonDeterminism,
+            ) -> Result<Vec<BFieldElement>> {
+                let [hi, lo] = public_input.individual_tokens[..] else {
+                    anyhow::bail!("expected two words of input");
+                };
+                let value = (hi.value() << 32) + lo.value() + 2;
+                Ok(bfe_vec![value & u64::from(u32::MAX), value >> 32])
+            }
+
+            fn code() -> (Vec<LabelledInstruction>, Library) {
+                let mut library = Library::new();
+                let incr = library.import(Box::new(Incr));
+                let code = triton_asm!(
+                    read_io 2
+                    call {incr}
+                    call {incr}
+                    write_io 2
+                    halt
+                );
+
+                (code, library)
+            }
+
+            fn inlining_threshold() -> Option<usize> {
+                Some(100)
+            }
+        }
+
+        let public_input = PublicInput::new(bfe_vec![3, u32::MAX - 1]);
+        let nondeterminism = NonDeterminism::default();
+        test_rust_shadow::<IncrTwice>(&public_input, &nondeterminism);
+
+        let program = IncrTwice::program().labelled_instructions();
+        let calls_incr = program.iter().any(|item| {
+            item == &LabelledInstruction::Instruction(AnInstruction::Call(Incr.entrypoint()))
+        });
+        assert!(!calls_incr);
+    }
+
+    #[test]
+    fn crash_reports_symbolic_call_stack() {
+        struct IncrMax;
+
+        impl CompiledProgram for IncrMax {
+            fn rust_shadow(_: &PublicInput, _: &NonDeterminism) -> Result<Vec<BFieldElement>> {
+                anyhow::bail!("u64::MAX cannot be incremented")
+            }
+
+            fn code() -> (Vec<LabelledInstruction>, Library) {
+                let mut library = Library::new();
+                let incr = library.import(Box::new(Incr));
+                let code = triton_asm!(
+                    read_io 2
+                    call {incr}
+                    halt
+                );
+
+                (code, library)
+            }
+        }
+
+        let public_input = PublicInput::new(bfe_vec![u32::MAX, u32::MAX]);
+        let err = IncrMax::run(&public_input, &NonDeterminism::default()).unwrap_err();
+        let err = err.to_string();
+        assert!(err.contains(&format!("{}+", Incr.entrypoint())), "{err}");
+        assert!(err.contains(&format!("{}+2", SourceMap::PROGRAM)), "{err}");
+    }
 }
 
 #[cfg(test)]
@@ -6,8 +6,11 @@ use triton_vm::prelude::*;
 use super::basic_snippet::BasicSnippet;
 use super::rust_shadow::RustShadow;
 use crate::linker::execute_bench;
+use crate::linker::maybe_execute_peephole_bench;
+use crate::peephole::OptimizationSavings;
 use crate::prelude::Tip5;
 use crate::snippet_bencher::write_benchmarks;
+use crate::snippet_bencher::write_optimization_savings;
 use crate::snippet_bencher::BenchmarkCase;
 use crate::snippet_bencher::NamedBenchmarkResult;
 use crate::test_helpers::test_rust_equivalence_given_complete_state;
@@ -77,6 +80,53 @@ impl<F: Function> ShadowedFunction<F> {
 }
 
 impl<P: Function> ShadowedFunction<P> {
+    /// Count number of cycles and other performance indicators for all
+    /// [benchmark cases](BenchmarkCase).
+    pub fn benchmarks(&self) -> Vec<NamedBenchmarkResult> {
+        self.benchmarks_and_optimization_savings().0
+    }
+
+    /// Like [`benchmarks`](Self::benchmarks), but additionally returns the
+    /// [savings](OptimizationSavings) of the peephole optimization if the
+    /// environment variable `TASMLIB_PEEPHOLE` is set.
+    fn benchmarks_and_optimization_savings(
+        &self,
+    ) -> (Vec<NamedBenchmarkResult>, Vec<OptimizationSavings>) {
+        let seed = hex::decode("73a24b6b8b32e4d7d563a4d9a85f476573a24b6b8b32e4d7d563a4d9a85f4765")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let mut rng = StdRng::from_seed(seed);
+        let mut benchmarks = Vec::with_capacity(2);
+        let mut optimization_savings = vec![];
+
+        for bench_case in [BenchmarkCase::CommonCase, BenchmarkCase::WorstCase] {
+            let FunctionInitialState { stack, memory } = self
+                .function
+                .pseudorandom_initial_state(rng.random(), Some(bench_case));
+            let program = self.function.link_for_isolated_run();
+            let non_determinism = NonDeterminism::default().with_ram(memory);
+            optimization_savings.extend(maybe_execute_peephole_bench(
+                self.function.entrypoint(),
+                bench_case,
+                &program,
+                &stack,
+                vec![],
+                non_determinism.clone(),
+                None,
+            ));
+            let benchmark = execute_bench(&program, &stack, vec![], non_determinism, None);
+            let benchmark = NamedBenchmarkResult {
+                name: self.function.entrypoint(),
+                benchmark_result: benchmark,
+                case: bench_case,
+            };
+            benchmarks.push(benchmark);
+        }
+
+        (benchmarks, optimization_savings)
+    }
+
     fn test_initial_state(&self, state: FunctionInitialState) {
         let FunctionInitialState { stack, memory } = state;
 
@@ -135,28 +185,8 @@ where
     /// Count number of cycles and other performance indicators and save them in directory
     /// benchmarks/.
     fn bench(&self) {
-        let seed = hex::decode("73a24b6b8b32e4d7d563a4d9a85f476573a24b6b8b32e4d7d563a4d9a85f4765")
-            .unwrap()
-            .try_into()
-            .unwrap();
-        let mut rng = StdRng::from_seed(seed);
-        let mut benchmarks = Vec::with_capacity(2);
-
-        for bench_case in [BenchmarkCase::CommonCase, BenchmarkCase::WorstCase] {
-            let FunctionInitialState { stack, memory } = self
-                .function
-                .pseudorandom_initial_state(rng.random(), Some(bench_case));
-            let program = self.function.link_for_isolated_run();
-            let non_determinism = NonDeterminism::default().with_ram(memory);
-            let benchmark = execute_bench(&program, &stack, vec![], non_determinism, None);
-            let benchmark = NamedBenchmarkResult {
-                name: self.function.entrypoint(),
-                benchmark_result: benchmark,
-                case: bench_case,
-            };
-            benchmarks.push(benchmark);
-        }
-
+        let (benchmarks, optimization_savings) = self.benchmarks_and_optimization_savings();
         write_benchmarks(benchmarks);
+        write_optimization_savings(optimization_savings);
     }
 }
@@ -7,7 +7,9 @@ use triton_vm::prelude::*;
 use super::basic_snippet::BasicSnippet;
 use super::rust_shadow::RustShadow;
 use crate::linker::execute_bench;
+use crate::linker::maybe_execute_peephole_bench;
 use crate::snippet_bencher::write_benchmarks;
+use crate::snippet_bencher::write_optimization_savings;
 use crate::snippet_bencher::BenchmarkCase;
 use crate::snippet_bencher::NamedBenchmarkResult;
 use crate::test_helpers::test_rust_equivalence_given_complete_state;
@@ -150,6 +152,7 @@ where
                 .unwrap(),
         );
         let mut benchmarks = Vec::with_capacity(2);
+        let mut optimization_savings = vec![];
 
         for bench_case in [BenchmarkCase::CommonCase, BenchmarkCase::WorstCase] {
             let MemPreserverInitialState {
@@ -161,6 +164,15 @@ where
                 .mem_preserver
                 .pseudorandom_initial_state(rng.random(), Some(bench_case));
             let program = self.mem_preserver.link_for_isolated_run();
+            optimization_savings.extend(maybe_execute_peephole_bench(
+                self.mem_preserver.entrypoint(),
+                bench_case,
+                &program,
+                &stack,
+                public_input.clone().into(),
+                non_determinism.clone(),
+                sponge_state.clone(),
+            ));
             let benchmark = execute_bench(
                 &program,
                 &stack,
@@ -177,5 +189,6 @@ where
         }
 
         write_benchmarks(benchmarks);
+        write_optimization_savings(optimization_savings);
     }
 }
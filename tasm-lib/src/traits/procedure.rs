@@ -4,8 +4,10 @@ use rand::prelude::*;
 use triton_vm::prelude::*;
 
 use crate::linker::execute_bench;
+use crate::linker::maybe_execute_peephole_bench;
 use crate::prelude::Tip5;
 use crate::snippet_bencher::write_benchmarks;
+use crate::snippet_bencher::write_optimization_savings;
 use crate::snippet_bencher::BenchmarkCase;
 use crate::snippet_bencher::NamedBenchmarkResult;
 use crate::test_helpers::test_rust_equivalence_given_complete_state;
@@ -126,6 +128,7 @@ impl<P: Procedure> RustShadow for ShadowedProcedure<P> {
                 .unwrap(),
         );
         let mut benchmarks = Vec::with_capacity(2);
+        let mut optimization_savings = vec![];
 
         for bench_case in [BenchmarkCase::CommonCase, BenchmarkCase::WorstCase] {
             let ProcedureInitialState {
@@ -137,6 +140,15 @@ impl<P: Procedure> RustShadow for ShadowedProcedure<P> {
                 .procedure
                 .pseudorandom_initial_state(rng.random(), Some(bench_case));
             let program = self.procedure.link_for_isolated_run();
+            optimization_savings.extend(maybe_execute_peephole_bench(
+                self.procedure.entrypoint(),
+                bench_case,
+                &program,
+                &stack,
+                public_input.clone(),
+                nondeterminism.clone(),
+                sponge.clone(),
+            ));
             let benchmark = execute_bench(&program, &stack, public_input, nondeterminism, sponge);
             let benchmark = NamedBenchmarkResult {
                 name: self.procedure.entrypoint(),
@@ -147,6 +159,7 @@ impl<P: Procedure> RustShadow for ShadowedProcedure<P> {
         }
 
         write_benchmarks(benchmarks);
+        write_optimization_savings(optimization_savings);
     }
 }
 
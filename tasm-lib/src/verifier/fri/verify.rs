@@ -166,8 +166,8 @@ impl BasicSnippet for FriSnippet {
         let reduce_indices_label = format!("{entrypoint}_reduce_indices");
         let map_buffer_len = Map::NUM_INTERNAL_REGISTERS;
         let map_reduce_indices =
-            library.import(Box::new(Map::new(InnerFunction::RawCode(RawCode {
-                function: triton_asm! {
+            library.import(Box::new(Map::new(InnerFunction::RawCode(RawCode::new(
+                triton_asm! {
                     {reduce_indices_label}:
                                         // _ half_domain_length [bu ff er] index
                     dup {map_buffer_len + 1}
@@ -176,16 +176,14 @@ impl BasicSnippet for FriSnippet {
                     pick 1 pop 1        // _ half_domain_length [bu ff er] index%half_domain_length
                     return
                 },
-                input_type: DataType::U32,
-                output_type: DataType::U32,
-            }))));
+                DataType::U32,
+                DataType::U32,
+            )))));
         let compute_c_values_loop = format!("{entrypoint}_compute_c_values_loop");
         let assert_membership_label = format!("{entrypoint}_assert_codeword_membership");
         let map_assert_membership =
-            library.import(Box::new(Map::new(InnerFunction::RawCode(RawCode {
-                input_type: DataType::Tuple(vec![DataType::U32, DataType::Xfe]),
-                output_type: DataType::Tuple(vec![DataType::U32, DataType::Xfe]),
-                function: triton_asm! {
+            library.import(Box::new(Map::new(InnerFunction::RawCode(RawCode::new(
+                triton_asm! {
                     // BEFORE: _ *codeword [bu ff er] index xfe2 xfe1 xfe0
                     // AFTER:  _ *codeword [bu ff er] index xfe2 xfe1 xfe0
                     {assert_membership_label}:
@@ -201,7 +199,9 @@ impl BasicSnippet for FriSnippet {
                         pop 1                   // _ *codeword [bu ff er] index xfe2 xfe1 xfe0
                         return
                 },
-            }))));
+                DataType::Tuple(vec![DataType::U32, DataType::Xfe]),
+                DataType::Tuple(vec![DataType::U32, DataType::Xfe]),
+            )))));
 
         let verify_a_values_authentication_paths_against_input_codeword = triton_asm!(
             // _ *vm_proof_iter *fri_verify num_rounds last_round_max_degree *last_codeword *roots *alphas dom_len *indices *a_elements num_leafs
@@ -616,8 +616,10 @@ mod tests {
         ) -> (Vec<XFieldElement>, BFieldElement) {
             let (init_memory, stack) = self.prepare_tvm_memory_and_stack(input_values);
 
+            let (code, source_map) = self.link_for_isolated_run_with_source_map();
             let final_state = execute_test(
-                &self.link_for_isolated_run(),
+                &code,
+                &source_map,
                 &mut stack.clone(),
                 self.stack_diff(),
                 vec![],
@@ -252,8 +252,10 @@ mod tests {
                 ],
             ]
             .concat();
+            let (code, source_map) = self.link_for_isolated_run_with_source_map();
             let final_state = execute_test(
-                &self.link_for_isolated_run(),
+                &code,
+                &source_map,
                 &mut stack.clone(),
                 self.stack_diff(),
                 vec![],
@@ -42,22 +42,26 @@ impl BasicSnippet for OwnProgramDigest {
 mod tests {
     use super::*;
     use crate::execute_with_terminal_state;
+    use crate::linker::SourceMap;
 
     #[derive(Debug, Clone, Eq, PartialEq)]
     struct ProgramSetup {
         program: Program,
+        source_map: SourceMap,
         program_digest: Digest,
         init_stack: Vec<BFieldElement>,
     }
 
     fn test_program() -> ProgramSetup {
         let snippet = OwnProgramDigest;
-        let program = Program::new(&snippet.link_for_isolated_run());
+        let (code, source_map) = snippet.link_for_isolated_run_with_source_map();
+        let program = Program::new(&code);
         let program_digest = program.hash();
         let init_stack = snippet.init_stack_for_isolated_run();
 
         ProgramSetup {
             program,
+            source_map,
             program_digest,
             init_stack,
         }
@@ -69,6 +73,7 @@ mod tests {
 
         let vm_end_state = execute_with_terminal_state(
             test_setup.program,
+            &test_setup.source_map,
             &[],
             &test_setup.init_stack,
             &NonDeterminism::default(),
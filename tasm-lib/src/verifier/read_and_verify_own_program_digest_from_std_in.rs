@@ -45,10 +45,13 @@ impl BasicSnippet for ReadAndVerifyOwnProgramDigestFromStdIn {
 mod tests {
     use super::*;
     use crate::execute_with_terminal_state;
+    use crate::linker::LinkedCode;
+    use crate::linker::SourceMap;
 
     #[derive(Debug, Clone, Eq, PartialEq)]
     struct ProgramSetup {
         program: Program,
+        source_map: SourceMap,
         program_digest: Digest,
         init_stack: Vec<BFieldElement>,
     }
@@ -56,18 +59,18 @@ mod tests {
     fn test_program() -> ProgramSetup {
         let snippet = ReadAndVerifyOwnProgramDigestFromStdIn;
         let snippet_code = snippet.annotated_code(&mut Library::empty());
-        let code_for_test = triton_asm!(
-            call {snippet.entrypoint()}
-            halt
-            {&snippet_code}
-        );
+        let mut code_for_test = LinkedCode::default();
+        code_for_test.push_code(&triton_asm!(call {snippet.entrypoint()} halt));
+        code_for_test.push_snippet(&snippet.entrypoint(), &snippet_code);
 
-        let program = Program::new(&code_for_test);
+        let program = Program::new(&code_for_test.code);
+        let source_map = code_for_test.source_map();
         let program_digest = program.hash();
         let init_stack = snippet.init_stack_for_isolated_run();
 
         ProgramSetup {
             program,
+            source_map,
             program_digest,
             init_stack,
         }
@@ -80,6 +83,7 @@ mod tests {
         let std_in = test_setup.program_digest.reversed().values();
         let vm_end_state = execute_with_terminal_state(
             test_setup.program,
+            &test_setup.source_map,
             &std_in,
             &test_setup.init_stack,
             &NonDeterminism::default(),
@@ -103,6 +107,7 @@ mod tests {
         let bad_std_in = Digest::default().encode();
         let execution_result = execute_with_terminal_state(
             test_setup.program,
+            &test_setup.source_map,
             &bad_std_in,
             &test_setup.init_stack,
             &NonDeterminism::default(),
@@ -1202,6 +1202,7 @@ pub mod tests {
 
     use super::*;
     use crate::execute_test;
+    use crate::maybe_write_debuggable_vm_state_and_source_map_to_disk;
     use crate::maybe_write_debuggable_vm_state_to_disk;
     use crate::memory::encode_to_memory;
     use crate::memory::FIRST_NON_DETERMINISTICALLY_INITIALIZED_MEMORY_ADDRESS;
@@ -1237,9 +1238,10 @@ pub mod tests {
             vec![claim_pointer, default_proof_pointer],
         ]
         .concat();
-        let code = snippet.link_for_isolated_run_populated_static_memory(claim_size);
+        let (code, source_map) = snippet.link_for_isolated_run_populated_static_memory(claim_size);
         let _final_tasm_state = execute_test(
             &code,
+            &source_map,
             &mut init_stack,
             snippet.stack_diff(),
             vec![],
@@ -1278,15 +1280,16 @@ pub mod tests {
             vec![claim_pointer, default_proof_pointer],
         ]
         .concat();
-        let code = snippet.link_for_isolated_run_populated_static_memory(claim_size);
+        let (code, source_map) = snippet.link_for_isolated_run_populated_static_memory(claim_size);
 
         let program = Program::new(&code);
         let mut vm_state = VMState::new(program, [].into(), non_determinism.clone());
         vm_state.op_stack.stack = init_stack.clone();
-        maybe_write_debuggable_vm_state_to_disk(&vm_state);
+        maybe_write_debuggable_vm_state_and_source_map_to_disk(&vm_state, Some(&source_map));
 
         let final_tasm_state = execute_test(
             &code,
+            &source_map,
             &mut init_stack,
             snippet.stack_diff(),
             vec![],
@@ -1640,7 +1643,7 @@ mod benches {
             vec![claim_pointer, default_proof_pointer],
         ]
         .concat();
-        let code = snippet.link_for_isolated_run_populated_static_memory(claim_size);
+        let (code, _) = snippet.link_for_isolated_run_populated_static_memory(claim_size);
         let program = prepend_program_with_stack_setup(&init_stack, &Program::new(&code));
 
         let name = snippet.entrypoint();
@@ -653,8 +653,10 @@ mod tests {
     fn disallow_too_big_dynamically_sized_proof_item() {
         let dequeue_next_as = DequeueNextAs::new(ProofItemVariant::MasterMainTableRows);
         let initial_state = initial_state_with_too_big_master_table_rows();
+        let (code, source_map) = dequeue_next_as.link_for_isolated_run_with_source_map();
         let tvm_result = execute_with_terminal_state(
-            Program::new(&dequeue_next_as.link_for_isolated_run()),
+            Program::new(&code),
+            &source_map,
             &[],
             &initial_state.stack,
             &initial_state.nondeterminism,
@@ -695,9 +697,10 @@ mod tests {
     fn disallow_trailing_zeros_in_xfe_poly_encoding() {
         let dequeue_next_as = DequeueNextAs::new(ProofItemVariant::FriPolynomial);
         let initial_state = initial_state_with_trailing_zeros_in_xfe_poly_encoding();
-        let code = dequeue_next_as.link_for_isolated_run();
+        let (code, source_map) = dequeue_next_as.link_for_isolated_run_with_source_map();
         let tvm_result = execute_with_terminal_state(
             Program::new(&code),
+            &source_map,
             &[],
             &initial_state.stack,
             &initial_state.nondeterminism,
@@ -1,5 +1,5 @@
 //! This crate provides a derive macro for the `TasmObject` and `TasmStruct`
-//! traits.
+//! traits, as well as the `TasmEnum` trait.
 //!
 //! Example usage:
 //! ```no_compile
@@ -7,6 +7,12 @@
 //! struct Foo<T: BFieldCodec> {
 //!     t_list: Vec<T>,
 //! }
+//!
+//! #[derive(BFieldCodec, TasmObject)]
+//! enum Bar {
+//!     Baz,
+//!     Qux(u64, Vec<Digest>),
+//! }
 //! ```
 //!
 //! Note: An implementation of `BFieldCodec` is required, else compilation will
@@ -23,6 +29,9 @@
 //!    //         ~~~~~~~~~~~~~~~~~~~~~~
 //!    //         currently unsupported in tuple structs
 //!    ```
+//!
+//!  - Like `BFieldCodec`, enums with variants that have named fields are
+//!    currently not supported.
 
 extern crate proc_macro;
 
@@ -30,7 +39,8 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::DeriveInput;
 
-/// Derives both, `TasmObject` and `TasmStruct` for structs.
+/// Derives both, `TasmObject` and `TasmStruct` for structs, and both,
+/// `TasmObject` and `TasmEnum` for enums.
 #[proc_macro_derive(TasmObject, attributes(tasm_object))]
 pub fn tasm_object_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let ast = syn::parse(input).unwrap();
@@ -94,14 +104,13 @@ struct ParsedStruct {
     /// Variables with identifiers equal to the struct field identifiers and of the
     /// appropriate type must be in scope.
     struct_builder: TokenStream,
+
+    /// The rust code evaluating to the static length of the struct, if any.
+    static_length: TokenStream,
 }
 
 impl ParsedStruct {
-    fn new(ast: &DeriveInput) -> Self {
-        let syn::Data::Struct(syn::DataStruct { fields, .. }) = &ast.data else {
-            panic!("expected a struct")
-        };
-
+    fn new(fields: &syn::Fields) -> Self {
         match fields {
             syn::Fields::Named(fields) => Self::parse_struct_with_named_fields(fields),
             syn::Fields::Unnamed(fields) => Self::parse_tuple_struct(fields),
@@ -109,6 +118,50 @@ impl ParsedStruct {
         }
     }
 
+    /// Parse the associated data of an enum variant. Since the associated data
+    /// is encoded like a tuple struct, all the tasm-generating functions can be
+    /// re-used. Notably, the generated tasm code expects a pointer to the
+    /// variant's first field, _i.e._, a pointer past the discriminant.
+    fn new_for_enum_variant(variant: &syn::Variant) -> Self {
+        let variant_name = &variant.ident;
+        let mut parsed_variant = match &variant.fields {
+            syn::Fields::Named(_) => {
+                panic!("variant `{variant_name}` has named fields, which is unsupported")
+            }
+            syn::Fields::Unnamed(fields) => Self::parse_tuple_struct(fields),
+            syn::Fields::Unit => Self::unit(),
+        };
+
+        let fields_in_declared_order = parsed_variant.field_names.iter().rev();
+        parsed_variant.struct_builder = if parsed_variant.field_names.is_empty() {
+            quote! { Self::#variant_name }
+        } else {
+            quote! { Self::#variant_name( #( #fields_in_declared_order ),* ) }
+        };
+
+        let field_types = &parsed_variant.field_types;
+        parsed_variant.static_length = if field_types.is_empty() {
+            quote! { ::std::option::Option::<usize>::Some(0) }
+        } else {
+            quote! {
+                [
+                    #( <#field_types as crate::twenty_first::math::bfield_codec::BFieldCodec>
+                        ::static_length(), )*
+                ]
+                .into_iter()
+                .sum::<::std::option::Option<usize>>()
+            }
+        };
+
+        parsed_variant
+    }
+
+    fn static_length_of_self() -> TokenStream {
+        quote! {
+            <Self as crate::twenty_first::math::bfield_codec::BFieldCodec>::static_length()
+        }
+    }
+
     fn parse_struct_with_named_fields(fields: &syn::FieldsNamed) -> Self {
         let (ignored_fields, fields): (Vec<_>, Vec<_>) = fields
             .named
@@ -131,6 +184,7 @@ impl ParsedStruct {
             field_types,
             ignored_fields,
             struct_builder,
+            static_length: Self::static_length_of_self(),
         }
     }
 
@@ -153,6 +207,7 @@ impl ParsedStruct {
             field_types,
             ignored_fields: vec![],
             struct_builder,
+            static_length: Self::static_length_of_self(),
         }
     }
 
@@ -162,6 +217,7 @@ impl ParsedStruct {
             field_types: vec![],
             ignored_fields: vec![],
             struct_builder: quote! { Self },
+            static_length: Self::static_length_of_self(),
         }
     }
 
@@ -236,12 +292,11 @@ impl ParsedStruct {
                 }
             )
         };
+        let static_length = &self.static_length;
         let mut rust = quote! {
             #type_aliases
 
-            if let Some(size) =
-                <Self as crate::twenty_first::math::bfield_codec::BFieldCodec>::static_length()
-            {
+            if let Some(size) = #static_length {
                 return [
                     Instruction::Instruction(AnInstruction::Pop(N::N1)),
                     Instruction::Instruction(AnInstruction::Push(BFE::from(size))),
@@ -399,7 +454,7 @@ impl ParsedStruct {
     /// tasm code that
     /// - assumes the stack is in the state `_ *struct`
     /// - leaves the stack in the state `_ *field_i`
-    fn generate_code_for_fn_get_field(&self, struct_name: &syn::Ident) -> TokenStream {
+    fn generate_code_for_fn_get_field(&self, struct_name: &str) -> TokenStream {
         debug_assert_eq!(self.field_types.len(), self.field_names.len());
 
         let mut fields = self
@@ -409,7 +464,6 @@ impl ParsedStruct {
             .zip(&self.field_types);
 
         let Some(first_field) = fields.next_back() else {
-            let struct_name = struct_name.to_string();
             return quote!(panic!("type `{}` has no fields", #struct_name););
         };
 
@@ -476,7 +530,6 @@ impl ParsedStruct {
         }
 
         let (first_field_name, first_field_ty) = first_field;
-        let struct_name = struct_name.to_string();
         let first_field_type_hint = Self::top_of_stack_pointer_type_hint(&first_field_name);
         rust.extend(quote!(
             if field_name != #first_field_name {
@@ -507,7 +560,7 @@ impl ParsedStruct {
     /// generate tasm code that
     /// - assumes the stack is in the state `_ *struct`
     /// - leaves the stack in the state `_ *field_i size_of_field_i`
-    fn generate_code_for_fn_get_field_with_size(&self, struct_name: &syn::Ident) -> TokenStream {
+    fn generate_code_for_fn_get_field_with_size(&self, struct_name: &str) -> TokenStream {
         debug_assert_eq!(self.field_types.len(), self.field_names.len());
 
         let mut fields = self
@@ -516,7 +569,6 @@ impl ParsedStruct {
             .map(|n| n.to_string())
             .zip(&self.field_types);
         let Some(first_field) = fields.next_back() else {
-            let struct_name = struct_name.to_string();
             return quote!(panic!("type `{}` has no fields", #struct_name););
         };
 
@@ -603,7 +655,6 @@ impl ParsedStruct {
         }
 
         let (first_field_name, first_field_ty) = first_field;
-        let struct_name = struct_name.to_string();
         let first_field_type_hint = Self::top_of_stack_pointer_type_hint(&first_field_name);
         rust.extend(quote!(
             if field_name != #first_field_name {
@@ -758,8 +809,357 @@ impl ParsedStruct {
     }
 }
 
+// To follow along with the enum-specific functions, consider enum `Bar`:
+//
+// #[derive(BFieldCodec, TasmObject)]
+// enum Bar {
+//     Baz,
+//     Qux(Vec<u32>, XFieldElement),
+// }
+//
+// The encoding of `Bar::Qux(vec![40, 41], xfe!([42, 43, 44]))` looks like
+// this:
+//
+//     ╭─ field_1 ──╮     ╭─ field_0 ─╮
+//  1, 42, 43, 44, 3, 2, 40, 41
+//  ↑  ↑              ↑  ↑
+//  | *field_1        | *field_0
+//  |                *field_0_si
+// *discriminant
+//
+// That is, the discriminant is followed by the variant's associated data,
+// which is encoded like a tuple struct.
+//
+// A pointer `*bar` is equal to pointer `*discriminant`.
+//
+struct ParsedEnum {
+    /// The names of all variants, in declaration order. The index of a variant
+    /// is its discriminant.
+    variant_names: Vec<syn::Ident>,
+
+    /// The associated data of all variants, each parsed as if it was a tuple
+    /// struct.
+    ///
+    /// The order of the entries mimics the order of
+    /// [`variant_names`](Self::variant_names).
+    variants: Vec<ParsedStruct>,
+}
+
+impl ParsedEnum {
+    fn new(variants: &syn::punctuated::Punctuated<syn::Variant, syn::Token![,]>) -> Self {
+        if variants.is_empty() {
+            panic!("enums without variants are unsupported");
+        }
+
+        let variant_names = variants.iter().map(|v| v.ident.clone()).collect();
+        let variants = variants
+            .iter()
+            .map(ParsedStruct::new_for_enum_variant)
+            .collect();
+
+        Self {
+            variant_names,
+            variants,
+        }
+    }
+
+    fn all_field_types(&self) -> impl Iterator<Item = &syn::Type> {
+        self.variants.iter().flat_map(|v| v.field_types.iter())
+    }
+
+    /// Generate the rust code for `TasmObject::label_friendly_name()`.
+    ///
+    /// Since enum variants get verified in subroutines, the label must be unique
+    /// for every instantiation of a generic enum.
+    fn generate_code_for_fn_label_friendly_name(
+        &self,
+        enum_name: &syn::Ident,
+        generics: &syn::Generics,
+    ) -> TokenStream {
+        let enum_name = enum_name.to_string();
+        if generics.params.is_empty() {
+            return quote!(#enum_name.to_owned());
+        }
+
+        let field_types = self.all_field_types();
+        quote! {
+            let field_type_names: ::std::vec::Vec<::std::string::String> = [
+                #( <#field_types as crate::tasm_lib::structure::tasm_object::TasmObject>
+                    ::label_friendly_name(), )*
+            ].to_vec();
+            ::std::format!("{}_L_{}_R", #enum_name, field_type_names.join("_"))
+        }
+    }
+
+    /// Generate the rust code for `TasmObject::compute_size_and_assert_valid_size_indicator(…)`.
+    ///
+    /// The generated tasm code crashes the VM if the discriminant is out of
+    /// range. The size of each variant's associated data is computed in a
+    /// dedicated subroutine.
+    fn generate_code_for_fn_compute_size_and_assert_valid_size_indicator(&self) -> TokenStream {
+        let type_aliases = ParsedStruct::type_aliases();
+        let num_variants = self.variants.len() as u64;
+        let mut rust = quote! {
+            #type_aliases
+
+            let mut instructions = [
+                // _ *discriminant
+                Instruction::Instruction(AnInstruction::ReadMem(N::N1)),
+                // _ discriminant (*discriminant - 1)
+                Instruction::Instruction(AnInstruction::AddI(BFE::new(2))),
+                // _ discriminant *variant
+                Instruction::Instruction(AnInstruction::Push(BFE::new(#num_variants))),
+                Instruction::Instruction(AnInstruction::Dup(ST::ST2)),
+                Instruction::Instruction(AnInstruction::Lt),
+                Instruction::Instruction(AnInstruction::Assert),
+                Instruction::AssertionContext(AssertionContext::ID(186)),
+                // _ discriminant *variant
+            ].to_vec();
+        };
+
+        for (discriminant, (variant_name, variant)) in
+            self.variant_names.iter().zip(&self.variants).enumerate()
+        {
+            let discriminant = discriminant as u64;
+            let variant_name = variant_name.to_string();
+            let variant_size_code =
+                variant.generate_code_for_fn_compute_size_and_assert_valid_size_indicator();
+            rust.extend(quote! {
+                let label = ::std::format!(
+                    "tasmlib_tasmobject_size_verifier_{}_variant_{}",
+                    <Self as crate::tasm_lib::structure::tasm_object::TasmObject>
+                        ::label_friendly_name(),
+                    #variant_name,
+                );
+                let mut subroutine = [Instruction::Label(label.clone())].to_vec();
+                subroutine.extend((|| -> ::std::vec::Vec<Instruction> { #variant_size_code })());
+                subroutine.push(Instruction::Instruction(AnInstruction::Return));
+                library.explicit_import(&label, &subroutine);
+
+                instructions.extend([
+                    // _ discriminant (*variant | variant_size)
+                    Instruction::Instruction(AnInstruction::Dup(ST::ST1)),
+                    Instruction::Instruction(AnInstruction::Push(BFE::new(#discriminant))),
+                    Instruction::Instruction(AnInstruction::Eq),
+                    Instruction::Instruction(AnInstruction::Skiz),
+                    Instruction::Instruction(AnInstruction::Call(label)),
+                ]);
+            });
+        }
+
+        rust.extend(quote! {
+            instructions.extend([
+                // _ discriminant variant_size
+                Instruction::Instruction(AnInstruction::Pick(ST::ST1)),
+                Instruction::Instruction(AnInstruction::Pop(N::N1)),
+                // _ variant_size
+                Instruction::Instruction(AnInstruction::AddI(BFE::new(1))),
+                // _ (variant_size + 1)
+            ]);
+
+            instructions
+        });
+
+        rust
+    }
+
+    /// Generate the rust code for `TasmObject::decode_iter(…)`.
+    fn generate_code_for_fn_decode_iter(&self) -> TokenStream {
+        let match_arms = self
+            .variants
+            .iter()
+            .enumerate()
+            .map(|(discriminant, variant)| {
+                let field_decoders = variant
+                    .field_names
+                    .iter()
+                    .cloned()
+                    .zip(variant.field_types.iter().cloned())
+                    .map(|(field_name, field_ty)| field_decoder(field_name, field_ty));
+                let variant_builder = &variant.struct_builder;
+                quote! {
+                    #discriminant => {
+                        #( #field_decoders )*
+                        ::std::result::Result::Ok(::std::boxed::Box::new(#variant_builder))
+                    }
+                }
+            });
+
+        quote! {
+            let discriminant: usize = iterator.next().ok_or("iterator exhausted")?.try_into()?;
+            match discriminant {
+                #( #match_arms )*
+                _ => ::std::result::Result::Err("invalid discriminant".into()),
+            }
+        }
+    }
+
+    /// Generate the rust code for `TasmEnum::get_discriminant()`, which will
+    /// then generate tasm code that
+    /// - assumes the stack is in the state `_ *enum`
+    /// - leaves the stack in the state `_ discriminant`
+    fn generate_code_for_fn_get_discriminant() -> TokenStream {
+        let type_aliases = ParsedStruct::type_aliases();
+        quote! {
+            #type_aliases
+            [
+                // _ *discriminant
+                Instruction::Instruction(AnInstruction::ReadMem(N::N1)),
+                // _ discriminant (*discriminant - 1)
+                Instruction::Instruction(AnInstruction::Pop(N::N1)),
+                // _ discriminant
+            ]
+            .to_vec()
+        }
+    }
+
+    /// Generate the rust code for `TasmEnum::get_variant_field(…)` or, if
+    /// `with_size` is set, for `TasmEnum::get_variant_field_with_size(…)`.
+    ///
+    /// For example, calling `TasmEnum::get_variant_field("Qux", "field_1")` will
+    /// generate tasm code that
+    /// - assumes the stack is in the state `_ *enum`
+    /// - crashes the VM if the discriminant does not match variant `Qux`
+    /// - leaves the stack in the state `_ *field_1`
+    fn generate_code_for_fn_get_variant_field(
+        &self,
+        enum_name: &syn::Ident,
+        with_size: bool,
+    ) -> TokenStream {
+        let type_aliases = ParsedStruct::type_aliases();
+        let match_arms = self
+            .variant_names
+            .iter()
+            .zip(&self.variants)
+            .enumerate()
+            .map(|(discriminant, (variant_name, variant))| {
+                let discriminant = discriminant as u64;
+                let qualified_name = format!("{enum_name}::{variant_name}");
+                let field_getter = if with_size {
+                    variant.generate_code_for_fn_get_field_with_size(&qualified_name)
+                } else {
+                    variant.generate_code_for_fn_get_field(&qualified_name)
+                };
+                let variant_name = variant_name.to_string();
+                quote! {
+                    #variant_name => (
+                        #discriminant,
+                        (|field_name: &str| -> ::std::vec::Vec<Instruction> {
+                            #field_getter
+                        })(field_name),
+                    ),
+                }
+            });
+
+        let enum_name = enum_name.to_string();
+        quote! {
+            #type_aliases
+
+            let (discriminant, field_getter) = match variant_name {
+                #( #match_arms )*
+                _ => panic!("unknown variant `{variant_name}` for type `{}`", #enum_name),
+            };
+
+            let mut instructions = [
+                // _ *discriminant
+                Instruction::Instruction(AnInstruction::ReadMem(N::N1)),
+                // _ discriminant (*discriminant - 1)
+                Instruction::Instruction(AnInstruction::AddI(BFE::new(2))),
+                // _ discriminant *variant
+                Instruction::Instruction(AnInstruction::Pick(ST::ST1)),
+                Instruction::Instruction(AnInstruction::Push(BFE::new(discriminant))),
+                Instruction::Instruction(AnInstruction::Eq),
+                Instruction::Instruction(AnInstruction::Assert),
+                Instruction::AssertionContext(AssertionContext::ID(187)),
+                // _ *variant
+            ].to_vec();
+            instructions.extend(field_getter);
+            instructions
+        }
+    }
+}
+
 fn impl_tasm_object_derive_macro(ast: DeriveInput) -> TokenStream {
-    let parsed_struct = ParsedStruct::new(&ast);
+    match &ast.data {
+        syn::Data::Struct(syn::DataStruct { fields, .. }) => impl_derive_for_struct(&ast, fields),
+        syn::Data::Enum(syn::DataEnum { variants, .. }) => impl_derive_for_enum(&ast, variants),
+        syn::Data::Union(_) => panic!("expected a struct or an enum"),
+    }
+}
+
+fn impl_derive_for_enum(
+    ast: &DeriveInput,
+    variants: &syn::punctuated::Punctuated<syn::Variant, syn::Token![,]>,
+) -> TokenStream {
+    let parsed_enum = ParsedEnum::new(variants);
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let code_for_fn_label_friendly_name =
+        parsed_enum.generate_code_for_fn_label_friendly_name(name, &ast.generics);
+    let code_for_fn_compute_size_and_assert_valid_size_indicator =
+        parsed_enum.generate_code_for_fn_compute_size_and_assert_valid_size_indicator();
+    let code_for_fn_decode_iter = parsed_enum.generate_code_for_fn_decode_iter();
+    let code_for_fn_get_discriminant = ParsedEnum::generate_code_for_fn_get_discriminant();
+    let code_for_fn_get_variant_field =
+        parsed_enum.generate_code_for_fn_get_variant_field(name, false);
+    let code_for_fn_get_variant_field_with_size =
+        parsed_enum.generate_code_for_fn_get_variant_field(name, true);
+
+    quote! {
+        impl #impl_generics crate::tasm_lib::structure::tasm_object::TasmObject
+        for #name #ty_generics #where_clause {
+            fn label_friendly_name() -> String {
+                #code_for_fn_label_friendly_name
+            }
+
+            fn compute_size_and_assert_valid_size_indicator(
+                library: &mut crate::tasm_lib::library::Library
+            ) -> ::std::vec::Vec<crate::triton_vm::isa::instruction::LabelledInstruction> {
+                #code_for_fn_compute_size_and_assert_valid_size_indicator
+            }
+
+            fn decode_iter<Itr: Iterator<Item = crate::triton_vm::prelude::BFieldElement>>(
+                iterator: &mut Itr
+            ) -> ::std::result::Result<
+                    ::std::boxed::Box<Self>,
+                    ::std::boxed::Box<
+                        dyn ::std::error::Error
+                        + ::core::marker::Send
+                        + ::core::marker::Sync
+                    >
+                >
+            {
+                #code_for_fn_decode_iter
+            }
+        }
+
+        impl #impl_generics crate::tasm_lib::structure::tasm_object::TasmEnum
+        for #name #ty_generics #where_clause {
+            fn get_discriminant(
+            ) -> ::std::vec::Vec<crate::triton_vm::isa::instruction::LabelledInstruction> {
+                #code_for_fn_get_discriminant
+            }
+
+            fn get_variant_field(
+                variant_name: &str,
+                field_name: &str,
+            ) -> ::std::vec::Vec<crate::triton_vm::isa::instruction::LabelledInstruction> {
+                #code_for_fn_get_variant_field
+            }
+
+            fn get_variant_field_with_size(
+                variant_name: &str,
+                field_name: &str,
+            ) -> ::std::vec::Vec<crate::triton_vm::isa::instruction::LabelledInstruction> {
+                #code_for_fn_get_variant_field_with_size
+            }
+        }
+    }
+}
+
+fn impl_derive_for_struct(ast: &DeriveInput, fields: &syn::Fields) -> TokenStream {
+    let parsed_struct = ParsedStruct::new(fields);
     let name = &ast.ident;
     let name_as_string = ast.ident.to_string();
 
@@ -781,9 +1181,9 @@ fn impl_tasm_object_derive_macro(ast: DeriveInput) -> TokenStream {
 
     let code_for_fn_compute_size_and_assert_valid_size_indicator =
         parsed_struct.generate_code_for_fn_compute_size_and_assert_valid_size_indicator();
-    let code_for_fn_get_field = parsed_struct.generate_code_for_fn_get_field(&ast.ident);
+    let code_for_fn_get_field = parsed_struct.generate_code_for_fn_get_field(&name_as_string);
     let code_for_fn_get_field_with_size =
-        parsed_struct.generate_code_for_fn_get_field_with_size(&ast.ident);
+        parsed_struct.generate_code_for_fn_get_field_with_size(&name_as_string);
     let code_for_fn_destructure = parsed_struct.generate_code_for_fn_destructure();
 
     quote! {
@@ -851,7 +1251,11 @@ fn field_decoder(field_name: syn::Ident, field_type: syn::Type) -> TokenStream {
             .collect::<::std::option::Option<::std::vec::Vec<_>>>()
             .ok_or("iterator exhausted")?;
         let #field_name : #field_type =
-            *crate::twenty_first::math::bfield_codec::BFieldCodec::decode(&sequence)?;
+            *<#field_type as crate::twenty_first::math::bfield_codec::BFieldCodec>
+                ::decode(&sequence)
+                .map_err(|err| -> ::std::boxed::Box<
+                    dyn ::std::error::Error + ::core::marker::Send + ::core::marker::Sync
+                > { err.into() })?;
     }
 }
 
@@ -894,7 +1298,6 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "expected a struct")] // enums are not supported (yet?)
     fn enum_with_tuple_variants() {
         let ast = parse_quote! {
             #[derive(TasmObject)]
@@ -933,7 +1336,6 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "expected a struct")] // enums are not supported (yet?)
     fn generic_enum() {
         let ast = parse_quote! {
             #[derive(TasmObject)]
@@ -948,6 +1350,40 @@ mod tests {
         let _rust_code = impl_tasm_object_derive_macro(ast);
     }
 
+    #[test]
+    fn enum_with_unit_variants_only() {
+        let ast = parse_quote! {
+            #[derive(TasmObject)]
+            enum Enum {
+                Variant1,
+                Variant2,
+            }
+        };
+        let _rust_code = impl_tasm_object_derive_macro(ast);
+    }
+
+    #[test]
+    #[should_panic(expected = "named fields")] // like `BFieldCodec`
+    fn enum_with_struct_variant() {
+        let ast = parse_quote! {
+            #[derive(TasmObject)]
+            enum Enum {
+                Variant1 { field: u64 },
+            }
+        };
+        let _rust_code = impl_tasm_object_derive_macro(ast);
+    }
+
+    #[test]
+    #[should_panic(expected = "without variants")]
+    fn enum_without_variants() {
+        let ast = parse_quote! {
+            #[derive(TasmObject)]
+            enum Enum {}
+        };
+        let _rust_code = impl_tasm_object_derive_macro(ast);
+    }
+
     #[test]
     fn struct_with_types_from_twenty_first() {
         let ast = parse_quote! {